@@ -1,13 +1,42 @@
 pub mod area;
 pub mod collapsing_header;
+pub mod combo_box;
 pub mod frame;
+pub mod group_box;
+pub mod hover_card;
+pub mod list_box;
 pub mod menu;
+pub mod move_animation;
 pub mod popup;
 pub mod resize;
 pub mod scroll_area;
+pub mod side_panel;
+pub mod splitter;
+pub mod table;
+pub mod timeline;
+pub mod top_bottom_panel;
+pub mod tree_view;
+pub mod virtual_keyboard;
 pub mod window;
 
 pub use {
-    area::Area, collapsing_header::CollapsingHeader, frame::Frame, popup::*, resize::Resize,
-    scroll_area::ScrollArea, window::Window,
+    area::Area,
+    collapsing_header::CollapsingHeader,
+    combo_box::combo_box,
+    frame::Frame,
+    group_box::GroupBox,
+    hover_card::HoverCard,
+    list_box::{ListBox, ListBoxResponse},
+    move_animation::MoveAnimation,
+    popup::*,
+    resize::Resize,
+    scroll_area::{ScrollArea, ScrollAreaResponse},
+    side_panel::SidePanel,
+    splitter::Splitter,
+    table::{Column, Table},
+    timeline::{Timeline, TimelineUi, TrackUi},
+    top_bottom_panel::{BottomPanel, TopPanel},
+    tree_view::{TreeUi, TreeView},
+    virtual_keyboard::{KeyboardLayout, VirtualKeyboard},
+    window::Window,
 };
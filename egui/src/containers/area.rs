@@ -39,6 +39,9 @@ pub struct Area {
     order: Order,
     default_pos: Option<Pos2>,
     fixed_pos: Option<Pos2>,
+    anchor: Option<((Align, Align), Vec2)>,
+    constrain: bool,
+    style_variant: Option<&'static str>,
 }
 
 impl Area {
@@ -50,6 +53,9 @@ impl Area {
             order: Order::Middle,
             default_pos: None,
             fixed_pos: None,
+            anchor: None,
+            constrain: true,
+            style_variant: None,
         }
     }
 
@@ -97,12 +103,45 @@ impl Area {
         self.movable = false;
         self
     }
+
+    /// Pin a corner or edge of the area to the same corner or edge of the screen,
+    /// e.g. `anchor((Align::Max, Align::Min), [-8.0, 8.0])` for the top right corner.
+    /// `offset` moves it from there. Like `fixed_pos`, this is not movable.
+    pub fn anchor(mut self, align: (Align, Align), offset: impl Into<Vec2>) -> Self {
+        self.anchor = Some((align, offset.into()));
+        self.movable = false;
+        self
+    }
+
+    /// Keep the top of the area (e.g. a window title bar) on screen,
+    /// both when dragged and when the screen shrinks. On by default.
+    pub fn constrain(mut self, constrain: bool) -> Self {
+        self.constrain = constrain;
+        self
+    }
+
+    /// Use the style registered under this name with `Context::set_style_variant`
+    /// instead of the default style.
+    pub fn style_variant(mut self, name: &'static str) -> Self {
+        self.style_variant = Some(name);
+        self
+    }
+
+    /// The style the contents will have.
+    pub fn style(&self, ctx: &Context) -> Style {
+        match self.style_variant {
+            Some(name) => ctx.style_variant(name),
+            None => ctx.style(),
+        }
+    }
 }
 
 pub(crate) struct Prepared {
     layer: Layer,
     state: State,
     movable: bool,
+    constrain: bool,
+    style: Style,
 }
 
 impl Area {
@@ -114,7 +153,11 @@ impl Area {
             interactable,
             default_pos,
             fixed_pos,
+            anchor,
+            constrain,
+            style_variant: _,
         } = self;
+        let style = self.style(ctx);
 
         let layer = Layer { order, id };
 
@@ -126,12 +169,27 @@ impl Area {
             vel: Vec2::zero(),
         });
         state.pos = fixed_pos.unwrap_or(state.pos);
+        if let Some((align, offset)) = anchor {
+            // Uses the size of last frame:
+            let screen = ctx.rect();
+            let anchor_pos = pos2(
+                align_range(align.0, screen.left(), screen.right()),
+                align_range(align.1, screen.top(), screen.bottom()),
+            ) + offset;
+            state.pos = align_rect(Rect::from_min_size(anchor_pos, state.size), align).min;
+        }
+        if constrain {
+            // The screen may have shrunk since we were last shown:
+            state.pos = constrain_to_screen(ctx, state.pos, state.size);
+        }
         state.pos = state.pos.round();
 
         Prepared {
             layer,
             state,
             movable,
+            constrain,
+            style,
         }
     }
 
@@ -153,12 +211,14 @@ impl Prepared {
     }
 
     pub(crate) fn content_ui(&self, ctx: &Arc<Context>) -> Ui {
-        Ui::new(
+        let mut ui = Ui::new(
             ctx.clone(),
             self.layer,
             self.layer.id,
             Rect::from_min_size(self.state.pos, Vec2::infinity()),
-        )
+        );
+        ui.set_style(self.style.clone());
+        ui
     }
 
     pub(crate) fn end(self, ctx: &Arc<Context>, content_ui: Ui) -> InteractInfo {
@@ -166,6 +226,8 @@ impl Prepared {
             layer,
             mut state,
             movable,
+            constrain,
+            style: _,
         } = self;
 
         state.size = (content_ui.child_bounds().max - state.pos).ceil();
@@ -185,6 +247,7 @@ impl Prepared {
         if move_interact.active {
             state.pos += input.mouse.delta;
             state.vel = input.mouse.velocity;
+            ctx.output().cursor_icon = CursorIcon::Grabbing;
         } else {
             let stop_speed = 20.0; // Pixels per second.
             let friction_coeff = 1000.0; // Pixels per second squared.
@@ -198,14 +261,9 @@ impl Prepared {
             }
         }
 
-        // Constrain to screen:
-        let margin = 32.0;
-        state.pos = state.pos.max(pos2(margin - state.size.x, 0.0));
-        state.pos = state.pos.min(pos2(
-            ctx.input().screen_size.x - margin,
-            ctx.input().screen_size.y - margin,
-        ));
-
+        if constrain {
+            state.pos = constrain_to_screen(ctx, state.pos, state.size);
+        }
         state.pos = state.pos.round();
 
         // ctx.debug_rect(
@@ -225,6 +283,23 @@ impl Prepared {
     }
 }
 
+/// Keeps at least `margin` of the width and the top `margin` of the height on screen,
+/// so that there is always something to drag it back by.
+fn constrain_to_screen(ctx: &Context, pos: Pos2, size: Vec2) -> Pos2 {
+    let margin = 32.0;
+    let screen_size = ctx.input().screen_size;
+    pos.max(pos2(margin - size.x, 0.0))
+        .min(pos2(screen_size.x - margin, screen_size.y - margin))
+}
+
+fn align_range(align: Align, min: f32, max: f32) -> f32 {
+    match align {
+        Align::Min => min,
+        Align::Center => 0.5 * (min + max),
+        Align::Max => max,
+    }
+}
+
 fn mouse_pressed_on_area(ctx: &Context, layer: Layer) -> bool {
     if let Some(mouse_pos) = ctx.input().mouse.pos {
         ctx.input().mouse.pressed && ctx.layer_at(mouse_pos) == Some(layer)
@@ -54,7 +54,7 @@ impl State {
     pub fn openness(&self, ui: &Ui) -> f32 {
         let animation_time = ui.style().animation_time;
         let time_since_toggle = (ui.input().time - self.toggle_time) as f32;
-        let time_since_toggle = time_since_toggle + ui.input().dt; // Instant feedback
+        let time_since_toggle = time_since_toggle + ui.input().predicted_dt; // Instant feedback
         if self.open {
             remap_clamp(time_since_toggle, 0.0..=animation_time, 0.0..=1.0)
         } else {
@@ -180,6 +180,7 @@ impl CollapsingHeader {
 
         let title = label.text();
         let id = ui.make_unique_child_id(title);
+        ui.register_widget_label(id, title);
 
         let available = ui.available_finite();
         let text_pos = available.min + vec2(ui.style().indent, 0.0);
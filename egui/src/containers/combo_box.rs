@@ -0,0 +1,112 @@
+use crate::{paint::*, *};
+
+use super::*;
+
+/// A button showing the `selected` text which, when clicked, opens a popup below it.
+/// The popup closes again on the next click (e.g. when you pick something) or on escape.
+///
+/// Usage:
+/// ```ignore
+/// combo_box(ui, ui.make_child_id("fruit"), &fruit, |ui| {
+///     for option in &["Apple", "Banana", "Cherry"] {
+///         if ui.radio(*option, fruit == *option).clicked {
+///             fruit = option.to_string();
+///         }
+///     }
+/// });
+/// ```
+pub fn combo_box(
+    ui: &mut Ui,
+    popup_id: Id,
+    selected: impl Into<String>,
+    menu_contents: impl FnOnce(&mut Ui),
+) -> GuiResponse {
+    combo_box_impl(ui, popup_id, selected, |ui| {
+        menu_contents(ui);
+        false
+    })
+}
+
+/// Like `combo_box`, but `menu_contents` returns true to keep the popup open
+/// even though there was a click this frame (e.g. on a search field in it).
+pub(crate) fn combo_box_impl(
+    ui: &mut Ui,
+    popup_id: Id,
+    selected: impl Into<String>,
+    menu_contents: impl FnOnce(&mut Ui) -> bool,
+) -> GuiResponse {
+    let button_interact = combo_box_button(ui, popup_id, selected.into());
+    let button_response = ui.response(button_interact);
+
+    if button_response.clicked {
+        ui.memory().toggle_popup(popup_id);
+    }
+
+    if ui.memory().is_popup_open(popup_id) {
+        let button_width = button_response.rect.width();
+        let mut keep_open = false;
+        Area::new(popup_id)
+            .order(Order::Foreground)
+            .fixed_pos(button_response.rect.left_bottom())
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_desired_width(button_width - 2.0 * ui.style().window_padding.x);
+                    keep_open = menu_contents(ui);
+                })
+            });
+
+        let pressed_escape = ui.ctx().consume_key(Key::Escape);
+        let clicked_elsewhere = ui.input().mouse.click && !button_response.clicked && !keep_open;
+        if pressed_escape || clicked_elsewhere {
+            ui.memory().close_popup();
+        }
+    }
+
+    button_response
+}
+
+fn combo_box_button(ui: &mut Ui, id: Id, text: String) -> InteractInfo {
+    let text_style = TextStyle::Button;
+    let font = &ui.fonts()[text_style];
+    let galley = font.layout_single_line(text);
+
+    let padding = ui.style().button_padding;
+    let icon_width = ui.style().start_icon_width;
+    let mut size = galley.size + 2.0 * padding + vec2(icon_width, 0.0);
+    size.y = size.y.max(ui.style().clickable_diameter);
+    let rect = ui.allocate_space(size);
+    let interact = ui.interact(rect, id, Sense::click());
+
+    let style = *ui.style().interact(&interact);
+    ui.add_paint_cmd(PaintCmd::Rect {
+        corner_radius: style.corner_radius,
+        fill: style.bg_fill,
+        outline: style.rect_outline,
+        rect: interact.rect,
+    });
+
+    // Down-pointing arrow at the right side:
+    let (small_icon_rect, _) = ui.style().icon_rectangles(interact.rect);
+    let icon_rect = Rect::from_center_size(
+        pos2(
+            interact.rect.right() - padding.x - 0.5 * icon_width,
+            interact.rect.center().y,
+        ),
+        small_icon_rect.size(),
+    );
+    ui.add_paint_cmd(PaintCmd::Path {
+        path: Path::from_point_loop(&[
+            icon_rect.left_top(),
+            icon_rect.right_top(),
+            icon_rect.center_bottom(),
+        ]),
+        closed: true,
+        fill: Some(style.stroke_color),
+        outline: None,
+    });
+
+    let text_cursor = interact.rect.left_center() + vec2(padding.x, -0.5 * galley.size.y);
+    ui.add_galley(text_cursor, galley, text_style, Some(style.stroke_color));
+
+    interact
+}
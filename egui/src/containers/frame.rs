@@ -9,6 +9,7 @@ pub struct Frame {
     pub corner_radius: f32,
     pub fill: Option<Color>,
     pub outline: Option<LineStyle>,
+    pub shadow: Option<Shadow>,
 }
 
 impl Frame {
@@ -18,6 +19,7 @@ impl Frame {
             corner_radius: style.window.corner_radius,
             fill: Some(style.background_fill),
             outline: style.interact.inactive.rect_outline, // becauce we can resize windows
+            shadow: style.window.shadow,
         }
     }
 
@@ -27,6 +29,7 @@ impl Frame {
             corner_radius: 0.0,
             fill: None,
             outline: Some(LineStyle::new(0.5, color::white(128))),
+            shadow: None,
         }
     }
 
@@ -36,6 +39,7 @@ impl Frame {
             corner_radius: 2.0,
             fill: Some(style.background_fill),
             outline: Some(LineStyle::new(1.0, color::white(128))),
+            shadow: style.window.shadow,
         }
     }
 
@@ -45,6 +49,18 @@ impl Frame {
             corner_radius: 5.0,
             fill: Some(style.background_fill),
             outline: Some(LineStyle::new(1.0, color::white(128))),
+            shadow: style.window.shadow,
+        }
+    }
+
+    /// For grouping related widgets. See `GroupBox`.
+    pub fn group(style: &Style) -> Self {
+        Self {
+            margin: style.group_box.margin,
+            corner_radius: style.group_box.corner_radius,
+            fill: style.group_box.fill,
+            outline: style.group_box.outline,
+            shadow: None,
         }
     }
 
@@ -57,6 +73,11 @@ impl Frame {
         self.outline = outline;
         self
     }
+
+    pub fn shadow(mut self, shadow: Option<Shadow>) -> Self {
+        self.shadow = shadow;
+        self
+    }
 }
 
 pub struct Prepared {
@@ -114,6 +135,16 @@ impl Prepared {
                 rect: outer_rect,
             },
         );
+        if let Some(shadow) = frame.shadow {
+            // The shadow reaches outside of the frame, so it must not be clipped to it:
+            let clip_rect = ui.clip_rect();
+            ui.set_clip_rect(clip_rect.expand(shadow.extent()));
+            ui.insert_paint_cmd(
+                where_to_put_background,
+                shadow.paint_cmd(outer_rect, frame.corner_radius),
+            );
+            ui.set_clip_rect(clip_rect);
+        }
 
         ui.expand_to_include_child(outer_rect);
         // TODO: move cursor in parent ui
@@ -0,0 +1,49 @@
+//! Group box container
+
+use crate::{widgets::*, *};
+
+/// A rounded border, with an optional title, around related widgets.
+/// Margin, fill and outline come from `Style::group_box`, unless you give it a `frame`.
+///
+/// ```ignore
+/// GroupBox::new().title("Audio").show(ui, |ui| {
+///     ui.add(Slider::f32(&mut volume, 0.0..=1.0).text("volume"));
+///     ui.add(Checkbox::new(&mut muted, "muted"));
+/// });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GroupBox {
+    title: Option<String>,
+    frame: Option<Frame>,
+}
+
+impl GroupBox {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Shown at the top, inside the border.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Use this instead of `Frame::group`, e.g. for a different fill.
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Rect) {
+        let GroupBox { title, frame } = self;
+        let frame = frame.unwrap_or_else(|| Frame::group(ui.style()));
+        ui.add_custom(|ui| {
+            frame.show(ui, |ui| {
+                if let Some(title) = title {
+                    ui.add(Label::new(title).text_style(TextStyle::Heading));
+                }
+                add_contents(ui)
+            })
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::*;
+
+/// A caller-provided card for a term, as registered with e.g. `MarkdownLabel::hover_card`.
+pub(crate) type CardContents<'a> = Box<dyn Fn(&mut Ui) + 'a>;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct State {
+    /// When the mouse started hovering the term.
+    hover_start: f64,
+    /// The term the card belongs to. The card is placed below it.
+    anchor: Rect,
+}
+
+/// A card with more information about a term (e.g. its documentation or a preview),
+/// shown when the term has been hovered for a little while.
+/// The card stays open while the mouse is over the term or the card,
+/// so you can move the mouse into the card and interact with it.
+///
+/// Usually you register terms with `MarkdownLabel::hover_card` or `CodeEditor::hover_card`
+/// instead of using this directly.
+///
+/// ```ignore
+/// let response = ui.label("egui");
+/// let hovered_rect = Some(response.rect).filter(|_| response.hovered);
+/// HoverCard::new("egui_card").show(ui.ctx(), hovered_rect, |ui| {
+///     ui.label("An immediate mode GUI library");
+/// });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct HoverCard {
+    id: Id,
+    delay: f32,
+}
+
+impl HoverCard {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            delay: 0.5,
+        }
+    }
+
+    /// How long (in seconds) the term must be hovered before the card opens.
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// `hovered_rect`: the rect of the term, if the mouse is over it this frame.
+    /// Returns true if the card is open.
+    pub fn show(
+        self,
+        ctx: &Arc<Context>,
+        hovered_rect: Option<Rect>,
+        add_contents: impl FnOnce(&mut Ui),
+    ) -> bool {
+        let HoverCard { id, delay } = self;
+        let time = ctx.input().time;
+        let state = ctx.memory().hover_cards.get(&id).copied();
+
+        let is_open = |state: &State| time - state.hover_start >= delay as f64;
+        let mouse_on_card = match (state, ctx.input().mouse.pos) {
+            (Some(state), Some(mouse_pos)) if is_open(&state) => matches!(
+                ctx.memory().areas.get(id),
+                Some(area) if area.rect().contains(mouse_pos)
+            ),
+            _ => false,
+        };
+
+        let state = match (state, hovered_rect) {
+            (Some(state), _) if mouse_on_card => state,
+            (Some(state), Some(_)) => state,
+            (None, Some(anchor)) => State {
+                hover_start: time,
+                anchor,
+            },
+            (_, None) => {
+                ctx.memory().hover_cards.remove(&id);
+                return false;
+            }
+        };
+        ctx.memory().hover_cards.insert(id, state);
+
+        if !is_open(&state) {
+            return false;
+        }
+
+        let pos = popup_pos_below(ctx, id, state.anchor);
+        Area::new(id)
+            .order(Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| Frame::popup(ui.style()).show(ui, add_contents));
+        true
+    }
+}
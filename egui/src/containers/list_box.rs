@@ -0,0 +1,193 @@
+use std::{collections::BTreeSet, hash::Hash};
+
+use crate::{paint::*, *};
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+pub(crate) struct State {
+    /// Indices of the selected items.
+    selected: BTreeSet<usize>,
+    /// Where shift-click (and shift + arrow key) range selections start.
+    anchor: Option<usize>,
+    /// The item last clicked or moved to with the arrow keys.
+    cursor: Option<usize>,
+}
+
+impl State {
+    fn select_only(&mut self, index: usize) {
+        self.selected.clear();
+        self.selected.insert(index);
+        self.anchor = Some(index);
+        self.cursor = Some(index);
+    }
+
+    fn toggle(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+        self.anchor = Some(index);
+        self.cursor = Some(index);
+    }
+
+    /// Select everything between the anchor and `index`, and nothing else.
+    fn select_range(&mut self, index: usize) {
+        let anchor = self.anchor.unwrap_or(index);
+        self.selected = (anchor.min(index)..=anchor.max(index)).collect();
+        self.anchor = Some(anchor);
+        self.cursor = Some(index);
+    }
+}
+
+/// A list of items to select from.
+///
+/// Click an item to select it.
+/// With `multi_select`, ctrl-click (cmd-click on Mac) toggles an item
+/// and shift-click selects all items from the last clicked one.
+/// Once clicked, the up and down arrow keys move the selection (extending it while holding shift).
+///
+/// The selection is stored in `Memory`, as indices into `items`.
+///
+/// ```ignore
+/// let fruits = ["Apple", "Banana", "Cherry"];
+/// let response = ListBox::new("fruits").multi_select(true).show(ui, &fruits);
+/// for index in response.selected {
+///     ui.add(label!("You picked {}", fruits[index]));
+/// }
+/// ```
+pub struct ListBox {
+    id_source: Id,
+    multi_select: bool,
+}
+
+/// What `ListBox::show` returns.
+pub struct ListBoxResponse {
+    /// The indices of the selected items, in order.
+    pub selected: Vec<usize>,
+    /// Did the selection change this frame?
+    pub changed: bool,
+}
+
+impl ListBox {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            multi_select: false,
+        }
+    }
+
+    /// Allow selecting more than one item. Off by default.
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, items: &[impl ToString]) -> ListBoxResponse {
+        let Self {
+            id_source,
+            multi_select,
+        } = self;
+
+        let id = ui.make_child_id(id_source);
+        let mut state = ui.memory().list_boxes.get(&id).cloned().unwrap_or_default();
+        // The list may have shrunk since last frame:
+        state.selected.retain(|&index| index < items.len());
+        state.anchor = state.anchor.filter(|&index| index < items.len());
+        state.cursor = state.cursor.filter(|&index| index < items.len());
+        let selected_before = state.selected.clone();
+
+        ui.vertical(|ui| {
+            let mut style = ui.style().clone();
+            style.item_spacing.y = 0.0;
+            ui.set_style(style);
+
+            for (index, item) in items.iter().enumerate() {
+                let interact = item_ui(ui, id.with(index), item.to_string(), &state, index);
+                if interact.clicked {
+                    let modifiers = ui.input().modifiers;
+                    if multi_select && modifiers.shift {
+                        state.select_range(index);
+                    } else if multi_select && modifiers.command() {
+                        state.toggle(index);
+                    } else {
+                        state.select_only(index);
+                    }
+                    ui.request_kb_focus(id);
+                }
+            }
+        });
+
+        if ui.has_kb_focus(id) {
+            keyboard_navigation(ui.input(), &mut state, multi_select, items.len());
+        }
+
+        let response = ListBoxResponse {
+            selected: state.selected.iter().copied().collect(),
+            changed: state.selected != selected_before,
+        };
+        ui.memory().list_boxes.insert(id, state);
+        response
+    }
+}
+
+fn item_ui(ui: &mut Ui, id: Id, text: String, state: &State, index: usize) -> InteractInfo {
+    let text_style = TextStyle::Body;
+    let galley = ui.fonts()[text_style].layout_single_line(text);
+    let padding = ui.style().button_padding;
+    let row_height = galley.size.y + 2.0;
+    let rect = ui.allocate_space(vec2(ui.available().width(), row_height));
+    let interact = ui.interact(rect, id, Sense::click());
+
+    let selected = state.selected.contains(&index);
+    let style = ui.style();
+    let fill = if selected {
+        Some(style.selection_color)
+    } else if interact.hovered || interact.active {
+        Some(color::gray(128, 32))
+    } else {
+        None
+    };
+    let text_color = if selected || interact.hovered {
+        style.interact.hovered.stroke_color
+    } else {
+        style.text_color
+    };
+
+    if fill.is_some() {
+        ui.add_paint_cmd(PaintCmd::Rect {
+            rect,
+            corner_radius: 2.0,
+            fill,
+            outline: None,
+        });
+    }
+
+    let text_pos = pos2(
+        rect.left() + padding.x,
+        rect.center().y - 0.5 * galley.size.y,
+    );
+    ui.add_galley(text_pos, galley, text_style, Some(text_color));
+    interact
+}
+
+fn keyboard_navigation(input: &InputState, state: &mut State, multi_select: bool, len: usize) {
+    let mut cursor = match state.cursor {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    for event in &input.events {
+        if let Event::Key { key, pressed: true } = event {
+            match key {
+                Key::Up if cursor > 0 => cursor -= 1,
+                Key::Down if cursor + 1 < len => cursor += 1,
+                _ => continue,
+            }
+            if multi_select && input.modifiers.shift {
+                state.select_range(cursor);
+            } else {
+                state.select_only(cursor);
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::{widgets::*, *};
 
 use super::*;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct BarState {
     #[cfg_attr(feature = "with_serde", serde(skip))]
@@ -10,6 +10,12 @@ pub struct BarState {
     #[cfg_attr(feature = "with_serde", serde(skip))]
     /// When did we open a menu?
     open_time: f64,
+    /// The item of the open menu picked with the arrow keys, counting from the top.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    highlighted: Option<usize>,
+    /// The menus of the bar, left to right, with the letter that opens them together with alt.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    menus: Vec<(Id, Option<char>)>,
 }
 
 impl Default for BarState {
@@ -17,10 +23,24 @@ impl Default for BarState {
         Self {
             open_menu: None,
             open_time: f64::NEG_INFINITY,
+            highlighted: None,
+            menus: vec![],
         }
     }
 }
 
+impl BarState {
+    fn open_with_keyboard(&mut self, menu_id: Id, time: f64) {
+        self.open_menu = Some(menu_id);
+        self.open_time = time;
+        self.highlighted = Some(0);
+    }
+}
+
+/// A menu bar: a horizontal row of `menu`:s.
+///
+/// The menus can also be used with the keyboard: alt and the first letter of a menu title opens it,
+/// the arrow keys move between menus and their items, return picks an item and escape closes.
 pub fn bar<R>(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Rect) {
     ui.inner_layout(Layout::horizontal(Align::Center), |ui| {
         Frame::menu_bar(ui.style()).show(ui, |ui| {
@@ -38,7 +58,15 @@ pub fn bar<R>(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Rect)
             let height = ui.style().menu_bar.height;
             ui.set_desired_height(height);
             ui.expand_to_size(vec2(ui.available().width(), height));
-            add_contents(ui)
+
+            // Each menu adds itself to the bar again, in order:
+            let bar_id = ui.id();
+            if let Some(bar_state) = ui.memory().menu_bar.get_mut(&bar_id) {
+                bar_state.menus.clear();
+            }
+            let ret = add_contents(ui);
+            bar_keyboard_ui(ui, bar_id);
+            ret
         })
     })
 }
@@ -63,6 +91,11 @@ fn menu_impl<'c>(
         .get(&bar_id)
         .cloned()
         .unwrap_or_default();
+    let mnemonic = title
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .map(|c| c.to_ascii_lowercase());
+    bar_state.menus.push((menu_id, mnemonic));
 
     let mut button = Button::new(title);
 
@@ -78,29 +111,17 @@ fn menu_impl<'c>(
         let area = Area::new(menu_id)
             .order(Order::Foreground)
             .fixed_pos(button_interact.rect.left_bottom());
-        let frame = Frame::menu(ui.style());
-
-        let resize = Resize::default().auto_sized().outline(false);
-
-        let menu_interact = area.show(ui.ctx(), |ui| {
-            frame.show(ui, |ui| {
-                resize.show(ui, |ui| {
-                    let mut style = ui.style().clone();
-                    style.button_padding = vec2(2.0, 0.0);
-                    // style.interact.active.bg_fill = None;
-                    style.interact.active.rect_outline = None;
-                    // style.interact.hovered.bg_fill = None;
-                    style.interact.hovered.rect_outline = None;
-                    style.interact.inactive.bg_fill = None;
-                    style.interact.inactive.rect_outline = None;
-                    ui.set_style(style);
-                    ui.set_layout(Layout::justified(Direction::Vertical));
-                    add_contents(ui)
-                })
-            })
-        });
+        highlight_menu_item(ui, &mut bar_state, area.layer());
+        let menu_interact = area.show(ui.ctx(), |ui| menu_frame(ui, add_contents));
 
-        if menu_interact.hovered && ui.input().mouse.released {
+        // The highlighted item has keyboard focus, so it was just clicked by this:
+        let picked_with_keyboard = bar_state.highlighted.is_some()
+            && ui
+                .input()
+                .events
+                .iter()
+                .any(crate::context::is_activation_event);
+        if (menu_interact.hovered && ui.input().mouse.released) || picked_with_keyboard {
             bar_state.open_menu = None;
         }
     }
@@ -108,12 +129,40 @@ fn menu_impl<'c>(
     ui.memory().menu_bar.insert(bar_id, bar_state);
 }
 
+/// The frame and style of a menu: a vertical list of buttons.
+/// Also used for context menus.
+pub(crate) fn menu_frame(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
+    let frame = Frame::menu(ui.style());
+    let resize = Resize::default().auto_sized().outline(false);
+
+    frame.show(ui, |ui| {
+        resize.show(ui, |ui| {
+            let mut style = ui.style().clone();
+            style.button_padding = vec2(2.0, 0.0);
+            // style.interact.active.bg_fill = None;
+            style.interact.active.rect_outline = None;
+            // style.interact.hovered.bg_fill = None;
+            style.interact.hovered.rect_outline = None;
+            style.interact.inactive.bg_fill = None;
+            style.interact.inactive.rect_outline = None;
+            ui.set_style(style);
+            ui.set_layout(Layout::justified(Direction::Vertical));
+            add_contents(ui)
+        })
+    })
+}
+
 fn interact_with_menu_button(
     bar_state: &mut BarState,
     input: &InputState,
     menu_id: Id,
     button_interact: &GuiResponse,
 ) {
+    if button_interact.hovered && (input.mouse.pressed || input.mouse.delta != Vec2::zero()) {
+        // The mouse takes over from the keyboard:
+        bar_state.highlighted = None;
+    }
+
     if button_interact.hovered && input.mouse.pressed {
         if bar_state.open_menu.is_some() {
             bar_state.open_menu = None;
@@ -139,16 +188,96 @@ fn interact_with_menu_button(
         bar_state.open_menu = Some(menu_id);
     }
 
-    let pressed_escape = input.events.iter().any(|event| {
-        matches!(
-            event,
+    if bar_state.open_menu.is_some() && input.key_pressed(Key::Escape) {
+        bar_state.open_menu = None;
+    }
+}
+
+/// Up and down moves the highlight between the items of the open menu.
+/// The items are the interactive widgets the menu had last frame.
+fn highlight_menu_item(ui: &Ui, bar_state: &mut BarState, layer: Layer) {
+    let items = ui.ctx().widgets_last_frame_on(layer);
+    for event in &ui.input().events {
+        let step: isize = match event {
             Event::Key {
-                key: Key::Escape,
-                pressed: true
+                key: Key::Up,
+                pressed: true,
+            } => -1,
+            Event::Key {
+                key: Key::Down,
+                pressed: true,
+            } => 1,
+            _ => continue,
+        };
+        let num_items = items.len().max(1) as isize;
+        bar_state.highlighted = Some(match bar_state.highlighted {
+            Some(index) => (index as isize + step).rem_euclid(num_items) as usize,
+            None if step < 0 => num_items as usize - 1,
+            None => 0,
+        });
+    }
+
+    let mut memory = ui.memory();
+    match bar_state.highlighted.and_then(|index| items.get(index)) {
+        Some(&(item_id, _)) => {
+            memory.menu_highlight = Some(item_id);
+            memory.kb_focus_id = Some(item_id); // So that return and space click it
+        }
+        None => clear_menu_highlight(&mut memory),
+    }
+}
+
+/// Alt and a letter opens the menu with that mnemonic, left and right open the neighbouring menus.
+fn bar_keyboard_ui(ui: &Ui, bar_id: Id) {
+    let input = ui.input();
+    let mut memory = ui.memory();
+    let bar_state = match memory.menu_bar.get_mut(&bar_id) {
+        Some(bar_state) => bar_state,
+        None => return,
+    };
+
+    for event in &input.events {
+        match event {
+            Event::Text(text) if input.modifiers.alt => {
+                let letter = text.chars().next().map(|c| c.to_ascii_lowercase());
+                let menu = bar_state
+                    .menus
+                    .iter()
+                    .find(|(_, mnemonic)| mnemonic.is_some() && *mnemonic == letter);
+                if let Some(&(menu_id, _)) = menu {
+                    bar_state.open_with_keyboard(menu_id, input.time);
+                }
             }
-        )
-    });
-    if pressed_escape {
-        bar_state.open_menu = None;
+            Event::Key { key, pressed: true } if *key == Key::Left || *key == Key::Right => {
+                let num_menus = bar_state.menus.len();
+                let open_index = bar_state
+                    .menus
+                    .iter()
+                    .position(|(menu_id, _)| Some(*menu_id) == bar_state.open_menu);
+                if let Some(open_index) = open_index {
+                    let index = if *key == Key::Left {
+                        (open_index + num_menus - 1) % num_menus
+                    } else {
+                        (open_index + 1) % num_menus
+                    };
+                    let menu_id = bar_state.menus[index].0;
+                    bar_state.open_with_keyboard(menu_id, input.time);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if bar_state.open_menu.is_none() {
+        bar_state.highlighted = None;
+        clear_menu_highlight(&mut memory);
+    }
+}
+
+fn clear_menu_highlight(memory: &mut Memory) {
+    if let Some(item_id) = memory.menu_highlight.take() {
+        if memory.kb_focus_id == Some(item_id) {
+            memory.kb_focus_id = None;
+        }
     }
 }
@@ -0,0 +1,95 @@
+use std::hash::Hash;
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct State {
+    /// Where the contents were laid out last frame, relative to the parent `Ui`.
+    target: Vec2,
+    /// Where the current animation started, relative to the parent `Ui`.
+    from: Vec2,
+    /// When the current animation started.
+    /// Times are relative, and we don't want to continue animations anyway, hence `serde(skip)`
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    start_time: f64,
+}
+
+impl State {
+    fn offset_at(&self, time: f64, duration: f32) -> Vec2 {
+        let t = remap_clamp((time - self.start_time) as f32, 0.0..=duration, 0.0..=1.0);
+        lerp(self.from..=self.target, ease_in_ease_out(t))
+    }
+}
+
+/// Contents that slide into their new place when the layout moves them,
+/// instead of jumping there.
+///
+/// Wrap each item of a list you reorder (or whatever comes after a section you collapse)
+/// in one of these, with an id that stays with the item:
+///
+/// ```ignore
+/// for item in &items {
+///     MoveAnimation::new(&item.name).show(ui, |ui| ui.label(&item.name));
+/// }
+/// ```
+///
+/// Only the painting is animated: the contents can be interacted with at their new position at once.
+#[derive(Clone, Copy, Debug)]
+pub struct MoveAnimation {
+    id_source: Id,
+    duration: f32,
+}
+
+impl MoveAnimation {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            duration: 0.2,
+        }
+    }
+
+    /// How long it takes to move to the new place, in seconds.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        let MoveAnimation {
+            id_source,
+            duration,
+        } = self;
+        let id = ui.make_child_id(id_source);
+        let time = ui.input().time;
+
+        let paint_start = ui.paint_list_len();
+        let (ret, rect) = ui.add_custom(add_contents);
+        // Relative to the parent, so that e.g. scrolling and window dragging are not animated:
+        let target = rect.min - ui.top_left();
+
+        let state = ui.memory().move_animations.get(&id).cloned();
+        let state = match state {
+            Some(state) if (state.target - target).length() > 0.5 => State {
+                // Start from wherever we are in any previous animation:
+                from: state.offset_at(time, duration),
+                target,
+                start_time: time,
+            },
+            Some(state) => state,
+            None => State {
+                target,
+                from: target,
+                start_time: time,
+            },
+        };
+
+        let offset = state.offset_at(time, duration);
+        if offset != target {
+            ui.translate_paint_cmds(paint_start, offset - target);
+        }
+
+        ui.memory().move_animations.insert(id, state);
+        ret
+    }
+}
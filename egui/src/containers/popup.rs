@@ -2,15 +2,103 @@ use std::sync::Arc;
 
 use crate::*;
 
+/// Which widget the mouse is resting on, for `show_tooltip_for`.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct TooltipState {
+    /// The widget, and since when it has been hovered.
+    hovered: Option<(Id, f64)>,
+    /// Was a tooltip asked for this frame?
+    requested: bool,
+}
+
+impl TooltipState {
+    pub(crate) fn begin_frame(&mut self) {
+        if !self.requested {
+            self.hovered = None; // The mouse left the widget, so start over
+        }
+        self.requested = false;
+    }
+}
+
+/// Show a tooltip next to the mouse.
+/// It is moved to the other side of the mouse if it would otherwise go off-screen.
 pub fn show_tooltip(ctx: &Arc<Context>, add_contents: impl FnOnce(&mut Ui)) {
     if let Some(mouse_pos) = ctx.input().mouse.pos {
-        //  TODO: default size
         let id = Id::tooltip();
-        let window_pos = mouse_pos + vec2(16.0, 16.0);
+        let size = size_last_frame(ctx, id);
+        let offset = vec2(16.0, 16.0);
+        let screen = ctx.rect();
+
+        let mut window_pos = mouse_pos + offset;
+        if window_pos.x + size.x > screen.right() {
+            window_pos.x = (mouse_pos.x - offset.x - size.x).max(screen.left());
+        }
+        if window_pos.y + size.y > screen.bottom() {
+            window_pos.y = (mouse_pos.y - offset.y - size.y).max(screen.top());
+        }
         show_popup(ctx, id, window_pos, add_contents);
     }
 }
 
+/// Like `show_tooltip`, but only once `widget_id` has been hovered for `Style::tooltip_delay`,
+/// and then fading in over `Style::tooltip_fade_time`.
+/// Call this every frame the widget is hovered.
+pub fn show_tooltip_for(ctx: &Arc<Context>, widget_id: Id, add_contents: impl FnOnce(&mut Ui)) {
+    let time = ctx.input().time;
+    let hover_start = {
+        let tooltip = &mut ctx.memory().tooltip;
+        tooltip.requested = true;
+        match tooltip.hovered {
+            Some((id, hover_start)) if id == widget_id => hover_start,
+            _ => {
+                tooltip.hovered = Some((widget_id, time));
+                time
+            }
+        }
+    };
+
+    let style = ctx.style();
+    let time_shown = (time - hover_start) as f32 - style.tooltip_delay;
+    if time_shown < 0.0 {
+        return;
+    }
+
+    show_tooltip(ctx, add_contents);
+
+    if time_shown < style.tooltip_fade_time {
+        let opacity = time_shown / style.tooltip_fade_time;
+        let layer = Layer {
+            order: Order::Foreground,
+            id: Id::tooltip(),
+        };
+        for (_, cmd) in ctx.graphics().layer(layer).iter_mut() {
+            cmd.fade(opacity);
+        }
+    }
+}
+
+/// Where to put a popup so that it is just below `anchor`,
+/// or above it if there is not enough room below.
+pub(crate) fn popup_pos_below(ctx: &Context, popup_id: Id, anchor: Rect) -> Pos2 {
+    let size = size_last_frame(ctx, popup_id);
+    let screen = ctx.rect();
+    let mut pos = anchor.left_bottom();
+    if pos.y + size.y > screen.bottom() {
+        pos.y = (anchor.top() - size.y).max(screen.top());
+    }
+    pos.x = pos.x.min(screen.right() - size.x).max(screen.left());
+    pos
+}
+
+/// Based on the size last frame, so the first frame a popup is shown it may be misplaced.
+fn size_last_frame(ctx: &Context, popup_id: Id) -> Vec2 {
+    ctx.memory()
+        .areas
+        .get(popup_id)
+        .map(|state| state.size)
+        .unwrap_or_default()
+}
+
 /// Show a pop-over window
 pub fn show_popup(
     ctx: &Arc<Context>,
@@ -25,3 +113,100 @@ pub fn show_popup(
         .interactable(false)
         .show(ctx, |ui| Frame::popup(&ctx.style()).show(ui, add_contents))
 }
+
+/// Show a menu at the mouse position when the secondary (usually right) mouse button
+/// is released while `hovered`.
+/// The menu closes again on the next click (e.g. when you pick something) or on escape.
+///
+/// You usually want `Ui::context_menu` or `GuiResponse::context_menu` instead.
+pub fn context_menu(
+    ctx: &Arc<Context>,
+    popup_id: Id,
+    hovered: bool,
+    add_contents: impl FnOnce(&mut Ui),
+) {
+    if hovered && ctx.input().mouse.secondary_released {
+        if let Some(mouse_pos) = ctx.input().mouse.pos {
+            let mut memory = ctx.memory();
+            memory.open_popup(popup_id);
+            memory.context_menu_pos = mouse_pos;
+        }
+        return; // Start showing it next frame, so the release doesn't count as a click on it
+    }
+
+    if !ctx.memory().is_popup_open(popup_id) {
+        return;
+    }
+
+    use containers::*;
+    let pos = ctx.memory().context_menu_pos;
+    Area::new(popup_id)
+        .order(Order::Foreground)
+        .fixed_pos(pos)
+        .show(ctx, |ui| menu::menu_frame(ui, add_contents));
+
+    let pressed_escape = ctx.consume_key(Key::Escape);
+    let mouse = &ctx.input().mouse;
+    if pressed_escape || mouse.click || mouse.secondary_pressed {
+        ctx.memory().close_popup();
+    }
+}
+
+/// A popup anchored to a widget, e.g. a drop-down, a date picker or a color picker.
+///
+/// ```ignore
+/// let response = ui.button("Pick");
+/// let popup_id = ui.make_child_id("picker");
+/// if response.clicked {
+///     ui.memory().toggle_popup(popup_id);
+/// }
+/// Popup::show_below(ui.ctx(), response.rect, popup_id, |ui| {
+///     ui.label("Contents");
+/// });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Popup;
+
+impl Popup {
+    /// Shows the popup just below `widget_rect`, or above it if there is not enough room below,
+    /// as long as `popup_id` is the open popup (see `Memory::open_popup`).
+    /// It closes on escape, or on a click outside of both the popup and `widget_rect`,
+    /// so the widget can toggle it without it closing and reopening.
+    ///
+    /// Returns `None` if the popup is closed.
+    pub fn show_below<R>(
+        ctx: &Arc<Context>,
+        widget_rect: Rect,
+        popup_id: Id,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<R> {
+        if !ctx.memory().is_popup_open(popup_id) {
+            return None;
+        }
+
+        use containers::*;
+        let pos = popup_pos_below(ctx, popup_id, widget_rect);
+        let mut ret = None;
+        let interact = Area::new(popup_id)
+            .order(Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| ret = Some(add_contents(ui)));
+            });
+
+        let pressed_escape = ctx.consume_key(Key::Escape);
+        let clicked_elsewhere = {
+            let mouse = &ctx.input().mouse;
+            let clicked_inside = match mouse.pos {
+                Some(pos) => interact.rect.contains(pos) || widget_rect.contains(pos),
+                None => false,
+            };
+            mouse.click && !clicked_inside
+        };
+        if pressed_escape || clicked_elsewhere {
+            ctx.memory().close_popup();
+        }
+
+        ret
+    }
+}
@@ -16,6 +16,10 @@ pub(crate) struct State {
 
     /// Externally requested size (e.g. by Window) for the next frame
     pub(crate) requested_size: Option<Vec2>,
+
+    /// Has the user resized it? Then `fit_to_content` stops.
+    #[cfg_attr(feature = "with_serde", serde(default))]
+    user_resized: bool,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -27,11 +31,13 @@ pub struct Resize {
 
     min_content_size: Vec2,
     min_desired_size: Vec2,
+    max_size: Vec2,
 
     default_size: Vec2,
 
     outline: bool,
     handle_offset: Vec2,
+    fit_to_content: bool,
 }
 
 impl Default for Resize {
@@ -41,9 +47,11 @@ impl Default for Resize {
             resizable: true,
             min_content_size: Vec2::splat(16.0),
             min_desired_size: vec2(200.0, 400.0),
+            max_size: Vec2::splat(f32::INFINITY),
             default_size: vec2(280.0, 400.0), // TODO: perferred size for a resizable area (e.g. a window)
             outline: true,
             handle_offset: Default::default(),
+            fit_to_content: false,
         }
     }
 }
@@ -95,6 +103,13 @@ impl Resize {
         self
     }
 
+    /// Can't be resized to larger than this.
+    /// This wins over the `default_size`, but not over the `min_desired_size`.
+    pub fn max_size(mut self, max_size: impl Into<Vec2>) -> Self {
+        self.max_size = max_size.into();
+        self
+    }
+
     /// Can you resize it with the mouse?
     /// Note that a window can still auto-resize
     pub fn resizable(mut self, resizable: bool) -> Self {
@@ -112,6 +127,13 @@ impl Resize {
             .resizable(false)
     }
 
+    /// Take exactly the size of the contents each frame, growing and shrinking with them,
+    /// until the user resizes it. Text is wrapped at the `default_width`.
+    pub fn fit_to_content(mut self, fit_to_content: bool) -> Self {
+        self.fit_to_content = fit_to_content;
+        self
+    }
+
     pub fn fixed_size(mut self, size: impl Into<Vec2>) -> Self {
         let size = size.into();
         self.default_size = size;
@@ -121,6 +143,19 @@ impl Resize {
         self
     }
 
+    /// The id given with `id`, if any.
+    pub(crate) fn explicit_id(&self) -> Option<Id> {
+        self.id
+    }
+
+    /// How small and how large it can be resized to.
+    pub(crate) fn size_range(&self) -> (Vec2, Vec2) {
+        (
+            self.min_desired_size,
+            self.max_size.max(self.min_desired_size),
+        )
+    }
+
     /// Offset the position of the resize handle by this much
     pub fn handle_offset(mut self, handle_offset: impl Into<Vec2>) -> Self {
         self.handle_offset = handle_offset.into();
@@ -136,6 +171,8 @@ impl Resize {
 struct Prepared {
     id: Id,
     state: State,
+    /// `fit_to_content`, and the user hasn't resized it.
+    fitting: bool,
     corner_interact: Option<InteractInfo>,
     content_ui: Ui,
 }
@@ -151,10 +188,14 @@ impl Resize {
                 desired_size: default_size,
                 last_content_size: vec2(0.0, 0.0),
                 requested_size: None,
+                user_resized: false,
             }
         });
 
-        state.desired_size = state.desired_size.max(self.min_desired_size);
+        state.desired_size = state
+            .desired_size
+            .min(self.max_size)
+            .max(self.min_desired_size);
 
         let position = ui.available().min;
 
@@ -168,6 +209,7 @@ impl Resize {
             let corner_interact = ui.interact(corner_rect, id.with("corner"), Sense::drag());
 
             if corner_interact.active {
+                state.user_resized = true;
                 if let Some(mouse_pos) = ui.input().mouse.pos {
                     state.desired_size = mouse_pos - position + 0.5 * corner_interact.rect.size()
                         - self.handle_offset;
@@ -180,14 +222,36 @@ impl Resize {
 
         if let Some(requested_size) = state.requested_size.take() {
             state.desired_size = requested_size;
+            state.user_resized = true;
+        }
+        if state.desired_size.x.is_nan() || state.desired_size.y.is_nan() {
+            state.desired_size = self.default_size.max(self.min_content_size);
         }
-        state.desired_size = state.desired_size.max(self.min_desired_size);
+        state.desired_size = state
+            .desired_size
+            .min(self.max_size)
+            .max(self.min_desired_size);
 
         // ------------------------------
 
-        let inner_rect = Rect::from_min_size(position, state.desired_size);
+        let fitting = self.fit_to_content && !state.user_resized;
+
+        let inner_rect = if fitting {
+            // Lay out as if we had the default size, then shrink or grow to what the contents took:
+            let layout_size = self
+                .default_size
+                .min(self.max_size)
+                .max(self.min_content_size);
+            Rect::from_min_size(position, layout_size)
+        } else {
+            Rect::from_min_size(position, state.desired_size)
+        };
 
         let mut content_clip_rect = inner_rect.expand(ui.style().clip_rect_margin);
+        if fitting {
+            // We will be as large as the contents:
+            content_clip_rect.max = pos2(f32::INFINITY, f32::INFINITY);
+        }
 
         // If we pull the resize handle to shrink, we want to TRY to shink it.
         // After laying out the contents, we might be much bigger.
@@ -206,6 +270,7 @@ impl Resize {
         Prepared {
             id,
             state,
+            fitting,
             corner_interact,
             content_ui,
         }
@@ -222,16 +287,28 @@ impl Resize {
         let Prepared {
             id,
             mut state,
+            fitting,
             corner_interact,
             content_ui,
         } = prepared;
 
         state.last_content_size = content_ui.bounding_size();
         state.last_content_size = state.last_content_size.ceil(); // Avoid rounding errors in math
+        if !state.last_content_size.is_finite() {
+            state.last_content_size = Vec2::zero(); // Nothing sensible was added
+        }
+        state.last_content_size = state.last_content_size.max(Vec2::zero());
 
         // ------------------------------
 
-        if self.outline || self.resizable {
+        if fitting {
+            state.desired_size = state
+                .last_content_size
+                .max(self.min_content_size)
+                .min(self.max_size);
+            state.desired_size = ui.round_vec_to_pixels(state.desired_size);
+            ui.allocate_space(state.desired_size);
+        } else if self.outline || self.resizable {
             // We show how large we are,
             // so we must follow the contents:
 
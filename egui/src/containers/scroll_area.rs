@@ -7,28 +7,76 @@ pub(crate) struct State {
     /// Positive offset means scrolling down/right
     offset: Vec2,
 
-    show_scroll: bool, // TODO: default value?
+    /// Was the scroll bar of each axis (x, y) shown last frame?
+    show_scroll: [bool; 2], // TODO: default value?
+
+    /// Is the scroll bar of this axis held outside the handle, paging towards the mouse?
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    paging: [bool; 2],
+    /// When to scroll the next page, while `paging`.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    next_page_time: f64,
+
+    /// With `stick_to_bottom`: has the user scrolled up, so that we should stop following the end?
+    scrolled_away_from_bottom: bool,
 }
 
-// TODO: rename VScroll
+/// Scrolls vertically by default. See `ScrollArea::horizontal` and `ScrollArea::both`.
 #[derive(Clone, Debug)]
 pub struct ScrollArea {
+    id_source: Option<Id>,
+    /// Which axes (x, y) to scroll along.
+    has_bar: [bool; 2],
     max_height: f32,
     always_show_scroll: bool,
     auto_hide_scroll: bool,
+    auto_shrink_width: bool,
+    stick_to_bottom: bool,
 }
 
 impl Default for ScrollArea {
     fn default() -> Self {
+        Self::vertical()
+    }
+}
+
+impl ScrollArea {
+    /// Scroll up and down, with a scroll bar on the right.
+    pub fn vertical() -> Self {
         Self {
+            id_source: None,
+            has_bar: [false, true],
             max_height: 200.0,
             always_show_scroll: false,
             auto_hide_scroll: true,
+            auto_shrink_width: false,
+            stick_to_bottom: false,
         }
     }
-}
 
-impl ScrollArea {
+    /// Scroll left and right, with a scroll bar at the bottom.
+    /// Also scrolled by the mouse wheel while holding shift.
+    pub fn horizontal() -> Self {
+        Self {
+            has_bar: [true, false],
+            ..Self::vertical()
+        }
+    }
+
+    /// Scroll along both axes, with a scroll bar on the right and one at the bottom.
+    pub fn both() -> Self {
+        Self {
+            has_bar: [true, true],
+            ..Self::vertical()
+        }
+    }
+
+    /// Needed if you have several scroll areas in the same `Ui`
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
     pub fn max_height(mut self, max_height: f32) -> Self {
         self.max_height = max_height;
         self
@@ -43,13 +91,49 @@ impl ScrollArea {
         self.auto_hide_scroll = auto_hide_scroll;
         self
     }
+
+    /// If true, only take up as much width as the contents need,
+    /// instead of all the available width.
+    pub fn auto_shrink_width(mut self, auto_shrink_width: bool) -> Self {
+        self.auto_shrink_width = auto_shrink_width;
+        self
+    }
+
+    /// Keep showing the end of the contents as more is added, e.g. for a log.
+    /// Scrolling up stops this, until you scroll back down to the bottom.
+    pub fn stick_to_bottom(mut self, stick_to_bottom: bool) -> Self {
+        self.stick_to_bottom = stick_to_bottom;
+        self
+    }
+}
+
+/// What a `ScrollArea` measured when it was shown.
+#[derive(Clone, Copy, Debug)]
+pub struct ScrollAreaResponse {
+    /// The size of the contents, which may be larger than what is visible.
+    pub content_size: Vec2,
+
+    /// The visible part of the contents, excluding the scroll bars.
+    pub inner_rect: Rect,
+
+    /// Was the content too large to fit (along an axis we scroll), so that we need to scroll?
+    pub scrolling: bool,
+}
+
+/// How much width a visible scroll bar takes (including the margin to the content)
+pub(crate) fn max_scroll_bar_width(style: &Style) -> f32 {
+    style.item_spacing.x + 16.0
 }
 
 struct Prepared {
     id: Id,
     state: State,
-    current_scroll_bar_width: f32,
+    has_bar: [bool; 2],
+    /// Room taken by the vertical scroll bar (x) and the horizontal one (y).
+    current_bar_use: Vec2,
     always_show_scroll: bool,
+    auto_shrink_width: bool,
+    stick_to_bottom: bool,
     inner_rect: Rect,
     content_ui: Ui,
 }
@@ -57,14 +141,21 @@ struct Prepared {
 impl ScrollArea {
     fn begin(self, ui: &mut Ui) -> Prepared {
         let Self {
+            id_source,
+            has_bar,
             max_height,
             always_show_scroll,
             auto_hide_scroll,
+            auto_shrink_width,
+            stick_to_bottom,
         } = self;
 
         let ctx = ui.ctx().clone();
 
-        let id = ui.make_child_id("scroll_area");
+        let id = match id_source {
+            Some(id_source) => ui.make_child_id(id_source),
+            None => ui.make_child_id("scroll_area"),
+        };
         let state = ctx
             .memory()
             .scroll_areas
@@ -76,182 +167,359 @@ impl ScrollArea {
         // outer: size of scroll area including scroll bar(s)
         // inner: excluding scroll bar(s). The area we clip the contents to.
 
-        let max_scroll_bar_width = ui.style().item_spacing.x + 16.0;
+        let max_scroll_bar_width = max_scroll_bar_width(ui.style());
 
-        let current_scroll_bar_width = if state.show_scroll || !auto_hide_scroll {
-            max_scroll_bar_width // TODO: animate?
-        } else {
-            0.0
+        let bar_use = |d: usize| {
+            if has_bar[d] && (state.show_scroll[d] || !auto_hide_scroll) {
+                max_scroll_bar_width // TODO: animate?
+            } else {
+                0.0
+            }
         };
+        // The vertical bar takes width, the horizontal one height:
+        let current_bar_use = vec2(bar_use(1), bar_use(0));
 
         let outer_size = vec2(
             ui.available().width(),
             ui.available().height().min(max_height),
-        );
+        )
+        .max(Vec2::zero()); // The parent may have overflowed
 
-        let inner_size = outer_size - vec2(current_scroll_bar_width, 0.0);
+        let inner_size = (outer_size - current_bar_use).max(Vec2::zero());
         let inner_rect = Rect::from_min_size(ui.available().min, inner_size);
 
+        // The offset itself is kept fractional so that slow trackpad scrolling accumulates,
+        // but the content is placed on whole pixels. Text is snapped to pixels when painted,
+        // so a fractional offset would make text and everything else move out of step.
+        let content_offset = ui.round_vec_to_pixels(state.offset);
+
+        let content_height = if has_bar[1] {
+            f32::INFINITY
+        } else {
+            inner_size.y
+        };
         let mut content_ui = ui.child_ui(Rect::from_min_size(
-            inner_rect.min - state.offset,
-            vec2(inner_size.x, f32::INFINITY),
+            inner_rect.min - content_offset,
+            vec2(inner_size.x, content_height),
         ));
         let mut content_clip_rect = inner_rect.expand(ui.style().clip_rect_margin);
         content_clip_rect = content_clip_rect.intersect(ui.clip_rect());
-        content_clip_rect.max.x = ui.clip_rect().max.x - current_scroll_bar_width; // Nice handling of forced resizing beyond the possible
+        if !has_bar[0] {
+            // Wide contents widen the area instead of scrolling.
+            // Nice handling of forced resizing beyond the possible:
+            content_clip_rect.max.x = ui.clip_rect().max.x - current_bar_use.x;
+        }
         content_ui.set_clip_rect(content_clip_rect);
 
         Prepared {
             id,
             state,
+            has_bar,
             always_show_scroll,
+            auto_shrink_width,
+            stick_to_bottom,
             inner_rect,
-            current_scroll_bar_width,
+            current_bar_use,
             content_ui,
         }
     }
 
     pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        self.show_with_response(ui, add_contents).0
+    }
+
+    /// Like `show`, but also tells you how large the contents were.
+    pub fn show_with_response<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, ScrollAreaResponse) {
         let mut prepared = self.begin(ui);
         let ret = add_contents(&mut prepared.content_ui);
-        prepared.end(ui);
-        ret
+        let response = prepared.end(ui);
+        (ret, response)
     }
 }
 
 impl Prepared {
-    fn end(self, ui: &mut Ui) {
+    fn end(self, ui: &mut Ui) -> ScrollAreaResponse {
         let Prepared {
             id,
             mut state,
+            has_bar,
             inner_rect,
             always_show_scroll,
-            current_scroll_bar_width,
+            auto_shrink_width,
+            stick_to_bottom,
+            current_bar_use,
             content_ui,
         } = self;
 
         let content_size = content_ui.bounding_size();
+        let stick_to_bottom = stick_to_bottom && has_bar[1];
 
-        let inner_rect = Rect::from_min_size(
-            inner_rect.min,
-            vec2(
-                inner_rect.width().max(content_size.x), // Expand width to fit content
-                inner_rect.height(),
-            ),
-        );
+        if stick_to_bottom && !state.scrolled_away_from_bottom {
+            // Follow the contents as they grow (clamped to zero below if they fit):
+            state.offset.y = content_size.y - inner_rect.height();
+        }
 
-        let outer_rect = Rect::from_min_size(
-            inner_rect.min,
-            inner_rect.size() + vec2(current_scroll_bar_width, 0.0),
-        );
+        let scroll_target = ui.memory().scroll_target;
+        if let Some((target, align)) = scroll_target {
+            let mut content_rect_size = inner_rect.size();
+            for d in 0..2 {
+                if has_bar[d] {
+                    content_rect_size[d] = content_size[d];
+                }
+            }
+            let content_rect =
+                Rect::from_min_size(inner_rect.min - state.offset, content_rect_size);
+            if content_rect.contains(target.center()) {
+                let mut delta = Vec2::zero();
+                for d in (0..2).filter(|&d| has_bar[d]) {
+                    delta[d] = match align {
+                        Some(Align::Min) => target.min[d] - inner_rect.min[d],
+                        Some(Align::Center) => target.center()[d] - inner_rect.center()[d],
+                        Some(Align::Max) => target.max[d] - inner_rect.max[d],
+                        None if target.min[d] < inner_rect.min[d] => {
+                            target.min[d] - inner_rect.min[d]
+                        }
+                        None if target.max[d] > inner_rect.max[d] => (target.max[d]
+                            - inner_rect.max[d])
+                            .min(target.min[d] - inner_rect.min[d]),
+                        None => 0.0,
+                    };
+                }
+                state.offset += delta;
+                // Any outer scroll area should scroll to where it will be after this:
+                ui.memory().scroll_target = Some((target.translate(-delta), align));
+            }
+        }
 
-        let content_is_too_small = content_size.y > inner_rect.height();
+        let mut inner_size = inner_rect.size();
+        inner_size.x = if has_bar[0] {
+            if auto_shrink_width {
+                inner_size.x.min(content_size.x)
+            } else {
+                inner_size.x
+            }
+        } else if auto_shrink_width {
+            content_size.x
+        } else {
+            inner_size.x.max(content_size.x) // Expand width to fit content
+        };
+        // Shrink if content is so small that we don't need to scroll:
+        inner_size.y = inner_size.y.min(content_size.y);
+        let inner_rect = Rect::from_min_size(inner_rect.min, inner_size);
+
+        let outer_rect = Rect::from_min_size(inner_rect.min, inner_rect.size() + current_bar_use);
+
+        let content_is_too_large = [
+            has_bar[0] && content_size.x > inner_rect.width(),
+            has_bar[1] && content_size.y > inner_rect.height(),
+        ];
+        // Only scroll along the axes that need it:
+        let mask = |delta: Vec2| {
+            vec2(
+                if content_is_too_large[0] {
+                    delta.x
+                } else {
+                    0.0
+                },
+                if content_is_too_large[1] {
+                    delta.y
+                } else {
+                    0.0
+                },
+            )
+        };
 
-        if content_is_too_small {
+        if content_is_too_large[0] || content_is_too_large[1] {
             // Drag contents to scroll (for touch screens mostly):
             let content_interact = ui.interact(inner_rect, id.with("area"), Sense::drag());
             if content_interact.active {
-                state.offset.y -= ui.input().mouse.delta.y;
+                state.offset -= mask(ui.input().mouse.delta);
             }
         }
 
-        // TODO: check that nothing else is being interacted with
-        if ui.contains_mouse(outer_rect) {
-            state.offset.y -= ui.input().scroll_delta.y;
+        if (content_is_too_large[0] || content_is_too_large[1])
+            && ui.style().scroll_area.middle_drag_pans
+        {
+            let input = ui.input();
+            if input.mouse.middle_pressed && ui.contains_mouse(inner_rect) {
+                // Inner scroll areas end first, so they get it first:
+                ui.memory().interaction.middle_drag_id.get_or_insert(id);
+            }
+            if input.mouse.middle_down && ui.memory().interaction.middle_drag_id == Some(id) {
+                state.offset -= mask(input.mouse.delta);
+            }
         }
 
-        let show_scroll_this_frame = content_is_too_small || always_show_scroll;
-        if show_scroll_this_frame || state.show_scroll {
-            let margin = ui.style().item_spacing.x; // margin between contents and scroll bar
-            let left = inner_rect.right() + margin;
-            let right = outer_rect.right();
-            let corner_radius = (right - left) / 2.0;
-            let top = inner_rect.top();
-            let bottom = inner_rect.bottom();
-
-            let outer_scroll_rect = Rect::from_min_max(
-                pos2(left, inner_rect.top()),
-                pos2(right, inner_rect.bottom()),
-            );
-
-            let from_content =
-                |content_y| remap_clamp(content_y, 0.0..=content_size.y, top..=bottom);
-
-            let handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
-            );
-
-            // intentionally use same id for inside and outside of handle
-            let interact_id = id.with("vertical");
-            let mut interact = ui.interact(handle_rect, interact_id, Sense::click_and_drag());
-
-            if let Some(mouse_pos) = ui.input().mouse.pos {
-                if interact.active {
-                    if inner_rect.top() <= mouse_pos.y && mouse_pos.y <= inner_rect.bottom() {
-                        state.offset.y +=
-                            ui.input().mouse.delta.y * content_size.y / inner_rect.height();
-                    }
-                } else {
-                    // Check for mouse down outside handle:
-                    let scroll_bg_interact =
-                        ui.interact(outer_scroll_rect, interact_id, Sense::click_and_drag());
-
-                    if scroll_bg_interact.active {
-                        // Center scroll at mouse pos:
-                        let mpos_top = mouse_pos.y - handle_rect.height() / 2.0;
-                        state.offset.y = remap(mpos_top, top..=bottom, 0.0..=content_size.y);
-                    }
-
-                    interact = interact.union(scroll_bg_interact);
-                }
+        let mut scroll_delta = ui.input().scroll_delta;
+        if ui.input().modifiers.shift {
+            // Shift turns the mouse wheel sideways:
+            scroll_delta = vec2(scroll_delta.y, scroll_delta.x);
+        }
+        if mask(scroll_delta) != Vec2::zero() {
+            // Widgets in the scroll area that use the wheel themselves get it first:
+            let scroll_interact = ui.interact(outer_rect, id.with("wheel"), Sense::scroll());
+            if scroll_interact.scroll_delta != Vec2::zero() {
+                state.offset -= mask(scroll_delta);
             }
+        }
 
-            state.offset.y = state.offset.y.max(0.0);
-            state.offset.y = state.offset.y.min(content_size.y - inner_rect.height());
-
-            // Avoid frame-delay by calculating a new handle rect:
-            let mut handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
-            );
-            let min_handle_height = (2.0 * corner_radius).max(8.0);
-            if handle_rect.size().y < min_handle_height {
-                handle_rect = Rect::from_center_size(
-                    handle_rect.center(),
-                    vec2(handle_rect.size().x, min_handle_height),
-                );
+        let mut show_scroll_this_frame = [false; 2];
+        for d in 0..2 {
+            if !has_bar[d] {
+                state.offset[d] = 0.0;
+                continue;
+            }
+            show_scroll_this_frame[d] = content_is_too_large[d] || always_show_scroll;
+            if show_scroll_this_frame[d] || state.show_scroll[d] {
+                scroll_bar_ui(ui, id, &mut state, d, inner_rect, outer_rect, content_size);
             }
-
-            let style = ui.style();
-            let handle_fill = style.interact(&interact).fill;
-            let handle_outline = style.interact(&interact).rect_outline;
-
-            ui.add_paint_cmd(paint::PaintCmd::Rect {
-                rect: outer_scroll_rect,
-                corner_radius,
-                fill: Some(ui.style().dark_bg_color),
-                outline: None,
-            });
-
-            ui.add_paint_cmd(paint::PaintCmd::Rect {
-                rect: handle_rect.expand(-2.0),
-                corner_radius,
-                fill: Some(handle_fill),
-                outline: handle_outline,
-            });
         }
 
-        let size = vec2(
-            outer_rect.size().x,
-            outer_rect.size().y.min(content_size.y), // shrink if content is so small that we don't need scroll bars
-        );
-        ui.allocate_space(size);
+        ui.allocate_space(outer_rect.size());
 
-        state.offset.y = state.offset.y.min(content_size.y - inner_rect.height());
-        state.offset.y = state.offset.y.max(0.0);
+        for d in 0..2 {
+            state.offset[d] = state.offset[d].min(content_size[d] - inner_rect.size()[d]);
+            state.offset[d] = state.offset[d].max(0.0);
+        }
+        if !state.offset.is_finite() {
+            state.offset = Vec2::zero(); // Don't let a NaN get stuck in memory
+        }
+        if stick_to_bottom {
+            // Half a pixel of slack, so a fractional offset doesn't count as scrolling up:
+            let max_offset = content_size.y - inner_rect.height();
+            state.scrolled_away_from_bottom = state.offset.y < max_offset - 0.5;
+        }
         state.show_scroll = show_scroll_this_frame;
 
         ui.memory().scroll_areas.insert(id, state);
+
+        ScrollAreaResponse {
+            content_size,
+            inner_rect,
+            scrolling: content_is_too_large[0] || content_is_too_large[1],
+        }
+    }
+}
+
+/// The scroll bar along axis `d`: 0 is the horizontal bar at the bottom, 1 the vertical one on the right.
+fn scroll_bar_ui(
+    ui: &mut Ui,
+    id: Id,
+    state: &mut State,
+    d: usize,
+    inner_rect: Rect,
+    outer_rect: Rect,
+    content_size: Vec2,
+) {
+    let o = 1 - d; // The other axis, across the bar
+
+    let margin = ui.style().item_spacing.x; // margin between contents and scroll bar
+    let main_min = inner_rect.min[d];
+    let main_max = inner_rect.max[d];
+    let cross_min = inner_rect.max[o] + margin;
+    let cross_max = outer_rect.max[o];
+    let corner_radius = (cross_max - cross_min) / 2.0;
+    let inner_length = inner_rect.size()[d];
+
+    let bar_rect = |from: f32, to: f32| {
+        let mut min = Pos2::default();
+        let mut max = Pos2::default();
+        min[d] = from;
+        max[d] = to;
+        min[o] = cross_min;
+        max[o] = cross_max;
+        Rect::from_min_max(min, max)
+    };
+
+    let outer_scroll_rect = bar_rect(main_min, main_max);
+
+    let from_content =
+        |content: f32| remap_clamp(content, 0.0..=content_size[d], main_min..=main_max);
+
+    let handle_rect = bar_rect(
+        from_content(state.offset[d]),
+        from_content(state.offset[d] + inner_length),
+    );
+
+    // intentionally use same id for inside and outside of handle
+    let interact_id = id.with(if d == 0 { "horizontal" } else { "vertical" });
+    let mut interact = ui.interact(handle_rect, interact_id, Sense::click_and_drag());
+
+    if let Some(mouse_pos) = ui.input().mouse.pos {
+        if interact.active && !state.paging[d] {
+            if main_min <= mouse_pos[d] && mouse_pos[d] <= main_max && inner_length > 0.0 {
+                state.offset[d] += ui.input().mouse.delta[d] * content_size[d] / inner_length;
+            }
+        } else {
+            // Check for mouse down outside handle:
+            let scroll_bg_interact =
+                ui.interact(outer_scroll_rect, interact_id, Sense::click_and_drag());
+
+            let style = ui.style().scroll_area;
+            if !scroll_bg_interact.active {
+                state.paging[d] = false;
+            } else if style.page_on_track_click {
+                let time = ui.input().time;
+                let page = if ui.input().mouse.pressed {
+                    state.paging[d] = true;
+                    state.next_page_time = time + style.page_repeat_delay as f64;
+                    true
+                } else if state.paging[d] && time >= state.next_page_time {
+                    state.next_page_time = time + style.page_repeat_interval as f64;
+                    true
+                } else {
+                    false
+                };
+                // Towards the mouse, until the handle reaches it:
+                if page && mouse_pos[d] < handle_rect.min[d] {
+                    state.offset[d] -= inner_length;
+                } else if page && mouse_pos[d] > handle_rect.max[d] {
+                    state.offset[d] += inner_length;
+                }
+            } else {
+                // Center scroll at mouse pos:
+                let mpos_min = mouse_pos[d] - handle_rect.size()[d] / 2.0;
+                state.offset[d] = remap(mpos_min, main_min..=main_max, 0.0..=content_size[d]);
+            }
+
+            interact = interact.union(scroll_bg_interact);
+        }
     }
+
+    state.offset[d] = state.offset[d].max(0.0);
+    state.offset[d] = state.offset[d].min(content_size[d] - inner_length);
+
+    // Avoid frame-delay by calculating a new handle rect:
+    let mut handle_rect = bar_rect(
+        from_content(state.offset[d]),
+        from_content(state.offset[d] + inner_length),
+    );
+    let min_handle_length = (2.0 * corner_radius).max(8.0);
+    if handle_rect.size()[d] < min_handle_length {
+        let mut size = handle_rect.size();
+        size[d] = min_handle_length;
+        handle_rect = Rect::from_center_size(handle_rect.center(), size);
+    }
+
+    let style = ui.style();
+    let handle_fill = style.interact(&interact).fill;
+    let handle_outline = style.interact(&interact).rect_outline;
+
+    ui.add_paint_cmd(paint::PaintCmd::Rect {
+        rect: outer_scroll_rect,
+        corner_radius,
+        fill: Some(ui.style().dark_bg_color),
+        outline: None,
+    });
+
+    ui.add_paint_cmd(paint::PaintCmd::Rect {
+        rect: handle_rect.expand(-2.0),
+        corner_radius,
+        fill: Some(handle_fill),
+        outline: handle_outline,
+    });
 }
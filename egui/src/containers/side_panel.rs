@@ -0,0 +1,159 @@
+use std::{hash::Hash, ops::RangeInclusive};
+
+use crate::{paint::*, *};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct State {
+    /// As picked by the user dragging the inner edge.
+    width: f32,
+}
+
+/// A strip along the left or right side of a `Ui` (usually the one from `Context::begin_frame`),
+/// with the widgets added to the `Ui` after it going beside it.
+/// Drag the inner edge of the panel to resize it. The width is remembered.
+///
+/// Add side panels before the rest of the contents,
+/// so that what remains is the central part of a classic editor layout:
+///
+/// ```ignore
+/// SidePanel::left("outliner").show(&mut ui, |ui| {
+///     ui.label("Scene");
+/// });
+/// SidePanel::right("inspector").show(&mut ui, |ui| {
+///     ui.label("Properties");
+/// });
+/// ui.label("The rest of the screen");
+/// ```
+#[derive(Clone, Debug)]
+pub struct SidePanel {
+    id_source: Id,
+    on_the_left: bool,
+    default_width: f32,
+    width_range: RangeInclusive<f32>,
+    resizable: bool,
+}
+
+impl SidePanel {
+    pub fn left(id_source: impl Hash) -> Self {
+        Self::new(id_source, true)
+    }
+
+    pub fn right(id_source: impl Hash) -> Self {
+        Self::new(id_source, false)
+    }
+
+    fn new(id_source: impl Hash, on_the_left: bool) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            on_the_left,
+            default_width: 200.0,
+            width_range: 64.0..=f32::INFINITY,
+            resizable: true,
+        }
+    }
+
+    /// The width until the user resizes the panel.
+    pub fn default_width(mut self, default_width: f32) -> Self {
+        self.default_width = default_width;
+        self
+    }
+
+    /// The user can't resize the panel outside of this range.
+    /// It is also never wider than the available space.
+    pub fn width_range(mut self, width_range: RangeInclusive<f32>) -> Self {
+        self.width_range = width_range;
+        self
+    }
+
+    /// Can the user drag the inner edge to resize the panel? Default: true.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        let SidePanel {
+            id_source,
+            on_the_left,
+            default_width,
+            width_range,
+            resizable,
+        } = self;
+        let id = ui.make_child_id(id_source);
+
+        let state = ui.memory().side_panels.get(&id).copied();
+        let mut state = state.unwrap_or(State {
+            width: default_width,
+        });
+
+        let available = ui.available_finite();
+        let clamp_width = |width: f32| {
+            clamp(width, width_range.clone())
+                .min(available.width())
+                .max(0.0)
+        };
+        state.width = clamp_width(state.width);
+
+        let inner_edge_x = |width: f32| {
+            if on_the_left {
+                available.left() + width
+            } else {
+                available.right() - width
+            }
+        };
+
+        let edge_interact = if resizable {
+            let grab_width = 2.0 * ui.style().resize_interact_radius_side;
+            let edge_rect = Rect::from_min_max(
+                pos2(inner_edge_x(state.width), available.top()),
+                pos2(inner_edge_x(state.width), available.bottom()),
+            )
+            .expand2(vec2(0.5 * grab_width, 0.0));
+            let interact = ui.interact(edge_rect, id.with("resize"), Sense::drag());
+            if interact.active {
+                let delta = ui.input().mouse.delta.x;
+                state.width += if on_the_left { delta } else { -delta };
+                state.width = clamp_width(state.width);
+            }
+            if interact.hovered || interact.active {
+                ui.output().cursor_icon = CursorIcon::ResizeHorizontal;
+            }
+            Some(interact)
+        } else {
+            None
+        };
+        ui.memory().side_panels.insert(id, state);
+
+        let rect = ui.allocate_side_strip(on_the_left, state.width);
+        ui.add_paint_cmd(PaintCmd::Rect {
+            rect,
+            corner_radius: 0.0,
+            fill: Some(ui.style().background_fill),
+            outline: None,
+        });
+
+        let mut content_ui = ui.child_ui(rect.shrink2(ui.style().window_padding));
+        content_ui.set_clip_rect(ui.clip_rect().intersect(rect));
+        let ret = add_contents(&mut content_ui);
+
+        let edge_x = if on_the_left {
+            rect.right()
+        } else {
+            rect.left()
+        };
+        let line_style = match &edge_interact {
+            Some(interact) if interact.hovered || interact.active => {
+                let style = ui.style().interact(interact);
+                LineStyle::new(style.stroke_width, style.stroke_color)
+            }
+            _ => ui.style().thin_outline,
+        };
+        ui.add_paint_cmd(PaintCmd::LineSegment {
+            points: [pos2(edge_x, rect.top()), pos2(edge_x, rect.bottom())],
+            style: line_style,
+        });
+
+        ret
+    }
+}
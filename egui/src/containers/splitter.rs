@@ -0,0 +1,166 @@
+use std::hash::Hash;
+
+use crate::{layout::Direction, paint::*, *};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct State {
+    /// The size of the first pane, as a fraction of the space of both panes.
+    ratio: f32,
+}
+
+/// Divides the available space into two panes, with a bar between them
+/// that you can drag to change how much space each pane gets.
+/// The split is remembered.
+///
+/// ```ignore
+/// Splitter::horizontal("outliner").show(ui, |left, right| {
+///     left.label("Scene");
+///     right.label("Properties");
+/// });
+/// ```
+///
+/// Takes up all of the available space (or 200 points in a direction where that is unbounded).
+#[derive(Clone, Copy, Debug)]
+pub struct Splitter {
+    id_source: Id,
+    /// The direction the panes are laid out in.
+    direction: Direction,
+    default_ratio: f32,
+    min_pane_size: f32,
+}
+
+impl Splitter {
+    /// A left and a right pane.
+    pub fn horizontal(id_source: impl Hash) -> Self {
+        Self::new(id_source, Direction::Horizontal)
+    }
+
+    /// A top and a bottom pane.
+    pub fn vertical(id_source: impl Hash) -> Self {
+        Self::new(id_source, Direction::Vertical)
+    }
+
+    fn new(id_source: impl Hash, direction: Direction) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            direction,
+            default_ratio: 0.5,
+            min_pane_size: 32.0,
+        }
+    }
+
+    /// How much of the space the first pane gets until the user drags the bar, in `0..=1`.
+    pub fn default_ratio(mut self, default_ratio: f32) -> Self {
+        self.default_ratio = default_ratio;
+        self
+    }
+
+    /// The bar can't be dragged closer than this to either side.
+    pub fn min_pane_size(mut self, min_pane_size: f32) -> Self {
+        self.min_pane_size = min_pane_size;
+        self
+    }
+
+    /// `add_contents` gets the left and right (or top and bottom) pane.
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui, &mut Ui) -> R) -> R {
+        let Splitter {
+            id_source,
+            direction,
+            default_ratio,
+            min_pane_size,
+        } = self;
+        let id = ui.make_child_id(id_source);
+        let horizontal = direction == Direction::Horizontal;
+
+        let available = ui.available();
+        let finite_or = |size: f32| if size.is_finite() { size } else { 200.0 };
+        let size = vec2(finite_or(available.width()), finite_or(available.height()));
+        let rect = ui.allocate_space(size);
+
+        let bar_thickness = ui.style().item_spacing.x;
+        // Along the direction of the panes:
+        let (start, length) = if horizontal {
+            (rect.left(), rect.width())
+        } else {
+            (rect.top(), rect.height())
+        };
+        let space = (length - bar_thickness).max(0.0);
+        let min_ratio = if space > 0.0 {
+            (min_pane_size / space).min(0.5)
+        } else {
+            0.5
+        };
+
+        let state = ui.memory().splitters.get(&id).copied();
+        let mut state = state.unwrap_or(State {
+            ratio: default_ratio,
+        });
+
+        let bar_rect_at = |ratio: f32| {
+            let bar_start = start + ratio * space;
+            if horizontal {
+                Rect::from_min_max(
+                    pos2(bar_start, rect.top()),
+                    pos2(bar_start + bar_thickness, rect.bottom()),
+                )
+            } else {
+                Rect::from_min_max(
+                    pos2(rect.left(), bar_start),
+                    pos2(rect.right(), bar_start + bar_thickness),
+                )
+            }
+        };
+
+        let interact = ui.interact(bar_rect_at(state.ratio), id, Sense::drag());
+        if interact.active && space > 0.0 {
+            let delta = ui.input().mouse.delta;
+            let delta_along = if horizontal { delta.x } else { delta.y };
+            state.ratio += delta_along / space;
+        }
+        state.ratio = clamp(state.ratio, min_ratio..=1.0 - min_ratio);
+        if interact.hovered || interact.active {
+            ui.output().cursor_icon = if horizontal {
+                CursorIcon::ResizeHorizontal
+            } else {
+                CursorIcon::ResizeVertical
+            };
+        }
+        ui.memory().splitters.insert(id, state);
+
+        let bar_rect = bar_rect_at(state.ratio);
+        let (first_rect, second_rect) = if horizontal {
+            (
+                Rect::from_min_max(rect.min, pos2(bar_rect.left(), rect.bottom())),
+                Rect::from_min_max(pos2(bar_rect.right(), rect.top()), rect.max),
+            )
+        } else {
+            (
+                Rect::from_min_max(rect.min, pos2(rect.right(), bar_rect.top())),
+                Rect::from_min_max(pos2(rect.left(), bar_rect.bottom()), rect.max),
+            )
+        };
+
+        let style = *ui.style().interact(&interact);
+        let center = bar_rect.center();
+        let line = if horizontal {
+            [pos2(center.x, rect.top()), pos2(center.x, rect.bottom())]
+        } else {
+            [pos2(rect.left(), center.y), pos2(rect.right(), center.y)]
+        };
+        ui.add_paint_cmd(PaintCmd::line_segment(
+            line,
+            style.stroke_color,
+            style.stroke_width,
+        ));
+
+        let pane_ui = |rect: Rect| {
+            let mut pane_ui = ui.child_ui(rect);
+            pane_ui.set_clip_rect(ui.clip_rect().intersect(rect));
+            pane_ui
+        };
+        let mut first_ui = pane_ui(first_rect);
+        let mut second_ui = pane_ui(second_rect);
+        add_contents(&mut first_ui, &mut second_ui)
+    }
+}
@@ -0,0 +1,389 @@
+use std::hash::Hash;
+
+use crate::{paint::*, widgets::*, *};
+
+use super::{scroll_area::max_scroll_bar_width, ScrollArea};
+
+/// A column of a `Table`.
+#[derive(Clone, Debug)]
+pub struct Column {
+    title: String,
+    align: Align,
+    width: Option<f32>,
+}
+
+impl Column {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            align: Align::Min,
+            width: None,
+        }
+    }
+
+    /// How to align the contents of the cells (and the title).
+    /// `Align::Max` is nice for numbers.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Fixed width of the column.
+    /// Columns without a width share the width that is left.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+}
+
+impl From<&str> for Column {
+    fn from(title: &str) -> Self {
+        Self::new(title)
+    }
+}
+
+impl From<String> for Column {
+    fn from(title: String) -> Self {
+        Self::new(title)
+    }
+}
+
+/// Which cell of a `Table` is being edited. See `TableRow::edit_cell`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct State {
+    /// The row and column of the cell being edited, if any.
+    editing: Option<(usize, usize)>,
+    /// Tab was pressed when editing this cell, so the next editable cell after it will be edited.
+    tab_from: Option<(usize, usize)>,
+    /// What has been typed so far. `None` for a cell that just started editing.
+    edit_text: Option<String>,
+    /// Was the edited cell shown this frame? If not, it is gone and the edit stops.
+    shown: bool,
+}
+
+/// Horizontal placement of a column, in points relative to the left of the table
+#[derive(Clone, Copy, Debug)]
+struct ColumnLayout {
+    left: f32,
+    width: f32,
+    align: Align,
+}
+
+/// Rows of cells, under column headers that stay put while you scroll the rows.
+/// Cells added with `TableRow::edit_cell` can be edited in place.
+///
+/// ```ignore
+/// Table::new("entities")
+///     .column("Name")
+///     .column(Column::new("HP").align(Align::Max).width(40.0))
+///     .show(ui, |body| {
+///         for entity in &entities {
+///             body.row(|row| {
+///                 row.label(&entity.name);
+///                 row.label(entity.hp.to_string());
+///             });
+///         }
+///     });
+/// ```
+pub struct Table {
+    id_source: Id,
+    columns: Vec<Column>,
+    striped: bool,
+    max_height: f32,
+}
+
+impl Table {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            columns: vec![],
+            striped: true,
+            max_height: 200.0,
+        }
+    }
+
+    pub fn column(mut self, column: impl Into<Column>) -> Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Paint every other row with a slightly different background. On by default.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Height of the scrollable body (excluding the header)
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_rows: impl FnOnce(&mut TableBody<'_>) -> R) -> R {
+        let Self {
+            id_source,
+            columns,
+            striped,
+            max_height,
+        } = self;
+
+        let id = ui.make_child_id(id_source);
+        let width = ui.available().width() - max_scroll_bar_width(ui.style());
+        let layout = layout_columns(&columns, width, ui.style().item_spacing.x);
+
+        // Header:
+        let header_rect = add_row(ui, &layout, width, id, 0, |row| {
+            for column in &columns {
+                row.cell(|ui| {
+                    ui.add(
+                        Label::new(column.title.as_str())
+                            .text_style(TextStyle::Button)
+                            .multiline(false),
+                    )
+                });
+            }
+        });
+        ui.add_paint_cmd(PaintCmd::line_segment(
+            [header_rect.left_bottom(), header_rect.right_bottom()],
+            ui.style().thin_outline.color,
+            ui.style().thin_outline.width,
+        ));
+
+        // Body:
+        let ret = ScrollArea::default()
+            .id_source(id_source)
+            .max_height(max_height)
+            .show(ui, |ui| {
+                let mut style = ui.style().clone();
+                style.item_spacing.y = 0.0; // Stripes should touch
+                ui.set_style(style);
+
+                add_rows(&mut TableBody {
+                    ui,
+                    id,
+                    layout: &layout,
+                    width,
+                    striped,
+                    num_rows: 0,
+                })
+            });
+
+        let mut memory = ui.memory();
+        if let Some(state) = memory.tables.get_mut(&id) {
+            if !state.shown || state.tab_from.is_some() {
+                // The cell to edit is not there, or can't be edited:
+                *state = Default::default();
+            }
+            state.shown = false;
+        }
+        ret
+    }
+}
+
+/// Fixed widths are used as-is, the rest share what is left.
+fn layout_columns(columns: &[Column], width: f32, spacing: f32) -> Vec<ColumnLayout> {
+    let num_spacings = columns.len().saturating_sub(1) as f32;
+    let fixed_width: f32 = columns.iter().filter_map(|column| column.width).sum();
+    let num_flexible = columns
+        .iter()
+        .filter(|column| column.width.is_none())
+        .count();
+    let flexible_width = if num_flexible == 0 {
+        0.0
+    } else {
+        ((width - fixed_width - num_spacings * spacing) / num_flexible as f32).max(0.0)
+    };
+
+    let mut left = 0.0;
+    columns
+        .iter()
+        .map(|column| {
+            let width = column.width.unwrap_or(flexible_width);
+            let layout = ColumnLayout {
+                left,
+                width,
+                align: column.align,
+            };
+            left += width + spacing;
+            layout
+        })
+        .collect()
+}
+
+/// Lay out one ui per cell, side by side, and allocate the space of the tallest.
+fn add_row(
+    ui: &mut Ui,
+    layout: &[ColumnLayout],
+    width: f32,
+    table_id: Id,
+    row: usize,
+    add_cells: impl FnOnce(&mut TableRow<'_>),
+) -> Rect {
+    let mut row = TableRow {
+        parent: ui,
+        table_id,
+        row,
+        layout,
+        top_left: ui.available().min,
+        num_cells: 0,
+        height: 0.0,
+    };
+    add_cells(&mut row);
+    let height = row.height;
+    ui.allocate_space(vec2(width, height))
+}
+
+/// The rows of a `Table`
+pub struct TableBody<'a> {
+    ui: &'a mut Ui,
+    id: Id,
+    layout: &'a [ColumnLayout],
+    width: f32,
+    striped: bool,
+    num_rows: usize,
+}
+
+impl<'a> TableBody<'a> {
+    /// Add a row. Add cells to it left-to-right, one per column.
+    pub fn row(&mut self, add_cells: impl FnOnce(&mut TableRow<'_>)) -> Rect {
+        let where_to_put_background = self.ui.paint_list_len();
+
+        let rect = add_row(
+            self.ui,
+            self.layout,
+            self.width,
+            self.id,
+            self.num_rows,
+            add_cells,
+        );
+
+        if self.striped && self.num_rows % 2 == 1 {
+            self.ui.insert_paint_cmd(
+                where_to_put_background,
+                PaintCmd::Rect {
+                    rect,
+                    corner_radius: 0.0,
+                    fill: Some(color::gray(128, 12)),
+                    outline: None,
+                },
+            );
+        }
+        self.num_rows += 1;
+
+        rect
+    }
+}
+
+/// The cells of a row in a `Table`
+pub struct TableRow<'a> {
+    parent: &'a Ui,
+    table_id: Id,
+    row: usize,
+    layout: &'a [ColumnLayout],
+    top_left: Pos2,
+    num_cells: usize,
+    /// Of the tallest cell so far
+    height: f32,
+}
+
+impl<'a> TableRow<'a> {
+    /// Add the next cell. Does nothing if there are no more columns.
+    pub fn cell<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> Option<R> {
+        let column = *self.layout.get(self.num_cells)?;
+        let cell_rect = Rect::from_min_size(
+            self.top_left + vec2(column.left, 0.0),
+            vec2(column.width, f32::INFINITY),
+        );
+        let mut cell_ui = self.parent.child_ui(cell_rect);
+        cell_ui.set_layout(Layout::vertical(column.align));
+        let mut clip_rect = self.parent.clip_rect();
+        clip_rect.min.x = clip_rect.min.x.max(cell_rect.left());
+        clip_rect.max.x = clip_rect.max.x.min(cell_rect.right());
+        cell_ui.set_clip_rect(clip_rect);
+
+        let ret = add_contents(&mut cell_ui);
+        self.num_cells += 1;
+        self.height = self.height.max(cell_ui.bounding_size().y);
+        Some(ret)
+    }
+
+    /// Add a cell with a single line of text
+    pub fn label(&mut self, text: impl Into<String>) {
+        self.cell(|ui| ui.add(Label::new(text).multiline(false)));
+    }
+
+    /// Add a cell with a single line of text that can be edited in place.
+    /// Double-click it (or press F2 when it has keyboard focus) to start editing.
+    /// Return keeps the edit and goes on to the cell below, tab to the next editable cell.
+    /// Escape cancels the edit.
+    ///
+    /// Returns `true` when `text` was changed.
+    pub fn edit_cell(&mut self, text: &mut String) -> bool {
+        let (table_id, cell) = (self.table_id, (self.row, self.num_cells));
+        let cell_id = table_id.with(cell);
+        self.cell(|ui| {
+            let mut state = ui.memory().tables.remove(&table_id).unwrap_or_default();
+            if state.tab_from.filter(|&tab_from| cell > tab_from).is_some() {
+                state.editing = Some(cell);
+                state.tab_from = None;
+                state.edit_text = None;
+            }
+
+            let mut changed = false;
+            if state.editing == Some(cell) {
+                state.shown = true;
+                let text_edit_id = TextEdit::id_for(ui, cell_id);
+                let just_started = state.edit_text.is_none();
+                if just_started {
+                    ui.request_kb_focus(text_edit_id);
+                }
+                let edit_text = state.edit_text.get_or_insert_with(|| text.clone());
+                ui.add(TextEdit::new(edit_text).id(cell_id).multiline(false));
+
+                // The keys that moved the edit here this frame are not for this cell:
+                if !just_started {
+                    let input = ui.input();
+                    let commit = input.key_pressed(Key::Return)
+                        || input.key_pressed(Key::Tab)
+                        || !ui.has_kb_focus(text_edit_id);
+                    if ui.ctx().consume_key(Key::Escape) {
+                        state = Default::default();
+                    } else if commit {
+                        let edit_text = state.edit_text.take().unwrap_or_default();
+                        changed = *text != edit_text;
+                        *text = edit_text;
+                        state.editing = None;
+                        if input.key_pressed(Key::Return) {
+                            state.editing = Some((cell.0 + 1, cell.1));
+                        } else if input.key_pressed(Key::Tab) {
+                            state.tab_from = Some(cell);
+                        }
+                    }
+                }
+            } else {
+                let available = ui.available();
+                let label_rect = ui.add(Label::new(text.as_str()).multiline(false)).rect;
+                // The whole width of the cell, so that short (or empty) texts are easy to hit:
+                let rect = Rect::from_min_max(
+                    pos2(available.left(), label_rect.top()),
+                    pos2(available.right(), label_rect.bottom()),
+                );
+                let interact = ui.interact(rect, cell_id, Sense::click());
+                if interact.clicked {
+                    ui.request_kb_focus(cell_id);
+                }
+                let pressed_f2 = interact.has_kb_focus && ui.input().key_pressed(Key::F2);
+                if interact.double_clicked || pressed_f2 {
+                    state = State {
+                        editing: Some(cell),
+                        ..Default::default()
+                    };
+                }
+            }
+
+            ui.memory().tables.insert(table_id, state);
+            changed
+        })
+        .unwrap_or(false)
+    }
+}
@@ -0,0 +1,463 @@
+use std::hash::Hash;
+
+use crate::{paint::*, *};
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+pub(crate) struct State {
+    /// Time at the left edge of the tracks
+    view_start: f32,
+
+    /// Zoom level. `None` means "fit the whole duration".
+    points_per_second: Option<f32>,
+
+    /// While dragging a clip or keyframe: where on the item (in seconds) we grabbed it.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    drag_offset: Option<f32>,
+}
+
+/// Maps between time and screen x coordinate
+#[derive(Clone, Copy, Debug)]
+struct Transform {
+    /// Screen x of `view_start`
+    left: f32,
+    view_start: f32,
+    points_per_second: f32,
+}
+
+impl Transform {
+    fn x_from_time(&self, time: f32) -> f32 {
+        self.left + (time - self.view_start) * self.points_per_second
+    }
+
+    fn time_from_x(&self, x: f32) -> f32 {
+        self.view_start + (x - self.left) / self.points_per_second
+    }
+}
+
+/// Don't zoom in further than this
+const MAX_POINTS_PER_SECOND: f32 = 10_000.0;
+
+/// Candidate distances (in seconds) between the labeled ticks of the ruler
+const RULER_STEPS: [f32; 16] = [
+    0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0, 600.0,
+];
+
+/// A time ruler over a stack of tracks with clips and keyframes on them,
+/// and a playhead you scrub by dragging on the ruler.
+///
+/// Scroll to zoom around the mouse, drag an empty part of a track to pan.
+///
+/// ```ignore
+/// Timeline::new("timeline")
+///     .duration(10.0)
+///     .frame_rate(24.0)
+///     .show(ui, &mut time, |timeline| {
+///         timeline.track("Clips", |track| {
+///             track.clip("intro", &mut intro_start, 2.0, "Intro");
+///         });
+///         timeline.track("Keys", |track| {
+///             for (i, key) in keys.iter_mut().enumerate() {
+///                 track.keyframe(i, key);
+///             }
+///         });
+///     });
+/// ```
+pub struct Timeline {
+    id_source: Id,
+    duration: f32,
+    frame_rate: Option<f32>,
+    names_width: f32,
+}
+
+impl Timeline {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            duration: 10.0,
+            frame_rate: None,
+            names_width: 80.0,
+        }
+    }
+
+    /// In seconds. Nothing can be moved past the end.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Snap the playhead, clips and keyframes to frame boundaries.
+    pub fn frame_rate(mut self, frame_rate: f32) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+
+    /// Width of the track names to the left of the tracks
+    pub fn names_width(mut self, names_width: f32) -> Self {
+        self.names_width = names_width;
+        self
+    }
+
+    /// `time` is the position of the playhead, in seconds.
+    /// The returned response is that of the ruler: it is `active` while scrubbing.
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        time: &mut f32,
+        add_tracks: impl FnOnce(&mut TimelineUi<'_>),
+    ) -> GuiResponse {
+        let Self {
+            id_source,
+            duration,
+            frame_rate,
+            names_width,
+        } = self;
+        let duration = duration.max(f32::EPSILON);
+        let snap = |time: f32| snap_to_frame(frame_rate, time);
+
+        let id = ui.make_child_id(id_source);
+        let mut state = ui.memory().timelines.get(&id).cloned().unwrap_or_default();
+
+        let width = ui.available().width();
+        let time_width = (width - names_width).max(1.0);
+        let fit_points_per_second = time_width / duration;
+        let clamp_zoom = |points_per_second: f32| {
+            let min = 0.5 * fit_points_per_second;
+            clamp(points_per_second, min..=MAX_POINTS_PER_SECOND.max(min))
+        };
+        let transform = Transform {
+            left: ui.available().left() + names_width,
+            view_start: state.view_start,
+            points_per_second: clamp_zoom(state.points_per_second.unwrap_or(fit_points_per_second)),
+        };
+
+        let mut drag = ItemDrag {
+            offset: state.drag_offset,
+            active: false,
+        };
+        let mut pan = 0.0;
+
+        let (response, rect) = ui.vertical(|ui| {
+            let mut style = ui.style().clone();
+            style.item_spacing.y = 0.0; // Tracks should touch
+            ui.set_style(style);
+
+            // Ruler:
+            let ruler_height = ui.fonts()[TextStyle::Body].line_spacing() + 8.0;
+            let ruler_rect = ui.allocate_space(vec2(width, ruler_height));
+            let ruler_rect = Rect::from_min_max(
+                pos2(ruler_rect.left() + names_width, ruler_rect.top()),
+                ruler_rect.max,
+            );
+            let ruler_interact = ui.interact(ruler_rect, id.with("ruler"), Sense::click_and_drag());
+            if ruler_interact.active {
+                if let Some(mouse_pos) = ui.input().mouse.pos {
+                    *time = clamp(snap(transform.time_from_x(mouse_pos.x)), 0.0..=duration);
+                }
+            }
+            paint_ruler(ui, ruler_rect, transform, duration, frame_rate);
+
+            add_tracks(&mut TimelineUi {
+                ui,
+                id,
+                names_width,
+                transform,
+                duration,
+                frame_rate,
+                drag: &mut drag,
+                pan: &mut pan,
+            });
+
+            ui.response(ruler_interact)
+        });
+
+        // Zoom around the mouse, and pan with sideways scrolling:
+        let scroll_delta = ui
+            .interact(rect, id.with("wheel"), Sense::scroll())
+            .scroll_delta;
+        if let Some(mouse_pos) = ui.input().mouse.pos {
+            if scroll_delta != Vec2::zero() {
+                let mouse_time = transform.time_from_x(mouse_pos.x);
+                let points_per_second =
+                    clamp_zoom(transform.points_per_second * (scroll_delta.y / 200.0).exp());
+                state.points_per_second = Some(points_per_second);
+                state.view_start = mouse_time
+                    - (mouse_pos.x - transform.left) / points_per_second
+                    - scroll_delta.x / points_per_second;
+            }
+        }
+        state.view_start -= pan / transform.points_per_second;
+        state.view_start = clamp(state.view_start, 0.0..=duration);
+        state.drag_offset = if drag.active { drag.offset } else { None };
+
+        // Playhead:
+        let playhead_x = ui.round_to_pixel(transform.x_from_time(*time));
+        if transform.left <= playhead_x && playhead_x <= rect.right() {
+            let color = ui.style().interact.active.stroke_color;
+            ui.add_paint_cmd(PaintCmd::line_segment(
+                [
+                    pos2(playhead_x, rect.top()),
+                    pos2(playhead_x, rect.bottom()),
+                ],
+                color,
+                2.0,
+            ));
+        }
+
+        ui.memory().timelines.insert(id, state);
+        response
+    }
+}
+
+fn snap_to_frame(frame_rate: Option<f32>, time: f32) -> f32 {
+    match frame_rate {
+        Some(frame_rate) => (time * frame_rate).round() / frame_rate,
+        None => time,
+    }
+}
+
+fn paint_ruler(
+    ui: &mut Ui,
+    rect: Rect,
+    transform: Transform,
+    duration: f32,
+    frame_rate: Option<f32>,
+) {
+    ui.add_paint_cmd(PaintCmd::Rect {
+        rect,
+        corner_radius: 0.0,
+        fill: Some(ui.style().dark_bg_color),
+        outline: None,
+    });
+
+    let color = ui.style().text_color;
+    let line_width = ui.style().thin_outline.width;
+    let end_time = transform.time_from_x(rect.right()).min(duration);
+
+    let tick = |ui: &mut Ui, time: f32, height: f32| {
+        let x = ui.round_to_pixel(transform.x_from_time(time));
+        ui.add_paint_cmd(PaintCmd::line_segment(
+            [pos2(x, rect.bottom() - height), pos2(x, rect.bottom())],
+            color,
+            line_width,
+        ));
+        x
+    };
+
+    // Room for a label between each labeled tick:
+    let min_step = 60.0 / transform.points_per_second;
+    let step = RULER_STEPS
+        .iter()
+        .copied()
+        .find(|&step| step >= min_step)
+        .unwrap_or(RULER_STEPS[RULER_STEPS.len() - 1]);
+    let decimals = if step >= 1.0 {
+        0
+    } else if step >= 0.1 {
+        1
+    } else {
+        2
+    };
+
+    // Frames, if they are far enough apart to be told apart:
+    let minor_step = match frame_rate {
+        Some(frame_rate) if transform.points_per_second / frame_rate >= 6.0 => 1.0 / frame_rate,
+        _ => step / 5.0,
+    };
+    if minor_step * transform.points_per_second >= 6.0 {
+        let first = (transform.view_start / minor_step).ceil() as i64;
+        let last = (end_time / minor_step).floor() as i64;
+        for i in first..=last {
+            tick(ui, i as f32 * minor_step, 3.0);
+        }
+    }
+
+    let first = (transform.view_start / step).ceil() as i64;
+    let last = (end_time / step).floor() as i64;
+    for i in first..=last {
+        let time = i as f32 * step;
+        let x = tick(ui, time, 0.5 * rect.height());
+        let text = format!("{:.*}", decimals, time);
+        let galley = ui.fonts()[TextStyle::Body].layout_single_line(text);
+        if x + 2.0 + galley.size.x <= rect.right() {
+            ui.add_galley(
+                pos2(x + 2.0, rect.top()),
+                galley,
+                TextStyle::Body,
+                Some(color),
+            );
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Shared by all items of a `Timeline`, since only one can be dragged at a time.
+struct ItemDrag {
+    offset: Option<f32>,
+    /// Was any item dragged this frame?
+    active: bool,
+}
+
+/// Use this to add tracks to a `Timeline`.
+pub struct TimelineUi<'a> {
+    ui: &'a mut Ui,
+    id: Id,
+    names_width: f32,
+    transform: Transform,
+    duration: f32,
+    frame_rate: Option<f32>,
+    drag: &'a mut ItemDrag,
+    /// How far (in points) the tracks were dragged this frame
+    pan: &'a mut f32,
+}
+
+impl<'a> TimelineUi<'a> {
+    /// Add a track (a row) with the given name to the left of it.
+    pub fn track(
+        &mut self,
+        name: impl Into<String>,
+        add_items: impl FnOnce(&mut TrackUi<'_>),
+    ) -> Rect {
+        let name = name.into();
+        let track_id = self.id.with(&name);
+        let height = self.ui.style().clickable_diameter + 4.0;
+        let rect = self
+            .ui
+            .allocate_space(vec2(self.ui.available().width(), height));
+        let time_rect =
+            Rect::from_min_max(pos2(rect.left() + self.names_width, rect.top()), rect.max);
+
+        let style = self.ui.style();
+        let outline = style.thin_outline;
+        let text_color = style.text_color;
+        self.ui.add_paint_cmd(PaintCmd::Rect {
+            rect: time_rect,
+            corner_radius: 0.0,
+            fill: None,
+            outline: Some(outline),
+        });
+
+        let galley = self.ui.fonts()[TextStyle::Body].layout_single_line(name);
+        let text_pos = pos2(rect.left(), rect.center().y - 0.5 * galley.size.y);
+        self.ui
+            .add_galley(text_pos, galley, TextStyle::Body, Some(text_color));
+
+        let mut time_ui = self.ui.child_ui(time_rect);
+        time_ui.set_clip_rect(time_rect.intersect(self.ui.clip_rect()));
+
+        add_items(&mut TrackUi {
+            ui: &mut time_ui,
+            id: track_id,
+            rect: time_rect,
+            transform: self.transform,
+            duration: self.duration,
+            frame_rate: self.frame_rate,
+            drag: self.drag,
+        });
+
+        // After the items, so that they get the drags first:
+        let background = time_ui.interact(time_rect, track_id.with("background"), Sense::drag());
+        if background.active {
+            *self.pan += time_ui.input().mouse.delta.x;
+        }
+
+        rect
+    }
+}
+
+/// Use this to add clips and keyframes to a track of a `Timeline`.
+pub struct TrackUi<'a> {
+    ui: &'a mut Ui,
+    id: Id,
+    rect: Rect,
+    transform: Transform,
+    duration: f32,
+    frame_rate: Option<f32>,
+    drag: &'a mut ItemDrag,
+}
+
+impl<'a> TrackUi<'a> {
+    /// A bar from `start` to `start + duration` that can be dragged along the track.
+    pub fn clip(
+        &mut self,
+        id_source: impl Hash,
+        start: &mut f32,
+        duration: f32,
+        text: impl Into<String>,
+    ) -> GuiResponse {
+        let id = self.id.with(id_source);
+        let clip_rect = Rect::from_min_max(
+            pos2(self.transform.x_from_time(*start), self.rect.top() + 2.0),
+            pos2(
+                self.transform.x_from_time(*start + duration),
+                self.rect.bottom() - 2.0,
+            ),
+        );
+        let interact = self.ui.interact(clip_rect, id, Sense::click_and_drag());
+        self.drag_item(&interact, start, duration);
+
+        let style = *self.ui.style().interact(&interact);
+        self.ui.add_paint_cmd(PaintCmd::Rect {
+            rect: clip_rect,
+            corner_radius: 3.0,
+            fill: Some(style.fill),
+            outline: style.rect_outline,
+        });
+
+        // Keep the text inside the clip:
+        let parent_clip_rect = self.ui.clip_rect();
+        self.ui.set_clip_rect(parent_clip_rect.intersect(clip_rect));
+        let galley = self.ui.fonts()[TextStyle::Body].layout_single_line(text.into());
+        let text_pos = pos2(
+            clip_rect.left() + 4.0,
+            clip_rect.center().y - 0.5 * galley.size.y,
+        );
+        self.ui
+            .add_galley(text_pos, galley, TextStyle::Body, Some(style.stroke_color));
+        self.ui.set_clip_rect(parent_clip_rect);
+
+        self.ui.response(interact)
+    }
+
+    /// A diamond at `time` that can be dragged along the track.
+    pub fn keyframe(&mut self, id_source: impl Hash, time: &mut f32) -> GuiResponse {
+        let id = self.id.with(id_source);
+        let radius = 0.3 * self.rect.height();
+        let center = pos2(self.transform.x_from_time(*time), self.rect.center().y);
+        let rect = Rect::from_center_size(center, Vec2::splat(2.0 * radius));
+        let interact = self.ui.interact(rect, id, Sense::click_and_drag());
+        self.drag_item(&interact, time, 0.0);
+
+        let style = *self.ui.style().interact(&interact);
+        self.ui.add_paint_cmd(PaintCmd::Path {
+            path: Path::from_point_loop(&[
+                rect.center_top(),
+                rect.right_center(),
+                rect.center_bottom(),
+                rect.left_center(),
+            ]),
+            closed: true,
+            fill: Some(style.fill),
+            outline: Some(LineStyle::new(style.stroke_width, style.stroke_color)),
+        });
+
+        self.ui.response(interact)
+    }
+
+    /// Move an item starting at `time` along with the mouse,
+    /// keeping the point we grabbed it at under the mouse.
+    fn drag_item(&mut self, interact: &InteractInfo, time: &mut f32, item_duration: f32) {
+        if !interact.active {
+            return;
+        }
+        if let Some(mouse_pos) = self.ui.input().mouse.pos {
+            let mouse_time = self.transform.time_from_x(mouse_pos.x);
+            let offset = *self.drag.offset.get_or_insert(mouse_time - *time);
+            let new_time = snap_to_frame(self.frame_rate, mouse_time - offset);
+            *time = clamp(new_time, 0.0..=(self.duration - item_duration).max(0.0));
+            self.drag.active = true;
+        }
+    }
+}
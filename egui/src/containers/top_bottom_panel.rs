@@ -0,0 +1,115 @@
+use std::hash::Hash;
+
+use crate::{paint::*, *};
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct State {
+    /// Of the panel last frame. A bottom panel needs it to know where to start.
+    height: f32,
+}
+
+/// A strip along the top of a `Ui` (usually the one from `Context::begin_frame`), e.g. for a menu bar,
+/// with the widgets added to the `Ui` after it going below it.
+/// It is as high as its contents.
+///
+/// Add panels before the rest of the contents.
+/// A panel only takes space from what is left by the panels added before it.
+///
+/// ```ignore
+/// TopPanel::new("menu").show(&mut ui, |ui| {
+///     menu::bar(ui, |ui| menu::menu(ui, "File", |ui| { ui.button("Quit"); }));
+/// });
+/// BottomPanel::new("status").show(&mut ui, |ui| {
+///     ui.label("Ready");
+/// });
+/// ui.label("The rest of the screen");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TopPanel {
+    id_source: Id,
+}
+
+impl TopPanel {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+        }
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        show_panel(ui, self.id_source, true, add_contents)
+    }
+}
+
+/// A strip along the bottom of a `Ui`, e.g. for a status bar. See `TopPanel`.
+#[derive(Clone, Copy, Debug)]
+pub struct BottomPanel {
+    id_source: Id,
+}
+
+impl BottomPanel {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+        }
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        show_panel(ui, self.id_source, false, add_contents)
+    }
+}
+
+fn show_panel<R>(
+    ui: &mut Ui,
+    id_source: Id,
+    at_the_top: bool,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> R {
+    let id = ui.make_child_id(id_source);
+    let available = ui.available_finite();
+    let padding = ui.style().window_padding;
+
+    // The contents of a bottom panel go where they fit with the height of last frame:
+    let top = if at_the_top {
+        available.top()
+    } else {
+        let state = ui.memory().top_bottom_panels.get(&id).copied();
+        let height = state.map_or(ui.style().clickable_diameter + 2.0 * padding.y, |state| {
+            state.height
+        });
+        (available.bottom() - height).max(available.top())
+    };
+
+    let where_to_put_background = ui.paint_list_len();
+    let mut content_ui = ui.child_ui(Rect::from_min_max(
+        pos2(available.left(), top) + padding,
+        available.max - padding,
+    ));
+    content_ui.set_clip_rect(ui.clip_rect().intersect(available));
+    let ret = add_contents(&mut content_ui);
+
+    let height = (content_ui.child_bounds().height() + 2.0 * padding.y).min(available.height());
+    ui.memory().top_bottom_panels.insert(id, State { height });
+
+    let rect = ui.allocate_top_or_bottom_strip(at_the_top, height);
+    ui.insert_paint_cmd(
+        where_to_put_background,
+        PaintCmd::Rect {
+            rect,
+            corner_radius: 0.0,
+            fill: Some(ui.style().background_fill),
+            outline: None,
+        },
+    );
+    let edge_y = if at_the_top {
+        rect.bottom()
+    } else {
+        rect.top()
+    };
+    ui.add_paint_cmd(PaintCmd::LineSegment {
+        points: [pos2(rect.left(), edge_y), pos2(rect.right(), edge_y)],
+        style: ui.style().thin_outline,
+    });
+
+    ret
+}
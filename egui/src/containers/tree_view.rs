@@ -0,0 +1,344 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{paint::*, *};
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+pub(crate) struct State {
+    /// Nodes that are not in their default open/closed state.
+    toggled: HashSet<Id>,
+    selected: Option<Id>,
+}
+
+impl State {
+    fn is_open(&self, id: Id, default_open: bool) -> bool {
+        default_open != self.toggled.contains(&id)
+    }
+
+    fn set_open(&mut self, id: Id, default_open: bool, open: bool) {
+        if open == default_open {
+            self.toggled.remove(&id);
+        } else {
+            self.toggled.insert(id);
+        }
+    }
+}
+
+/// A node as it was laid out this frame, top to bottom. Used for keyboard navigation.
+struct VisibleNode {
+    id: Id,
+    parent: Option<Id>,
+    has_children: bool,
+}
+
+/// A compact tree of expandable nodes, with a single selected node.
+///
+/// Click a node to select it. Click the arrow (or double-click the node) to expand/collapse it.
+/// Once selected, the arrow keys navigate the tree: up/down move the selection,
+/// right expands and left collapses (or jumps to the parent).
+///
+/// ```ignore
+/// TreeView::new("my_tree").show(ui, |tree| {
+///     tree.node("fruits", "Fruits", |tree| {
+///         tree.leaf("apple", "Apple");
+///         tree.leaf("banana", "Banana");
+///     });
+/// });
+/// ```
+pub struct TreeView {
+    id_source: Id,
+    indent: Option<f32>,
+    default_open: bool,
+}
+
+impl TreeView {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            indent: None,
+            default_open: false,
+        }
+    }
+
+    /// How much to indent children. Defaults to `style.start_icon_width`,
+    /// which lines up the children's arrows with the parent's text.
+    pub fn indent(mut self, indent: f32) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    /// Are nodes open the first time they are shown?
+    pub fn default_open(mut self, default_open: bool) -> Self {
+        self.default_open = default_open;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut TreeUi<'_>) -> R) -> R {
+        let Self {
+            id_source,
+            indent,
+            default_open,
+        } = self;
+
+        let id = ui.make_child_id(id_source);
+        let mut state = ui.memory().tree_views.get(&id).cloned().unwrap_or_default();
+        let mut visible_nodes = vec![];
+
+        let ret = ui
+            .vertical(|ui| {
+                let mut style = ui.style().clone();
+                style.item_spacing.y = 0.0;
+                ui.set_style(style);
+
+                let mut tree_ui = TreeUi {
+                    indent: indent.unwrap_or(ui.style().start_icon_width),
+                    ui,
+                    tree_id: id,
+                    parent: None,
+                    default_open,
+                    state: &mut state,
+                    visible_nodes: &mut visible_nodes,
+                };
+                add_contents(&mut tree_ui)
+            })
+            .0;
+
+        if ui.has_kb_focus(id) {
+            keyboard_navigation(ui.input(), &mut state, default_open, &visible_nodes);
+        }
+
+        ui.memory().tree_views.insert(id, state);
+        ret
+    }
+}
+
+/// Use this to add nodes to a `TreeView`.
+pub struct TreeUi<'a> {
+    ui: &'a mut Ui,
+    tree_id: Id,
+    parent: Option<Id>,
+    indent: f32,
+    default_open: bool,
+    state: &'a mut State,
+    visible_nodes: &'a mut Vec<VisibleNode>,
+}
+
+pub struct NodeResponse {
+    pub response: GuiResponse,
+    pub selected: bool,
+    pub open: bool,
+}
+
+impl<'a> TreeUi<'a> {
+    /// A node with children. `add_children` is only called when the node is open.
+    pub fn node(
+        &mut self,
+        id_source: impl Hash,
+        text: impl Into<String>,
+        add_children: impl FnOnce(&mut TreeUi<'_>),
+    ) -> NodeResponse {
+        let id = self.node_id(id_source);
+        let response = self.row(id, text.into(), true);
+
+        if response.open {
+            let indent = self.indent;
+            let child_rect = Rect::from_min_max(
+                self.ui.available().min + vec2(indent, 0.0),
+                self.ui.bottom_right(),
+            );
+            let mut child_ui = self.ui.child_ui(child_rect);
+            add_children(&mut TreeUi {
+                ui: &mut child_ui,
+                tree_id: self.tree_id,
+                parent: Some(id),
+                indent,
+                default_open: self.default_open,
+                state: self.state,
+                visible_nodes: self.visible_nodes,
+            });
+            let size = child_ui.bounding_size();
+
+            // Indentation guide, in line with the arrow of the parent:
+            if size.y > 0.0 {
+                let x = self.ui.round_to_pixel(child_rect.left() - 0.5 * indent);
+                let color = self.ui.style().thin_outline.color;
+                let width = self.ui.style().thin_outline.width;
+                self.ui.add_paint_cmd(PaintCmd::line_segment(
+                    [
+                        pos2(x, child_rect.top()),
+                        pos2(x, child_rect.top() + size.y),
+                    ],
+                    color,
+                    width,
+                ));
+            }
+
+            self.ui.allocate_space(vec2(indent, 0.0) + size);
+        }
+
+        response
+    }
+
+    /// A node without children.
+    pub fn leaf(&mut self, id_source: impl Hash, text: impl Into<String>) -> NodeResponse {
+        let id = self.node_id(id_source);
+        self.row(id, text.into(), false)
+    }
+
+    fn node_id(&self, id_source: impl Hash) -> Id {
+        self.parent.unwrap_or(self.tree_id).with(id_source)
+    }
+
+    fn row(&mut self, id: Id, text: String, has_children: bool) -> NodeResponse {
+        self.visible_nodes.push(VisibleNode {
+            id,
+            parent: self.parent,
+            has_children,
+        });
+
+        let ui = &mut *self.ui;
+        let text_style = TextStyle::Body;
+        let galley = ui.fonts()[text_style].layout_single_line(text);
+        let icon_width = ui.style().start_icon_width;
+        let row_height = galley.size.y + 2.0;
+        let rect = ui.allocate_space(vec2(ui.available().width(), row_height));
+        let interact = ui.interact(rect, id, Sense::click());
+        let icon_rect = Rect::from_min_size(rect.min, vec2(icon_width, row_height));
+
+        if interact.clicked {
+            let clicked_icon = matches!(ui.input().mouse.pos, Some(pos) if icon_rect.contains(pos));
+            if !clicked_icon || !has_children {
+                self.state.selected = Some(id);
+            }
+            if has_children && (clicked_icon || interact.double_clicked) {
+                let open = self.state.is_open(id, self.default_open);
+                self.state.set_open(id, self.default_open, !open);
+            }
+            ui.request_kb_focus(self.tree_id);
+        }
+
+        let selected = self.state.selected == Some(id);
+        let open = has_children && self.state.is_open(id, self.default_open);
+
+        let style = ui.style();
+        let bg_fill = if selected {
+            Some(style.interact.active.fill)
+        } else if interact.hovered {
+            style.interact.hovered.bg_fill
+        } else {
+            None
+        };
+        let text_color = if selected || interact.hovered {
+            style.interact.hovered.stroke_color
+        } else {
+            style.text_color
+        };
+        let arrow_color = style.interact(&interact).stroke_color;
+
+        if let Some(bg_fill) = bg_fill {
+            ui.add_paint_cmd(PaintCmd::Rect {
+                rect,
+                corner_radius: 2.0,
+                fill: Some(bg_fill),
+                outline: None,
+            });
+        }
+
+        if has_children {
+            let r = Rect::from_center_size(icon_rect.center(), Vec2::splat(0.5 * row_height));
+            let points = if open {
+                [r.left_top(), r.right_top(), r.center_bottom()]
+            } else {
+                [r.left_top(), r.right_center(), r.left_bottom()]
+            };
+            ui.add_paint_cmd(PaintCmd::Path {
+                path: Path::from_point_loop(&points),
+                closed: true,
+                fill: Some(arrow_color),
+                outline: None,
+            });
+        }
+
+        let text_pos = pos2(icon_rect.right(), rect.center().y - 0.5 * galley.size.y);
+        ui.add_galley(text_pos, galley, text_style, Some(text_color));
+
+        NodeResponse {
+            response: ui.response(interact),
+            selected,
+            open,
+        }
+    }
+}
+
+/// Several presses in one frame move the selection several steps.
+fn keyboard_navigation(
+    input: &InputState,
+    state: &mut State,
+    default_open: bool,
+    visible_nodes: &[VisibleNode],
+) {
+    let mut index = match visible_nodes
+        .iter()
+        .position(|node| Some(node.id) == state.selected)
+    {
+        Some(index) => index,
+        None => return,
+    };
+
+    for event in &input.events {
+        if let Event::Key { key, pressed: true } = event {
+            let node = &visible_nodes[index];
+            // Nodes collapsed by an earlier press this frame are still in `visible_nodes`:
+            let shown = |i: usize| is_shown(visible_nodes, i, state, default_open);
+            match key {
+                Key::Up => {
+                    if let Some(above) = (0..index).rev().find(|&i| shown(i)) {
+                        index = above;
+                    }
+                }
+                Key::Down => {
+                    if let Some(below) = (index + 1..visible_nodes.len()).find(|&i| shown(i)) {
+                        index = below;
+                    }
+                }
+                Key::Right if node.has_children => {
+                    state.set_open(node.id, default_open, true);
+                }
+                Key::Left => {
+                    if node.has_children && state.is_open(node.id, default_open) {
+                        state.set_open(node.id, default_open, false);
+                    } else if let Some(parent) = node.parent {
+                        if let Some(parent_index) =
+                            visible_nodes.iter().position(|node| node.id == parent)
+                        {
+                            index = parent_index;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            state.selected = Some(visible_nodes[index].id);
+        }
+    }
+}
+
+/// Are all the ancestors of `visible_nodes[index]` open?
+fn is_shown(
+    visible_nodes: &[VisibleNode],
+    index: usize,
+    state: &State,
+    default_open: bool,
+) -> bool {
+    let mut parent = visible_nodes[index].parent;
+    while let Some(parent_id) = parent {
+        if !state.is_open(parent_id, default_open) {
+            return false;
+        }
+        parent = visible_nodes
+            .iter()
+            .find(|node| node.id == parent_id)
+            .and_then(|node| node.parent);
+    }
+    true
+}
@@ -0,0 +1,239 @@
+//! An on-screen keyboard, for touch screens without a physical keyboard.
+
+use std::{hash::Hash, sync::Arc};
+
+use crate::{paint::*, *};
+
+/// Width and height of a normal key, in points.
+const KEY_SIZE: f32 = 36.0;
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+pub(crate) struct State {
+    /// Is the next letter upper case?
+    shift: bool,
+}
+
+/// Which keys a `VirtualKeyboard` has.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum KeyboardLayout {
+    /// Letters, digits, space, backspace and return.
+    #[default]
+    Qwerty,
+    /// Digits, a decimal point, backspace and return.
+    Numeric,
+}
+
+/// A key on a `VirtualKeyboard`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum VirtualKey {
+    Char(char),
+    Space,
+    Backspace,
+    Return,
+    Shift,
+    /// Empty space, in key widths
+    Gap(f32),
+}
+
+impl VirtualKey {
+    fn label(self, shift: bool) -> String {
+        match self {
+            VirtualKey::Char(c) if shift => c.to_ascii_uppercase().to_string(),
+            VirtualKey::Char(c) => c.to_string(),
+            VirtualKey::Space => "Space".to_owned(),
+            VirtualKey::Backspace => "Back".to_owned(),
+            VirtualKey::Return => "Return".to_owned(),
+            VirtualKey::Shift => "Shift".to_owned(),
+            VirtualKey::Gap(_) => String::new(),
+        }
+    }
+
+    /// In key widths
+    fn width(self) -> f32 {
+        match self {
+            VirtualKey::Char(_) => 1.0,
+            VirtualKey::Space => 5.0,
+            VirtualKey::Backspace | VirtualKey::Return | VirtualKey::Shift => 1.5,
+            VirtualKey::Gap(width) => width,
+        }
+    }
+}
+
+impl KeyboardLayout {
+    fn rows(self) -> Vec<Vec<VirtualKey>> {
+        let chars = |s: &str| s.chars().map(VirtualKey::Char).collect::<Vec<_>>();
+        let row = |before: Vec<VirtualKey>, s: &str, after: Vec<VirtualKey>| {
+            let mut row = before;
+            row.extend(chars(s));
+            row.extend(after);
+            row
+        };
+
+        match self {
+            KeyboardLayout::Qwerty => vec![
+                chars("1234567890"),
+                chars("qwertyuiop"),
+                row(vec![VirtualKey::Gap(0.5)], "asdfghjkl", vec![]),
+                row(
+                    vec![VirtualKey::Shift],
+                    "zxcvbnm",
+                    vec![VirtualKey::Backspace],
+                ),
+                vec![VirtualKey::Gap(2.0), VirtualKey::Space, VirtualKey::Return],
+            ],
+            KeyboardLayout::Numeric => vec![
+                chars("789"),
+                chars("456"),
+                chars("123"),
+                row(vec![], "0.", vec![VirtualKey::Backspace]),
+                vec![VirtualKey::Return],
+            ],
+        }
+    }
+}
+
+/// An on-screen keyboard, shown at the bottom of the screen.
+///
+/// Pressing a key sends the same events as a physical keyboard would
+/// (via `Context::inject_event`), so they arrive at whatever widget
+/// has keyboard focus (e.g. a `TextEdit`) in the next frame.
+///
+/// Useful for kiosks and other touch screens without a physical keyboard.
+///
+/// ```ignore
+/// if show_keyboard {
+///     VirtualKeyboard::new("keyboard")
+///         .layout(KeyboardLayout::Numeric)
+///         .show(ctx);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct VirtualKeyboard {
+    id: Id,
+    layout: KeyboardLayout,
+}
+
+impl VirtualKeyboard {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            layout: KeyboardLayout::default(),
+        }
+    }
+
+    pub fn layout(mut self, layout: KeyboardLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn show(self, ctx: &Arc<Context>) -> InteractInfo {
+        let VirtualKeyboard { id, layout } = self;
+
+        // Center at the bottom of the screen, based on the size last frame:
+        let last_size = ctx
+            .memory()
+            .areas
+            .get(id)
+            .map(|state| state.size)
+            .unwrap_or_default();
+        let screen = ctx.rect();
+        let pos = pos2(
+            screen.center().x - 0.5 * last_size.x,
+            screen.bottom() - last_size.y,
+        );
+
+        Area::new(id)
+            .order(Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| keyboard_ui(ui, id, layout))
+            })
+    }
+}
+
+fn keyboard_ui(ui: &mut Ui, id: Id, layout: KeyboardLayout) {
+    let mut state = ui
+        .memory()
+        .virtual_keyboards
+        .get(&id)
+        .cloned()
+        .unwrap_or_default();
+
+    for (row_nr, row) in layout.rows().into_iter().enumerate() {
+        ui.horizontal(|ui| {
+            for (key_nr, key) in row.into_iter().enumerate() {
+                let key_id = id.with(row_nr).with(key_nr);
+                if key_ui(ui, key_id, key, state.shift) {
+                    press(ui.ctx(), key, &mut state);
+                }
+            }
+        });
+    }
+
+    ui.memory().virtual_keyboards.insert(id, state);
+}
+
+/// Returns true if the key was clicked.
+fn key_ui(ui: &mut Ui, id: Id, key: VirtualKey, shift: bool) -> bool {
+    let spacing = ui.style().item_spacing.x;
+    let width = key.width() * KEY_SIZE + (key.width() - 1.0).max(0.0) * spacing;
+    let rect = ui.allocate_space(vec2(width, KEY_SIZE));
+    if let VirtualKey::Gap(_) = key {
+        return false;
+    }
+
+    let interact = ui.interact(rect, id, Sense::click());
+    let style = *ui.style().interact(&interact);
+    let fill = if key == VirtualKey::Shift && shift {
+        Some(ui.style().selection_color)
+    } else {
+        style.bg_fill
+    };
+    ui.add_paint_cmd(PaintCmd::Rect {
+        rect,
+        corner_radius: style.corner_radius,
+        fill,
+        outline: style.rect_outline,
+    });
+    ui.floating_text(
+        rect.center(),
+        key.label(shift),
+        TextStyle::Button,
+        (Align::Center, Align::Center),
+        Some(style.stroke_color),
+    );
+    interact.clicked
+}
+
+fn press(ctx: &Context, key: VirtualKey, state: &mut State) {
+    let press_and_release = |key| {
+        ctx.inject_event(Event::Key { key, pressed: true });
+        ctx.inject_event(Event::Key {
+            key,
+            pressed: false,
+        });
+    };
+
+    match key {
+        VirtualKey::Char(c) => {
+            let c = if state.shift {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            };
+            ctx.inject_event(Event::Text(c.to_string()));
+            state.shift = false;
+        }
+        VirtualKey::Space => ctx.inject_event(Event::Text(" ".to_owned())),
+        VirtualKey::Backspace => press_and_release(Key::Backspace),
+        VirtualKey::Return => {
+            // Like the integrations, which send both:
+            press_and_release(Key::Return);
+            ctx.inject_event(Event::Text("\n".to_owned()));
+        }
+        VirtualKey::Shift => state.shift = !state.shift,
+        VirtualKey::Gap(_) => {}
+    }
+}
@@ -12,6 +12,8 @@ pub struct Window<'open> {
     pub frame: Option<Frame>,
     pub resize: Resize,
     pub scroll: Option<ScrollArea>,
+    modal: bool,
+    focus: bool,
 }
 
 impl<'open> Window<'open> {
@@ -31,11 +33,9 @@ impl<'open> Window<'open> {
                 .outline(false)
                 .min_content_size([96.0, 32.0])
                 .min_desired_size([96.0, 200.0]),
-            scroll: Some(
-                ScrollArea::default()
-                    .always_show_scroll(false)
-                    .max_height(f32::INFINITY),
-            ), // As large as we can be
+            scroll: Some(default_scroll_area()),
+            modal: false,
+            focus: false,
         }
     }
 
@@ -46,6 +46,18 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// If true, bring the window in front of all other windows this frame,
+    /// e.g. when the user re-triggers an action whose window is already open behind others.
+    pub fn focus(mut self, focus: bool) -> Self {
+        self.focus = focus;
+        self
+    }
+
+    /// The layer of the window. Bring it to the front with `Context::move_to_top`.
+    pub fn layer(&self) -> Layer {
+        self.area.layer()
+    }
+
     /// Usage: `Winmdow::new(...).mutate(|w| w.resize = w.resize.auto_expand_width(true))`
     /// Not sure this is a good interface for this.
     pub fn mutate(mut self, mutate: impl Fn(&mut Self)) -> Self {
@@ -72,6 +84,25 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// Always at this position, ignoring the position remembered in `Memory`. Not movable.
+    pub fn fixed_pos(mut self, fixed_pos: impl Into<Pos2>) -> Self {
+        self.area = self.area.fixed_pos(fixed_pos);
+        self
+    }
+
+    /// Pin the window to a corner or edge of the screen, whatever its resolution.
+    /// E.g. `anchor((Align::Max, Align::Min), [-8.0, 8.0])` for the top right corner. Not movable.
+    pub fn anchor(mut self, align: (Align, Align), offset: impl Into<Vec2>) -> Self {
+        self.area = self.area.anchor(align, offset);
+        self
+    }
+
+    /// Keep the title bar on screen. On by default.
+    pub fn constrain(mut self, constrain: bool) -> Self {
+        self.area = self.area.constrain(constrain);
+        self
+    }
+
     pub fn default_size(mut self, default_size: impl Into<Vec2>) -> Self {
         self.resize = self.resize.default_size(default_size);
         self
@@ -86,6 +117,24 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// The user can't resize the window to smaller than this.
+    /// This is the size of the contents, i.e. without the title bar and frame.
+    pub fn min_size(mut self, min_size: impl Into<Vec2>) -> Self {
+        let min_size = min_size.into();
+        self.resize = self
+            .resize
+            .min_content_size(min_size)
+            .min_desired_size(min_size);
+        self
+    }
+
+    /// The user can't resize the window to larger than this.
+    /// This is the size of the contents, i.e. without the title bar and frame.
+    pub fn max_size(mut self, max_size: impl Into<Vec2>) -> Self {
+        self.resize = self.resize.max_size(max_size);
+        self
+    }
+
     /// Can you resize it with the mouse?
     /// Note that a window can still auto-resize
     pub fn resizable(mut self, resizable: bool) -> Self {
@@ -93,21 +142,59 @@ impl<'open> Window<'open> {
         self
     }
 
-    /// Not resizable, just takes the size of its contents.
+    /// Grows and shrinks to exactly fit its contents each frame, until the user resizes it.
+    /// Text is wrapped at the `default_size` width.
     pub fn auto_sized(mut self) -> Self {
-        self.resize = self.resize.auto_sized();
+        self.resize = self.resize.fit_to_content(true);
         self.scroll = None;
         self
     }
 
+    /// Not resizable. Grows to fit its contents until it reaches `max_size`,
+    /// after which you can scroll the contents.
+    pub fn fit_to_content_up_to(mut self, max_size: impl Into<Vec2>) -> Self {
+        self.resize = self.resize.fixed_size(max_size);
+        self.scroll = Some(
+            ScrollArea::default()
+                .always_show_scroll(false)
+                .max_height(f32::INFINITY)
+                .auto_shrink_width(true),
+        );
+        self
+    }
+
+    /// Use the style registered under this name with `Context::set_style_variant`,
+    /// e.g. for a borderless and transparent HUD.
+    pub fn style_variant(mut self, name: &'static str) -> Self {
+        self.area = self.area.style_variant(name);
+        self
+    }
+
+    /// A modal window blocks all input to the windows beneath it (and dims them) while open.
+    /// Pressing escape closes it (if it has an `open` bool).
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    /// Wrap the contents in a `ScrollArea` as large as the window, so contents
+    /// larger than the window can be scrolled to. On by default, except for `auto_sized`.
     pub fn scroll(mut self, scroll: bool) -> Self {
         if !scroll {
             self.scroll = None;
+        } else if self.scroll.is_none() {
+            self.scroll = Some(default_scroll_area());
         }
         self
     }
 }
 
+fn default_scroll_area() -> ScrollArea {
+    ScrollArea::default()
+        .always_show_scroll(false)
+        .max_height(f32::INFINITY) // As large as we can be
+}
+
 impl<'open> Window<'open> {
     pub fn show(
         self,
@@ -124,11 +211,13 @@ impl<'open> Window<'open> {
     ) -> Option<InteractInfo> {
         let Window {
             title_label,
-            open,
+            mut open,
             area,
             frame,
             resize,
             scroll,
+            modal,
+            focus,
         } = self;
 
         if matches!(open, Some(false)) {
@@ -137,6 +226,26 @@ impl<'open> Window<'open> {
 
         let window_id = Id::new(title_label.text());
         let area_layer = area.layer();
+        if focus {
+            ctx.move_to_top(area_layer);
+        }
+
+        if modal {
+            let mut memory = ctx.memory();
+            memory.open_modal(area_layer);
+            drop(memory);
+
+            // Painted first in our layer, so it ends up beneath the window but above everything else:
+            ctx.add_paint_cmd(
+                area_layer,
+                PaintCmd::Rect {
+                    rect: ctx.rect(),
+                    corner_radius: 0.0,
+                    fill: Some(color::black(96)),
+                    outline: None,
+                },
+            );
+        }
         let resize_id = window_id.with("resize");
         let collapsing_id = window_id.with("collapsing");
 
@@ -151,15 +260,17 @@ impl<'open> Window<'open> {
 
         let resize = resize.id(resize_id);
 
-        let frame = frame.unwrap_or_else(|| Frame::window(&ctx.style()));
+        let style = area.style(ctx);
+        let frame = frame.unwrap_or_else(|| Frame::window(&style));
+        let title_label = title_label.text_style(style.window.title_text_style);
 
         let mut area = area.begin(ctx);
 
         // First interact (move etc) to avoid frame delay:
         let last_frame_outer_rect = area.state().rect();
         let interaction = if possible.movable || possible.resizable {
-            let title_bar_height =
-                title_label.font_height(ctx.fonts()) + 1.0 * ctx.style().item_spacing.y; // this could be better
+            let title_bar_height = title_bar_height(&style, &title_label, ctx.fonts());
+            let title_bar_height = title_bar_height + 1.0 * style.item_spacing.y; // this could be better
             let margins = 2.0 * frame.margin + vec2(0.0, title_bar_height);
             interact(
                 ctx,
@@ -168,13 +279,18 @@ impl<'open> Window<'open> {
                 area_layer,
                 area.state_mut(),
                 window_id,
-                resize_id,
+                &resize,
                 last_frame_outer_rect,
             )
         } else {
             None
         };
         let hover_interaction = resize_hover(ctx, possible, area_layer, last_frame_outer_rect);
+        if let Some(interaction) = interaction {
+            if interaction.is_pure_move() && style.window.drag_opacity < 1.0 {
+                ctx.set_layer_opacity(area_layer, style.window.drag_opacity);
+            }
+        }
 
         let mut area_content_ui = area.content_ui(ctx);
 
@@ -215,6 +331,20 @@ impl<'open> Window<'open> {
             let outer_rect = frame.end(&mut area_content_ui);
             // END FRAME --------------------------------
 
+            // Escape closes the topmost modal, unless the contents used it (e.g. to cancel an edit),
+            // or it is for an open popup (which may be shown later this frame):
+            let closes_on_escape = modal
+                && open.is_some()
+                && ctx.memory().top_modal() == Some(area_layer)
+                && !ctx.memory().is_any_popup_open()
+                && ctx.consume_key(Key::Escape);
+            if closes_on_escape {
+                if let Some(open) = &mut open {
+                    **open = false;
+                }
+                ctx.memory().close_modal(area_layer);
+            }
+
             title_bar.ui(
                 &mut area_content_ui,
                 outer_rect,
@@ -233,14 +363,14 @@ impl<'open> Window<'open> {
                     &mut area_content_ui,
                     outer_rect,
                     interaction,
-                    ctx.style().interact.active,
+                    style.interact.active,
                 );
             } else if let Some(hover_interaction) = hover_interaction {
                 paint_frame_interaction(
                     &mut area_content_ui,
                     outer_rect,
                     hover_interaction,
-                    ctx.style().interact.hovered,
+                    style.interact.hovered,
                 );
             }
         }
@@ -297,7 +427,7 @@ fn interact(
     area_layer: Layer,
     area_state: &mut area::State,
     window_id: Id,
-    resize_id: Id,
+    resize: &Resize,
     rect: Rect,
 ) -> Option<WindowInteraction> {
     let window_interaction = window_interaction(
@@ -309,12 +439,28 @@ fn interact(
     )?;
     let new_rect = resize_window(ctx, &window_interaction)?;
 
-    let new_rect = ctx.round_rect_to_pixels(new_rect);
+    let mut new_rect = ctx.round_rect_to_pixels(new_rect);
+    if window_interaction.is_resize() {
+        let (min_size, max_size) = resize.size_range();
+        let size = (new_rect.size() - margins).min(max_size).max(min_size) + margins;
+        // Keep the edges that aren't dragged where they are:
+        if window_interaction.left {
+            new_rect.min.x = new_rect.max.x - size.x;
+        } else {
+            new_rect.max.x = new_rect.min.x + size.x;
+        }
+        if window_interaction.top {
+            new_rect.min.y = new_rect.max.y - size.y;
+        } else {
+            new_rect.max.y = new_rect.min.y + size.y;
+        }
+    }
     // TODO: add this to a Window state instead as a command "move here next frame"
 
     area_state.pos = new_rect.min;
 
     if window_interaction.is_resize() {
+        let resize_id = resize.explicit_id()?;
         let mut resize_state = ctx.memory().resize.get(&resize_id).cloned().unwrap();
         resize_state.requested_size = Some(new_rect.size() - margins);
         ctx.memory().resize.insert(resize_id, resize_state);
@@ -395,6 +541,10 @@ fn resize_hover(
     area_layer: Layer,
     rect: Rect,
 ) -> Option<WindowInteraction> {
+    if ctx.memory().is_beneath_modal(area_layer) {
+        return None;
+    }
+
     if let Some(mouse_pos) = ctx.input().mouse.pos {
         if let Some(top_layer) = ctx.layer_at(mouse_pos) {
             if top_layer != area_layer && top_layer.order != Order::Background {
@@ -512,6 +662,13 @@ fn paint_frame_interaction(
 
 // ----------------------------------------------------------------------------
 
+fn title_bar_height(style: &Style, title_label: &Label, fonts: &Fonts) -> f32 {
+    style
+        .window
+        .title_bar_height
+        .unwrap_or_else(|| title_label.font_height(fonts))
+}
+
 struct TitleBar {
     title_label: Label,
     title_galley: font::Galley,
@@ -527,7 +684,7 @@ fn show_title_bar(
     collapsing: &mut collapsing_header::State,
 ) -> TitleBar {
     let title_bar_and_rect = ui.inner_layout(Layout::horizontal(Align::Center), |ui| {
-        ui.set_desired_height(title_label.font_height(ui.fonts()));
+        ui.set_desired_height(title_bar_height(ui.style(), &title_label, ui.fonts()));
 
         let item_spacing = ui.style().item_spacing;
         let button_size = ui.style().start_icon_width;
@@ -589,6 +746,7 @@ impl TitleBar {
             self.rect.max.x = self.rect.max.x.max(content_rect.max.x);
         }
 
+        let open_is_some = open.is_some();
         if let Some(open) = open {
             // Add close button now that we know our full width:
             if self.close_button_ui(ui).clicked {
@@ -596,6 +754,23 @@ impl TitleBar {
             }
         }
 
+        // Now that we know our full width, we can align the title:
+        let item_spacing = ui.style().item_spacing;
+        let min_x = self.title_rect.left();
+        let mut max_x = self.rect.right() - item_spacing.x;
+        if open_is_some {
+            max_x -= ui.style().start_icon_width + item_spacing.x; // the close button
+        }
+        let width = self.title_rect.width();
+        let title_left = match ui.style().window.title_align {
+            Align::Min => min_x,
+            Align::Center => self.rect.center().x - 0.5 * width,
+            Align::Max => max_x - width,
+        };
+        // Never over the collapse button:
+        let title_left = title_left.min(max_x - width).max(min_x);
+        self.title_rect = self.title_rect.translate(vec2(title_left - min_x, 0.0));
+
         // TODO: pick style for title based on move interaction
         self.title_label
             .paint_galley(ui, self.title_rect.min, self.title_galley);
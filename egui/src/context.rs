@@ -1,15 +1,75 @@
-use std::sync::Arc;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use {ahash::AHashMap, parking_lot::Mutex};
 
 use crate::{layout::align_rect, paint::*, *};
 
+/// How many frames of `PaintStats` to keep for the diagnostics window
+const PAINT_STATS_HISTORY_LEN: usize = 120;
+
 #[derive(Clone, Copy, Default)]
 struct PaintStats {
     num_batches: usize,
     num_primitives: usize,
+    num_rejected: usize,
     num_vertices: usize,
     num_triangles: usize,
+    /// Texts laid out (by any widget) this frame.
+    num_galleys: usize,
+    /// Calls to `Context::memory` (and `Ui::memory`) this frame.
+    /// Not the number of entries read or changed: one call may touch none or many.
+    num_memory_accesses: usize,
+    /// Capacity (in bytes) of the vertex and index buffers of the triangles of this frame.
+    /// An upper bound of what the tessellator allocated, not the number of allocations.
+    triangle_buffer_bytes: usize,
+}
+
+/// A closure to run at the start of a frame. See `Context::defer`.
+type Command = Box<dyn FnOnce(&Arc<Context>) + Send>;
+
+/// Lets other threads run closures against the `Context`,
+/// without the app having to guard its own message queue in a mutex.
+/// Get one with `Context::command_sender`.
+///
+/// The closures are run (in the order they were sent) at the start of the next `begin_frame`,
+/// so e.g. a worker thread can send progress updates to `Memory`.
+/// This does not wake up the integration: it is your job to make sure a new frame is painted.
+///
+/// ```ignore
+/// let sender = ctx.command_sender();
+/// std::thread::spawn(move || {
+///     let result = do_work();
+///     sender.send(move |ctx| ctx.memory().open_popup(Id::new("work_done")));
+/// });
+/// ```
+#[derive(Clone)]
+pub struct CommandSender {
+    commands: Arc<Mutex<Vec<Command>>>,
+}
+
+impl CommandSender {
+    pub fn send(&self, command: impl FnOnce(&Arc<Context>) + Send + 'static) {
+        self.commands.lock().push(Box::new(command));
+    }
+}
+
+/// Does this event click the widget with keyboard focus?
+pub(crate) fn is_activation_event(event: &Event) -> bool {
+    match event {
+        Event::Key {
+            key: Key::Return,
+            pressed: true,
+        } => true,
+        // Space is only sent as text:
+        Event::Text(text) => text == " ",
+        _ => false,
+    }
 }
 
 /// Contains the input, style and output of all GUI commands.
@@ -20,6 +80,8 @@ struct PaintStats {
 pub struct Context {
     /// The default style for new `Ui`:s
     style: Mutex<Style>,
+    /// Named styles for different kinds of windows and areas. See `set_style_variant`.
+    style_variants: Mutex<AHashMap<String, Style>>,
     paint_options: Mutex<paint::PaintOptions>,
     /// None until first call to `begin_frame`.
     fonts: Option<Arc<Fonts>>,
@@ -33,14 +95,35 @@ pub struct Context {
     output: Mutex<Output>,
     /// Used to debug name clashes of e.g. windows
     used_ids: Mutex<AHashMap<Id, Pos2>>,
-
+    /// Used to debug widgets that lose their state because their `Id` keeps changing
+    id_change_tracker: Mutex<crate::id::IdChangeTracker>,
+    measure_tool: Mutex<crate::measure::MeasureTool>,
+    widget_search: Mutex<crate::widget_search::WidgetSearch>,
+    notifications: Mutex<crate::notifications::Notifications>,
+    /// Between `begin_frame` and `end_frame`
+    in_frame: Mutex<bool>,
+    /// Added to the input of the next frame. See `inject_event`.
+    injected_events: Mutex<Vec<Event>>,
+    /// Used up by `consume_shortcut` this frame.
+    consumed_shortcuts: Mutex<Vec<(Modifiers, Key)>>,
+    /// Used up by `consume_key` this frame.
+    consumed_keys: Mutex<Vec<Key>>,
+    /// Run at the start of the next frame. Shared by all copies of the context,
+    /// so that a `CommandSender` outlives `begin_frame`.
+    commands: Arc<Mutex<Vec<Command>>>,
+
+    /// Calls to `memory()` since the last frame was painted.
+    num_memory_accesses: AtomicUsize,
     paint_stats: Mutex<PaintStats>,
+    /// Most recent last
+    paint_stats_history: Mutex<VecDeque<PaintStats>>,
 }
 
 impl Clone for Context {
     fn clone(&self) -> Self {
         Context {
             style: Mutex::new(self.style()),
+            style_variants: Mutex::new(self.style_variants.lock().clone()),
             paint_options: Mutex::new(*self.paint_options.lock()),
             fonts: self.fonts.clone(),
             font_definitions: Mutex::new(self.font_definitions.lock().clone()),
@@ -49,7 +132,18 @@ impl Clone for Context {
             graphics: Mutex::new(self.graphics.lock().clone()),
             output: Mutex::new(self.output.lock().clone()),
             used_ids: Mutex::new(self.used_ids.lock().clone()),
+            id_change_tracker: Mutex::new(self.id_change_tracker.lock().clone()),
+            measure_tool: Mutex::new(*self.measure_tool.lock()),
+            widget_search: Mutex::new(self.widget_search.lock().clone()),
+            notifications: Mutex::new(self.notifications.lock().clone()),
+            in_frame: Mutex::new(*self.in_frame.lock()),
+            injected_events: Mutex::new(self.injected_events.lock().clone()),
+            consumed_shortcuts: Mutex::new(self.consumed_shortcuts.lock().clone()),
+            consumed_keys: Mutex::new(self.consumed_keys.lock().clone()),
+            commands: self.commands.clone(),
+            num_memory_accesses: AtomicUsize::new(self.num_memory_accesses.load(Ordering::Relaxed)),
             paint_stats: Mutex::new(*self.paint_stats.lock()),
+            paint_stats_history: Mutex::new(self.paint_stats_history.lock().clone()),
         }
     }
 }
@@ -64,6 +158,7 @@ impl Context {
     }
 
     pub fn memory(&self) -> parking_lot::MutexGuard<'_, Memory> {
+        self.num_memory_accesses.fetch_add(1, Ordering::Relaxed);
         self.memory.try_lock().expect("memory already locked")
     }
 
@@ -109,6 +204,20 @@ impl Context {
         *self.style.try_lock().expect("style already locked") = style;
     }
 
+    /// Register a named style (e.g. "hud" or "dialog"), for `Window`:s and `Area`:s
+    /// that pick it with `style_variant`.
+    pub fn set_style_variant(&self, name: impl Into<String>, style: Style) {
+        self.style_variants.lock().insert(name.into(), style);
+    }
+
+    /// The style registered under this name, or the default style if there is none.
+    pub fn style_variant(&self, name: &str) -> Style {
+        match self.style_variants.lock().get(name) {
+            Some(style) => style.clone(),
+            None => self.style(),
+        }
+    }
+
     pub fn pixels_per_point(&self) -> f32 {
         self.input.pixels_per_point
     }
@@ -136,20 +245,168 @@ impl Context {
     // ---------------------------------------------------------------------
 
     /// Call at the start of every frame.
-    /// Returns a master fullscreen UI, covering the entire screen.
-    pub fn begin_frame(self: &mut Arc<Self>, new_input: RawInput) -> Ui {
+    /// Returns a guard that derefs to a master fullscreen UI, covering the entire screen.
+    /// Call `FrameGuard::end` at the end of the frame to get what to paint.
+    ///
+    /// Panics if the previous frame was never ended.
+    pub fn begin_frame(self: &mut Arc<Self>, new_input: RawInput) -> FrameGuard {
+        assert!(
+            !self.is_in_frame(),
+            "Context::begin_frame called twice without ending the first frame"
+        );
         let mut self_: Self = (**self).clone();
         self_.begin_frame_mut(new_input);
         *self = Arc::new(self_);
-        self.fullscreen_ui()
+        self.run_commands();
+        if self.style().debug_widget_search {
+            self.widget_search_ui();
+        }
+        self.notifications_ui();
+        FrameGuard {
+            ui: Some(self.fullscreen_ui()),
+        }
+    }
+
+    fn widget_search_ui(self: &Arc<Self>) {
+        let widgets: Vec<crate::widget_search::FoundWidget> = self
+            .id_change_tracker
+            .lock()
+            .widgets_last_frame()
+            .map(|(id, rect, label)| (id, rect, label.map(ToOwned::to_owned)))
+            .collect();
+        let mut widget_search = self.widget_search.lock().clone(); // Don't hold the lock while adding widgets
+        widget_search.show(self, &widgets);
+        *self.widget_search.lock() = widget_search;
+    }
+
+    fn notifications_ui(self: &Arc<Self>) {
+        let mut notifications = self.notifications.lock().clone(); // Don't hold the lock while adding widgets
+        notifications.show(self);
+        *self.notifications.lock() = notifications;
+    }
+
+    /// Show a toast in the corner of the screen, e.g. `ctx.notify("Saved!", Level::Info)`.
+    /// It goes away after `Style::notification_duration` seconds.
+    pub fn notify(&self, text: impl Into<String>, level: Level) {
+        self.notifications.lock().push(text.into(), level);
+    }
+
+    /// Add an event to the input of the next frame, as if it came from the integration.
+    /// Used by e.g. `VirtualKeyboard`.
+    pub fn inject_event(&self, event: Event) {
+        self.injected_events.lock().push(event);
+    }
+
+    /// Was `key` pressed this frame, with exactly these modifiers held?
+    /// Only the first call per frame for a shortcut returns true, so it triggers one action.
+    /// Show it next to the action with e.g. `Button::shortcut_text`.
+    ///
+    /// ```ignore
+    /// if ctx.consume_shortcut(Modifiers::CTRL, Key::S) {
+    ///     save();
+    /// }
+    /// ```
+    pub fn consume_shortcut(&self, modifiers: Modifiers, key: Key) -> bool {
+        if self.input.modifiers != modifiers || !self.input.key_pressed(key) {
+            return false;
+        }
+        let mut consumed = self.consumed_shortcuts.lock();
+        if consumed.contains(&(modifiers, key)) {
+            false
+        } else {
+            consumed.push((modifiers, key));
+            true
+        }
+    }
+
+    /// Was `key` pressed this frame (with any modifiers), and has nothing else used it yet?
+    /// Only the first call per frame returns true. For keys with more than one meaning,
+    /// e.g. escape, which cancels an edit, closes a popup or closes a modal window, in that order.
+    pub fn consume_key(&self, key: Key) -> bool {
+        if !self.input.key_pressed(key) {
+            return false;
+        }
+        let mut consumed = self.consumed_keys.lock();
+        if consumed.contains(&key) {
+            false
+        } else {
+            consumed.push(key);
+            true
+        }
+    }
+
+    /// Run `command` at the start of the next frame.
+    /// To do this from another thread, use a `command_sender`.
+    pub fn defer(&self, command: impl FnOnce(&Arc<Context>) + Send + 'static) {
+        self.commands.lock().push(Box::new(command));
+    }
+
+    /// For running closures against the context from other threads. See `CommandSender`.
+    pub fn command_sender(&self) -> CommandSender {
+        CommandSender {
+            commands: self.commands.clone(),
+        }
     }
 
-    fn begin_frame_mut(&mut self, new_raw_input: RawInput) {
+    fn run_commands(self: &Arc<Self>) {
+        // Don't hold the lock while running them, so they can defer more commands to the next frame:
+        let commands = std::mem::take(&mut *self.commands.lock());
+        for command in commands {
+            command(self);
+        }
+    }
+
+    /// Are we between `begin_frame` and `end_frame`?
+    pub fn is_in_frame(&self) -> bool {
+        *self.in_frame.lock()
+    }
+
+    fn begin_frame_mut(&mut self, mut new_raw_input: RawInput) {
+        *self.in_frame.lock() = true;
         self.memory().begin_frame(&self.input);
 
         self.used_ids.lock().clear();
+        self.consumed_shortcuts.lock().clear();
+        self.consumed_keys.lock().clear();
+
+        let mut events = std::mem::take(&mut *self.injected_events.lock());
+        events.append(&mut new_raw_input.events);
+        new_raw_input.events = events;
+
+        let pressed_escape = new_raw_input.events.contains(&Event::Key {
+            key: Key::Escape,
+            pressed: true,
+        });
+
+        let mut style = self.style();
+        if style.debug_measure {
+            if pressed_escape {
+                style.debug_measure = false;
+                self.set_style(style.clone());
+                self.consumed_keys.lock().push(Key::Escape);
+            } else {
+                let widget_rects: Vec<Rect> = self
+                    .id_change_tracker
+                    .lock()
+                    .widget_rects_last_frame()
+                    .collect();
+                self.measure_tool
+                    .lock()
+                    .begin_frame(&mut new_raw_input, &widget_rects);
+            }
+        }
+
+        if pressed_escape && style.debug_widget_search {
+            style.debug_widget_search = false;
+            self.set_style(style);
+            self.consumed_keys.lock().push(Key::Escape);
+        }
 
-        self.input = std::mem::take(&mut self.input).begin_frame(new_raw_input);
+        let style = self.style();
+        let (key_repeat, drag_threshold) = (style.key_repeat, style.drag_threshold);
+        self.input =
+            std::mem::take(&mut self.input).begin_frame(new_raw_input, key_repeat, drag_threshold);
+        self.memory().begin_kb_focus_frame(&self.input);
 
         let mut font_definitions = self.font_definitions.lock();
         font_definitions.pixels_per_point = self.input.pixels_per_point;
@@ -157,14 +414,41 @@ impl Context {
         {
             self.fonts = Some(Arc::new(Fonts::from_definitions(font_definitions.clone())));
         }
+
+        // Now that we have the fonts we can turn wheel notches into points:
+        let scroll = self.style().scroll_area;
+        let points_per_line = scroll
+            .points_per_line
+            .unwrap_or_else(|| self.fonts()[TextStyle::Body].line_spacing());
+        self.input.scroll_delta = scroll.precise_scroll_speed * self.input.raw.scroll_delta
+            + scroll.lines_per_notch * points_per_line * self.input.raw.scroll_notches
+            + self.input.touches.pan_delta;
     }
 
-    /// Call at the end of each frame.
+    /// Call at the end of each frame (or use `FrameGuard::end`).
     /// Returns what has happened this frame (`Output`) as well as what you need to paint.
+    ///
+    /// Panics if there is no frame to end.
     #[must_use]
     pub fn end_frame(&self) -> (Output, PaintBatches) {
+        assert!(
+            self.is_in_frame(),
+            "Context::end_frame called without a matching begin_frame"
+        );
+        *self.in_frame.lock() = false;
         self.memory().end_frame();
-        let output: Output = std::mem::take(&mut self.output());
+        self.id_change_tracker.lock().end_frame(self.input.time);
+        if self.style().debug_measure {
+            let widget_rects: Vec<Rect> = self
+                .id_change_tracker
+                .lock()
+                .widget_rects_last_frame()
+                .collect();
+            self.measure_tool.lock().paint(self, &widget_rects);
+        }
+        let mut output: Output = std::mem::take(&mut self.output());
+        output.wants_pointer_input = self.wants_pointer_input();
+        output.wants_keyboard_input = self.wants_keyboard_input();
         let paint_batches = self.paint();
         (output, paint_batches)
     }
@@ -176,22 +460,35 @@ impl Context {
 
     fn paint(&self) -> PaintBatches {
         let mut paint_options = *self.paint_options.lock();
+        paint_options.pixels_per_point = self.pixels_per_point();
         paint_options.aa_size = 1.0 / self.pixels_per_point();
         paint_options.aa_size *= 1.5; // Looks better, but TODO: should not be needed
         let paint_commands = self.drain_paint_lists();
         let num_primitives = paint_commands.len();
-        let batches =
+        let (batches, num_rejected) =
             mesher::paint_commands_into_triangles(paint_options, self.fonts(), paint_commands);
 
         {
             let mut stats = PaintStats::default();
             stats.num_batches = batches.len();
             stats.num_primitives = num_primitives;
+            stats.num_rejected = num_rejected;
             for (_, triangles) in &batches {
                 stats.num_vertices += triangles.vertices.len();
                 stats.num_triangles += triangles.indices.len() / 3;
+                stats.triangle_buffer_bytes += triangles.vertices.capacity()
+                    * std::mem::size_of::<Vertex>()
+                    + triangles.indices.capacity() * std::mem::size_of::<u32>();
             }
+            stats.num_galleys = self.fonts().take_num_galleys();
+            stats.num_memory_accesses = self.num_memory_accesses.swap(0, Ordering::Relaxed);
             *self.paint_stats.lock() = stats;
+
+            let mut history = self.paint_stats_history.lock();
+            if history.len() >= PAINT_STATS_HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back(stats);
         }
 
         batches
@@ -258,6 +555,29 @@ impl Context {
         }
     }
 
+    /// The interactive widgets of last frame on this layer, top to bottom.
+    /// Used for the keyboard navigation of menus.
+    pub(crate) fn widgets_last_frame_on(&self, layer: Layer) -> Vec<(Id, Rect)> {
+        let mut widgets: Vec<(Id, Rect)> = self
+            .id_change_tracker
+            .lock()
+            .widgets_last_frame_on(layer)
+            .collect();
+        widgets.sort_by(|a, b| {
+            a.1.top()
+                .partial_cmp(&b.1.top())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        widgets
+    }
+
+    /// See `Ui::register_widget_label`.
+    pub fn register_widget_label(&self, id: Id, label: &str) {
+        if self.style.lock().debug_widget_search {
+            self.id_change_tracker.lock().register_label(id, label);
+        }
+    }
+
     // ---------------------------------------------------------------------
 
     pub fn layer_at(&self, pos: Pos2) -> Option<Layer> {
@@ -265,6 +585,37 @@ impl Context {
         self.memory().layer_at(pos, resize_interact_radius_side)
     }
 
+    /// Paint this layer (e.g. a `Window`, see `Window::layer`) above all others of the same `Order`,
+    /// as if the user had clicked it.
+    pub fn move_to_top(&self, layer: Layer) {
+        self.memory().areas.move_to_top(layer);
+    }
+
+    /// Is the mouse over an `Area` (e.g. a `Window`), or is egui using it for a click or drag?
+    /// If so, an app with egui on top of its own content (e.g. a game)
+    /// should not use the mouse itself, e.g. for moving the camera.
+    pub fn wants_pointer_input(&self) -> bool {
+        let memory = self.memory();
+        if memory.interaction.click_id.is_some()
+            || memory.interaction.drag_id.is_some()
+            || memory.top_modal().is_some()
+        {
+            return true;
+        }
+        drop(memory);
+        self.input
+            .mouse
+            .pos
+            .and_then(|pos| self.layer_at(pos))
+            .is_some()
+    }
+
+    /// Does a widget have keyboard focus (e.g. a `TextEdit`)?
+    /// If so, the app should not act on key presses itself, e.g. as shortcuts.
+    pub fn wants_keyboard_input(&self) -> bool {
+        self.memory().kb_focus_id.is_some()
+    }
+
     pub fn contains_mouse(&self, layer: Layer, clip_rect: Rect, rect: Rect) -> bool {
         let rect = rect.intersect(clip_rect);
         if let Some(mouse_pos) = self.input.mouse.pos {
@@ -282,6 +633,10 @@ impl Context {
         interaction_id: Option<Id>,
         sense: Sense,
     ) -> InteractInfo {
+        assert!(
+            self.is_in_frame(),
+            "Interacting with a Ui after its frame has ended. Was it kept from an earlier frame?"
+        );
         let interact_rect = rect.expand2(0.5 * self.style().item_spacing); // make it easier to click. TODO: nice way to do this
         let hovered = self.contains_mouse(layer, clip_rect, interact_rect);
 
@@ -290,29 +645,34 @@ impl Context {
             return InteractInfo {
                 rect,
                 hovered,
-                clicked: false,
-                double_clicked: false,
-                active: false,
+                hover_pos: self.input.mouse.pos.filter(|_| hovered),
+                ..InteractInfo::nothing()
             };
         }
         let interaction_id = interaction_id.unwrap();
 
+        self.id_change_tracker
+            .lock()
+            .register(interaction_id, rect, layer);
+
         let mut memory = self.memory();
 
+        if sense.click && !sense.drag && !memory.is_beneath_modal(layer) {
+            memory.interested_in_kb_focus(interaction_id);
+        }
+
         memory.interaction.click_interest |= hovered && sense.click;
         memory.interaction.drag_interest |= hovered && sense.drag;
 
         let active = memory.interaction.click_id == Some(interaction_id)
             || memory.interaction.drag_id == Some(interaction_id);
 
-        if self.input.mouse.pressed {
+        let mut info = if self.input.mouse.pressed {
             if hovered {
                 let mut info = InteractInfo {
                     rect,
                     hovered: true,
-                    clicked: false,
-                    double_clicked: false,
-                    active: false,
+                    ..InteractInfo::nothing()
                 };
 
                 if sense.click && memory.interaction.click_id.is_none() {
@@ -337,9 +697,7 @@ impl Context {
                 InteractInfo {
                     rect,
                     hovered,
-                    clicked: false,
-                    double_clicked: false,
-                    active: false,
+                    ..InteractInfo::nothing()
                 }
             }
         } else if self.input.mouse.released {
@@ -349,25 +707,86 @@ impl Context {
                 hovered,
                 clicked,
                 double_clicked: clicked && self.input.mouse.double_click,
+                triple_clicked: clicked && self.input.mouse.triple_click,
                 active,
+                drag_released: memory.interaction.drag_id == Some(interaction_id),
+                ..InteractInfo::nothing()
             }
         } else if self.input.mouse.down {
             InteractInfo {
                 rect,
                 hovered: hovered && active,
-                clicked: false,
-                double_clicked: false,
                 active,
+                ..InteractInfo::nothing()
             }
         } else {
             InteractInfo {
                 rect,
                 hovered,
-                clicked: false,
-                double_clicked: false,
                 active,
+                ..InteractInfo::nothing()
             }
+        };
+
+        info.has_kb_focus =
+            memory.kb_focus_id == Some(interaction_id) && !memory.is_beneath_modal(layer);
+
+        if memory.interaction.drag_id == Some(interaction_id) {
+            info.drag_started = self.input.mouse.drag_started;
+            info.drag_delta = self.input.mouse.delta;
+            info.drag_origin = self.input.mouse.press_origin;
+        }
+        if info.hovered {
+            info.hover_pos = self.input.mouse.pos;
         }
+        if info.active {
+            info.interact_pointer_pos = self.input.mouse.pos;
+        }
+
+        // The item of an open menu picked with the arrow keys (it also gets keyboard focus):
+        if memory.menu_highlight == Some(interaction_id) {
+            info.hovered = true;
+        }
+
+        // Return or space clicks the widget with keyboard focus, like the mouse would.
+        // Not for widgets that are also dragged (e.g. `TextEdit`), since they use the keys themselves.
+        if sense.click
+            && !sense.drag
+            && memory.kb_focus_id == Some(interaction_id)
+            && !memory.is_beneath_modal(layer)
+            && self.input.events.iter().any(is_activation_event)
+        {
+            info.clicked = true;
+        }
+
+        // The wheel, pinches and the secondary button go to the first interested widget under the mouse.
+        // Since widgets are added before the container they are in (e.g. a `ScrollArea`) ends,
+        // the innermost widget gets them.
+        if hovered && sense.scroll && self.input.scroll_delta != Vec2::zero() {
+            let scroll_id = memory.interaction.scroll_id.get_or_insert(interaction_id);
+            if *scroll_id == interaction_id {
+                info.scroll_delta = self.input.scroll_delta;
+            }
+        }
+        if hovered && sense.zoom && self.input.zoom_delta != 1.0 {
+            let zoom_id = memory.interaction.zoom_id.get_or_insert(interaction_id);
+            if *zoom_id == interaction_id {
+                info.zoom_delta = self.input.zoom_delta;
+            }
+        }
+        if sense.secondary_click {
+            if hovered
+                && self.input.mouse.secondary_pressed
+                && memory.interaction.secondary_click_id.is_none()
+            {
+                memory.interaction.secondary_click_id = Some(interaction_id);
+            }
+            info.secondary_clicked = hovered
+                && self.input.mouse.secondary_released
+                && memory.interaction.secondary_click_id == Some(interaction_id);
+        }
+
+        info
     }
 
     // ---------------------------------------------------------------------
@@ -462,6 +881,11 @@ impl Context {
         );
     }
 
+    /// Multiply the colors of everything on this layer with `opacity` this frame.
+    pub fn set_layer_opacity(&self, layer: Layer, opacity: f32) {
+        self.graphics().set_opacity(layer, opacity);
+    }
+
     pub fn add_paint_cmd(&self, layer: Layer, paint_cmd: PaintCmd) {
         self.graphics()
             .layer(layer)
@@ -469,6 +893,59 @@ impl Context {
     }
 }
 
+// ----------------------------------------------------------------------------
+
+/// One frame of a `Context`, returned by `Context::begin_frame`.
+///
+/// Derefs to the fullscreen `Ui` of the frame.
+/// Call `end` when you are done adding widgets to get what to paint.
+/// If it is dropped without calling `end`, the frame is still ended, but its output is lost.
+///
+/// ```ignore
+/// let mut ui = ctx.begin_frame(raw_input);
+/// ui.label("Hello world!");
+/// let (output, paint_batches) = ui.end();
+/// ```
+#[must_use = "call FrameGuard::end to get what to paint"]
+pub struct FrameGuard {
+    /// Only `None` after `end`.
+    ui: Option<Ui>,
+}
+
+impl FrameGuard {
+    /// Ends the frame. Same as `Context::end_frame`.
+    pub fn end(mut self) -> (Output, PaintBatches) {
+        let ui = self.ui.take().unwrap();
+        ui.ctx().end_frame()
+    }
+}
+
+impl std::ops::Deref for FrameGuard {
+    type Target = Ui;
+
+    fn deref(&self) -> &Ui {
+        self.ui.as_ref().unwrap()
+    }
+}
+
+impl std::ops::DerefMut for FrameGuard {
+    fn deref_mut(&mut self) -> &mut Ui {
+        self.ui.as_mut().unwrap()
+    }
+}
+
+impl Drop for FrameGuard {
+    fn drop(&mut self) {
+        if let Some(ui) = &self.ui {
+            // Someone may have called `Context::end_frame` already.
+            // Don't make a panic worse by ending the frame while unwinding from it.
+            if ui.ctx().is_in_frame() && !std::thread::panicking() {
+                let _ = ui.ctx().end_frame();
+            }
+        }
+    }
+}
+
 impl Context {
     pub fn settings_ui(&self, ui: &mut Ui) {
         use crate::containers::*;
@@ -505,9 +982,54 @@ impl Context {
                 ui.input().pixels_per_point,
             ));
 
+            let paint_stats = *self.paint_stats.lock();
             ui.add(label!("Painting:").text_style(TextStyle::Heading));
-            self.paint_stats.lock().ui(ui);
+            paint_stats.ui(ui);
+
+            ui.add(label!("Last frame, in total:").text_style(TextStyle::Heading));
+            paint_stats.churn_ui(ui);
         });
+
+        ui.collapsing("Id changes", |ui| self.id_changes_ui(ui));
+    }
+
+    /// Lists widgets whose `Id` changed while their rect stayed the same.
+    /// Hover an entry to highlight the widget.
+    fn id_changes_ui(&self, ui: &mut Ui) {
+        let tracker = self.id_change_tracker.lock().clone(); // Don't hold the lock while adding widgets
+
+        ui.add(label!(
+            "{} interactive widgets last frame",
+            tracker.num_widgets_last_frame()
+        ));
+        ui.add(
+            label!(
+                "Widgets that got a new Id without moving lose their state (e.g. a window that keeps resetting). \
+                The usual cause is an id source that changes every frame, like a counter or a changing label."
+            )
+            .auto_shrink(),
+        );
+
+        let mut num_changes = 0;
+        for change in tracker.changes().rev() {
+            num_changes += 1;
+            let response = ui.add(label!(
+                "{:?} -> {:?} at {:?}, {:.1} s ago",
+                change.old_id,
+                change.new_id,
+                change.rect.min,
+                self.input.time - change.time
+            ));
+            if response.hovered {
+                self.debug_rect(change.rect, color::RED, "Id changed");
+            }
+        }
+
+        if num_changes == 0 {
+            ui.label("No id changes detected");
+        } else if ui.add(Button::new("Clear")).clicked {
+            self.id_change_tracker.lock().clear_changes();
+        }
     }
 
     pub fn memory_ui(&self, ui: &mut crate::Ui) {
@@ -570,6 +1092,144 @@ impl Context {
 }
 
 impl Context {
+    /// A window with everything useful for a bug report:
+    /// version, settings, inspection, memory, fonts and recent paint stats.
+    pub fn diagnostics_window(self: &Arc<Self>, open: &mut bool) {
+        crate::containers::Window::new("Diagnostics")
+            .open(open)
+            .default_size(vec2(360.0, 480.0))
+            .scroll(true)
+            .show(self, |ui| self.diagnostics_ui(ui));
+    }
+
+    pub fn diagnostics_ui(&self, ui: &mut Ui) {
+        use crate::containers::*;
+
+        ui.add(label!("Egui version {}", env!("CARGO_PKG_VERSION")));
+        if ui
+            .add(Button::new("Copy diagnostics to clipboard"))
+            .tooltip_text("Attach this to your bug report")
+            .clicked
+        {
+            self.output().copied_text = self.diagnostics_report();
+        }
+
+        CollapsingHeader::new("Paint stats history")
+            .default_open(true)
+            .show(ui, |ui| self.paint_stats_history_ui(ui));
+
+        CollapsingHeader::new("Font atlas")
+            .default_open(false)
+            .show(ui, |ui| self.fonts().texture().ui(ui));
+
+        CollapsingHeader::new("Settings")
+            .default_open(false)
+            .show(ui, |ui| self.settings_ui(ui));
+
+        CollapsingHeader::new("Inspection")
+            .default_open(false)
+            .show(ui, |ui| self.inspection_ui(ui));
+
+        CollapsingHeader::new("Memory")
+            .default_open(false)
+            .show(ui, |ui| self.memory_ui(ui));
+    }
+
+    /// A plain text summary of the state of egui, suitable for bug reports.
+    pub fn diagnostics_report(&self) -> String {
+        let input = &self.input;
+        let texture = self.fonts().texture();
+        let paint_stats = *self.paint_stats.lock();
+        let max_vertices = self
+            .paint_stats_history
+            .lock()
+            .iter()
+            .map(|stats| stats.num_vertices)
+            .max()
+            .unwrap_or_default();
+        let num_id_changes = self.id_change_tracker.lock().changes().count();
+        let memory = self.memory();
+
+        let lines = [
+            format!("egui version: {}", env!("CARGO_PKG_VERSION")),
+            format!(
+                "screen size: {} x {} points, pixels_per_point: {}",
+                input.screen_size.x, input.screen_size.y, input.pixels_per_point
+            ),
+            format!("time: {:.3} s, dt: {:.4} s", input.time, input.dt),
+            format!("font texture: {} x {}", texture.width, texture.height),
+            format!(
+                "paint: {} batches, {} primitives ({} rejected), {} vertices (max {} recently), {} triangles",
+                paint_stats.num_batches,
+                paint_stats.num_primitives,
+                paint_stats.num_rejected,
+                paint_stats.num_vertices,
+                max_vertices,
+                paint_stats.num_triangles
+            ),
+            format!(
+                "memory: {} areas, {} collapsing headers, {} menu bars, {} resize areas, {} scroll areas",
+                memory.areas.count(),
+                memory.collapsing_headers.len(),
+                memory.menu_bar.len(),
+                memory.resize.len(),
+                memory.scroll_areas.len()
+            ),
+            format!("recent id changes: {}", num_id_changes),
+        ];
+        lines.join("\n")
+    }
+
+    fn paint_stats_history_ui(&self, ui: &mut Ui) {
+        let history = self.paint_stats_history.lock().clone(); // Don't hold the lock while adding widgets
+        if history.is_empty() {
+            ui.label("Nothing painted yet");
+            return;
+        }
+
+        let num_vertices = || history.iter().map(|stats| stats.num_vertices);
+        let min = num_vertices().min().unwrap_or_default();
+        let max = num_vertices().max().unwrap_or_default();
+        let mean = num_vertices().sum::<usize>() / history.len();
+        ui.add(label!(
+            "Vertices over the last {} frames: min {}, mean {}, max {}",
+            history.len(),
+            min,
+            mean,
+            max
+        ));
+
+        let rect = ui.allocate_space(vec2(ui.available().width(), 48.0));
+        ui.add_paint_cmd(PaintCmd::Rect {
+            rect,
+            corner_radius: 0.0,
+            fill: Some(ui.style().dark_bg_color),
+            outline: None,
+        });
+        let max = max.max(1) as f32;
+        let points: Vec<Pos2> = num_vertices()
+            .enumerate()
+            .map(|(i, n)| {
+                pos2(
+                    remap(
+                        i as f32,
+                        0.0..=(PAINT_STATS_HISTORY_LEN - 1) as f32,
+                        rect.range_x(),
+                    ),
+                    remap(n as f32, 0.0..=max, rect.bottom()..=rect.top()),
+                )
+            })
+            .collect();
+        if points.len() >= 2 {
+            ui.add_paint_cmd(PaintCmd::Path {
+                path: Path::from_open_points(&points),
+                closed: false,
+                fill: None,
+                outline: Some(LineStyle::new(1.0, color::LIGHT_BLUE)),
+            });
+        }
+    }
+
     pub fn style_ui(&self, ui: &mut Ui) {
         let mut style = self.style();
         style.ui(ui);
@@ -594,7 +1254,27 @@ impl PaintStats {
             .tooltip_text("Number of separate clip rectanlges");
         ui.add(label!("Primitives: {}", self.num_primitives))
             .tooltip_text("Boxes, circles, text areas etc");
+        ui.add(label!("Rejected: {}", self.num_rejected))
+            .tooltip_text(
+            "Primitives skipped because of NaN coordinates, negative sizes or an empty clip rect",
+        );
         ui.add(label!("Vertices: {}", self.num_vertices));
         ui.add(label!("Triangles: {}", self.num_triangles));
     }
+
+    /// What all the ui code together did last frame. Totals, not broken down per layer.
+    pub fn churn_ui(&self, ui: &mut Ui) {
+        ui.add(label!("Galleys laid out: {}", self.num_galleys))
+            .tooltip_text("Texts laid out by labels, buttons, text edits etc");
+        ui.add(label!("Paint commands: {}", self.num_primitives));
+        ui.add(label!("Calls to memory(): {}", self.num_memory_accesses))
+            .tooltip_text(
+                "Calls to Context::memory and Ui::memory, e.g. by widgets looking up their state",
+            );
+        ui.add(label!(
+            "Triangle buffers: {:.1} kB",
+            self.triangle_buffer_bytes as f32 / 1024.0
+        ))
+        .tooltip_text("Capacity of the vertex and index buffers the tessellator filled");
+    }
 }
@@ -33,6 +33,21 @@ impl ExampleApp {
         }
 
         show_menu_bar(ui, &mut self.open_windows);
+        if self.open_windows.panels {
+            BottomPanel::new("example_status_bar").show(ui, |ui| {
+                ui.label("A status bar in a bottom panel");
+            });
+            SidePanel::left("example_left_panel").show(ui, |ui| {
+                ui.add(label!("Left panel").text_style(TextStyle::Heading));
+                ui.label("Drag the edge to resize me.");
+            });
+            SidePanel::right("example_right_panel")
+                .default_width(150.0)
+                .show(ui, |ui| {
+                    ui.add(label!("Right panel").text_style(TextStyle::Heading));
+                    ui.label("Windows float above the panels.");
+                });
+        }
         self.windows(ui.ctx());
     }
 
@@ -56,6 +71,8 @@ impl ExampleApp {
 
         Window::new("Settings")
             .open(&mut open_windows.settings)
+            .min_size([200.0, 200.0])
+            .max_size([500.0, 800.0])
             .show(ctx, |ui| {
                 ctx.settings_ui(ui);
             });
@@ -68,11 +85,13 @@ impl ExampleApp {
 
         Window::new("Memory")
             .open(&mut open_windows.memory)
-            .resizable(false)
+            .fit_to_content_up_to([400.0, 600.0])
             .show(ctx, |ui| {
                 ctx.memory_ui(ui);
             });
 
+        ctx.diagnostics_window(&mut open_windows.diagnostics);
+
         fractal_clock.window(ctx, &mut open_windows.fractal_clock);
     }
 }
@@ -80,15 +99,18 @@ impl ExampleApp {
 // ----------------------------------------------------------------------------
 
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
 struct OpenWindows {
     // examples:
     examples: bool,
     fractal_clock: bool,
+    panels: bool,
 
     // egui stuff:
     settings: bool,
     inspection: bool,
     memory: bool,
+    diagnostics: bool,
 }
 
 impl Default for OpenWindows {
@@ -105,28 +127,35 @@ impl OpenWindows {
         Self {
             examples: false,
             fractal_clock: false,
+            panels: false,
 
             settings: false,
             inspection: false,
             memory: false,
+            diagnostics: false,
         }
     }
 }
 
 fn show_menu_bar(ui: &mut Ui, windows: &mut OpenWindows) {
+    let mut clear_memory = ui
+        .ctx()
+        .consume_shortcut(Modifiers::CTRL | Modifiers::SHIFT, Key::M);
     menu::bar(ui, |ui| {
         menu::menu(ui, "File", |ui| {
-            if ui.add(Button::new("Clear memory")).clicked {
-                *ui.ctx().memory() = Default::default();
-            }
+            clear_memory |= ui
+                .add(Button::new("Clear memory").shortcut_text("Ctrl+Shift+M"))
+                .clicked;
         });
         menu::menu(ui, "Windows", |ui| {
             ui.add(Checkbox::new(&mut windows.examples, "Examples"));
             ui.add(Checkbox::new(&mut windows.fractal_clock, "Fractal Clock"));
+            ui.add(Checkbox::new(&mut windows.panels, "Panels"));
             ui.add(Separator::new());
             ui.add(Checkbox::new(&mut windows.settings, "Settings"));
             ui.add(Checkbox::new(&mut windows.inspection, "Inspection"));
             ui.add(Checkbox::new(&mut windows.memory, "Memory"));
+            ui.add(Checkbox::new(&mut windows.diagnostics, "Diagnostics"));
         });
         menu::menu(ui, "About", |ui| {
             ui.add(label!("This is Egui"));
@@ -151,12 +180,17 @@ fn show_menu_bar(ui: &mut Ui, windows: &mut OpenWindows) {
             });
         }
     });
+
+    if clear_memory {
+        *ui.ctx().memory() = Default::default();
+    }
 }
 
 // ----------------------------------------------------------------------------
 
 /// Showcase some ui code
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
 pub struct ExampleWindow {
     num_columns: usize,
 
@@ -165,6 +199,16 @@ pub struct ExampleWindow {
     tree: Tree,
     box_painting: BoxPainting,
     painting: Painting,
+    drag_handles: DragHandles,
+    timeline: TimelineExample,
+    code_example: CodeExample,
+    virtual_keyboard: VirtualKeyboardExample,
+    modal: ModalExample,
+    show_hud: bool,
+    todo_list: Vec<String>,
+    table_words: Vec<String>,
+    scroll_to_item: usize,
+    num_log_lines: usize,
 }
 
 impl Default for ExampleWindow {
@@ -177,6 +221,23 @@ impl Default for ExampleWindow {
             tree: Tree::example(),
             box_painting: Default::default(),
             painting: Default::default(),
+            drag_handles: Default::default(),
+            timeline: Default::default(),
+            code_example: Default::default(),
+            virtual_keyboard: Default::default(),
+            modal: Default::default(),
+            show_hud: false,
+            todo_list: ["Buy milk", "Write code", "Walk the dog", "Read a book"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            table_words: LOREM_IPSUM
+                .split_whitespace()
+                .take(100)
+                .map(ToOwned::to_owned)
+                .collect(),
+            scroll_to_item: 50,
+            num_log_lines: 10,
         }
     }
 }
@@ -208,6 +269,50 @@ impl ExampleWindow {
             .default_open(true)
             .show(ui, |ui| self.tree.ui(ui));
 
+        CollapsingHeader::new("Tree view")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.add(label!(
+                    "The same tree in a TreeView. Select a node and use the arrow keys."
+                ));
+                let tree = &self.tree;
+                TreeView::new("tree_view").show(ui, |tree_ui| tree.tree_view_ui(tree_ui));
+            });
+
+        CollapsingHeader::new("List box")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.add(label!(
+                    "Ctrl-click to select several items, shift-click to select a range."
+                ));
+                let words: Vec<&str> = LOREM_IPSUM.split_whitespace().take(12).collect();
+                let response = ListBox::new("list_box").multi_select(true).show(ui, &words);
+                ui.add(label!("Selected: {:?}", response.selected));
+            });
+
+        CollapsingHeader::new("Move animation")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label("Reorder the list, and see the items slide into place.");
+                let mut move_up = None;
+                for (i, item) in self.todo_list.iter().enumerate() {
+                    MoveAnimation::new(item).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            if ui.button("Move up").clicked && i > 0 {
+                                move_up = Some(i);
+                            }
+                            ui.label(item.as_str());
+                        });
+                    });
+                }
+                if let Some(i) = move_up {
+                    self.todo_list.swap(i - 1, i);
+                }
+                if ui.button("Reverse").clicked {
+                    self.todo_list.reverse();
+                }
+            });
+
         ui.collapsing("Columns", |ui| {
             ui.add(Slider::usize(&mut self.num_columns, 1..=10).text("Columns"));
             ui.columns(self.num_columns, |cols| {
@@ -220,20 +325,220 @@ impl ExampleWindow {
             });
         });
 
+        ui.collapsing("Table", |ui| {
+            ui.label("Double-click a name (or tab to it and press F2) to edit it.");
+            Table::new("example_table")
+                .column("Name")
+                .column(Column::new("Length").align(Align::Max).width(60.0))
+                .column(Column::new("Vowels").align(Align::Max).width(60.0))
+                .show(ui, |body| {
+                    for word in &mut self.table_words {
+                        body.row(|row| {
+                            row.edit_cell(word);
+                            row.label(word.len().to_string());
+                            row.label(
+                                word.chars()
+                                    .filter(|c| "aeiou".contains(*c))
+                                    .count()
+                                    .to_string(),
+                            );
+                        });
+                    }
+                });
+        });
+
         ui.collapsing("Test box rendering", |ui| self.box_painting.ui(ui));
 
         CollapsingHeader::new("Scroll area")
             .default_open(false)
             .show(ui, |ui| {
-                ScrollArea::default().show(ui, |ui| {
+                let (_, response) = ScrollArea::default().show_with_response(ui, |ui| {
                     ui.label(LOREM_IPSUM);
                 });
+                ui.add(label!(
+                    "Content size: {:.0} x {:.0}",
+                    response.content_size.x,
+                    response.content_size.y
+                ));
+
+                ui.label("Jump to an item in a long list:");
+                let mut jump = None;
+                ui.horizontal(|ui| {
+                    ui.add(Slider::usize(&mut self.scroll_to_item, 0..=99).text("item"));
+                    if ui.button("Top").clicked {
+                        jump = Some(Align::Min);
+                    }
+                    if ui.button("Center").clicked {
+                        jump = Some(Align::Center);
+                    }
+                    if ui.button("Bottom").clicked {
+                        jump = Some(Align::Max);
+                    }
+                });
+                let scroll_to_item = self.scroll_to_item;
+                ScrollArea::default()
+                    .id_source("scroll_to_demo")
+                    .max_height(100.0)
+                    .show(ui, |ui| {
+                        for i in 0..100 {
+                            if let Some(align) = jump {
+                                if i == scroll_to_item {
+                                    ui.scroll_to_cursor(align);
+                                }
+                            }
+                            ui.add(label!("Item {}", i));
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    ui.label("A log that sticks to the bottom unless you scroll up:");
+                    if ui.button("Add lines").clicked {
+                        self.num_log_lines += 5;
+                    }
+                });
+                let num_log_lines = self.num_log_lines;
+                ScrollArea::default()
+                    .id_source("stick_to_bottom_demo")
+                    .max_height(100.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for i in 0..num_log_lines {
+                            ui.add(label!("Log line {}", i));
+                        }
+                    });
+
+                ui.label("Scrolls sideways (or hold shift and use the mouse wheel):");
+                ScrollArea::horizontal()
+                    .id_source("horizontal_scroll_demo")
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            for i in 0..30 {
+                                ui.button(format!("Button {}", i));
+                            }
+                        });
+                    });
+
+                ui.label("Scrolls both ways:");
+                ScrollArea::both()
+                    .id_source("both_scroll_demo")
+                    .max_height(100.0)
+                    .show(ui, |ui| {
+                        for row in 0..20 {
+                            ui.horizontal(|ui| {
+                                for col in 0..20 {
+                                    ui.add(label!("({}, {})", col, row));
+                                }
+                            });
+                        }
+                    });
             });
 
         CollapsingHeader::new("Painting")
             .default_open(false)
             .show(ui, |ui| self.painting.ui(ui));
 
+        CollapsingHeader::new("Drag handles")
+            .default_open(false)
+            .show(ui, |ui| self.drag_handles.ui(ui));
+
+        CollapsingHeader::new("Timeline")
+            .default_open(false)
+            .show(ui, |ui| self.timeline.ui(ui));
+
+        CollapsingHeader::new("Code editor")
+            .default_open(false)
+            .show(ui, |ui| self.code_example.ui(ui));
+
+        CollapsingHeader::new("Virtual keyboard")
+            .default_open(false)
+            .show(ui, |ui| self.virtual_keyboard.ui(ui));
+
+        CollapsingHeader::new("Modal window")
+            .default_open(false)
+            .show(ui, |ui| self.modal.ui(ui));
+
+        CollapsingHeader::new("Style variants")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label("Windows and areas can use a named style registered with the Context.");
+                ui.add(Checkbox::new(
+                    &mut self.show_hud,
+                    "Show a HUD window in the top right corner",
+                ));
+                if self.show_hud {
+                    let mut hud_style = ui.ctx().style();
+                    hud_style.background_fill = TRANSPARENT;
+                    hud_style.interact.inactive.rect_outline = None;
+                    hud_style.text_color = WHITE;
+                    ui.ctx().set_style_variant("hud", hud_style);
+
+                    Window::new("HUD")
+                        .style_variant("hud")
+                        .anchor((Align::Max, Align::Min), [-8.0, 32.0])
+                        .auto_sized()
+                        .show(ui.ctx(), |ui| {
+                            ui.label("No background and no border");
+                            ui.add(label!("Time: {:.1} s", ui.input().time));
+                        });
+                }
+            });
+
+        CollapsingHeader::new("Group box")
+            .default_open(false)
+            .show(ui, |ui| {
+                GroupBox::new().title("HUD").show(ui, |ui| {
+                    ui.add(Checkbox::new(&mut self.show_hud, "Show the HUD"));
+                    ui.label("Related settings, framed together.");
+                });
+                ui.label("Below the group box");
+            });
+
+        CollapsingHeader::new("Notifications")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Info").clicked {
+                        ui.ctx().notify("Saved!", Level::Info);
+                    }
+                    if ui.button("Warning").clicked {
+                        ui.ctx().notify("Disk almost full", Level::Warning);
+                    }
+                    if ui.button("Error").clicked {
+                        ui.ctx().notify("Failed to save", Level::Error);
+                    }
+                });
+            });
+
+        CollapsingHeader::new("Plot")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label("Hover the lines to see their values. Click a name below the plot to hide or show its line.");
+                let xs = (0..=100).map(|i| i as f32 / 10.0);
+                ui.add(
+                    Plot::new()
+                        .series(Series::new("sin", xs.clone().map(|x| (x, x.sin()))))
+                        .series(Series::new("cos", xs.map(|x| (x, 0.5 * x.cos())))),
+                );
+            });
+
+        CollapsingHeader::new("Splitter")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label("Drag the bars between the panes:");
+                Resize::default().default_height(200.0).show(ui, |ui| {
+                    Splitter::horizontal("splitter_demo").show(ui, |left, right| {
+                        left.label("Left pane");
+                        left.label(LOREM_IPSUM);
+                        Splitter::vertical("splitter_demo_right")
+                            .default_ratio(0.3)
+                            .show(right, |top, bottom| {
+                                top.label("Top pane");
+                                bottom.label("Bottom pane");
+                            });
+                    });
+                });
+            });
+
         CollapsingHeader::new("Resize")
             .default_open(false)
             .show(ui, |ui| {
@@ -277,9 +582,19 @@ struct Widgets {
     checked: bool,
     count: usize,
     radio: usize,
+    searched_number: usize,
     slider_value: usize,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    slider_commits: usize,
+    progress: f32,
+    log_value: f32,
+    angle: f32,
+    range: (f32, f32),
+    color: Color,
     single_line_text_input: String,
     multiline_text_input: String,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    password: String,
 }
 
 impl Default for Widgets {
@@ -287,10 +602,18 @@ impl Default for Widgets {
         Self {
             checked: true,
             radio: 0,
+            searched_number: 42,
             count: 0,
             slider_value: 100,
+            slider_commits: 0,
+            progress: 0.3,
+            log_value: 1.0,
+            angle: 45.0,
+            range: (20.0, 80.0),
+            color: srgba(100, 150, 250, 255),
             single_line_text_input: "Hello World!".to_owned(),
             multiline_text_input: "Text can both be so wide that it needs a linebreak, but you can also add manual linebreak by pressing enter, creating new paragraphs.\nThis is the start of the next paragraph.\n\nClick me to edit me!".to_owned(),
+            password: "hunter2".to_owned(),
         }
     }
 }
@@ -305,17 +628,69 @@ impl Widgets {
                 );
             });
 
+        ui.label("Tooltips can contain any widgets (hover me)")
+            .on_hover_ui(|ui| {
+                ui.add(label!("A rich tooltip").text_style(TextStyle::Heading));
+                ui.add(MarkdownLabel::new(
+                    "With **markdown**, `code` and\n- a bullet list",
+                ));
+                ui.add(Separator::new());
+                ui.add(label!("and colors").text_color(srgba(255, 190, 90, 255)));
+            });
+
         ui.add(Checkbox::new(&mut self.checked, "checkbox"));
+        ui.horizontal(|ui| {
+            ui.add_enabled(self.checked, Button::new("Enabled by the checkbox"));
+            ui.add_enabled(
+                self.checked,
+                Slider::usize(&mut self.slider_value, 1..=1000).text("value"),
+            );
+        });
+
+        ui.color_edit_button_srgba(&mut self.color);
+        ui.add(label!("This text has the color above").text_color(self.color));
 
         ui.horizontal(|ui| {
-            if ui.add(radio(self.radio == 0, "First")).clicked {
-                self.radio = 0;
-            }
-            if ui.add(radio(self.radio == 1, "Second")).clicked {
-                self.radio = 1;
+            ui.radio_value(&mut self.radio, 0, "First");
+            ui.radio_value(&mut self.radio, 1, "Second");
+            ui.radio_value(&mut self.radio, 2, "Final");
+        });
+
+        ui.add(
+            EnumDropDown::new(
+                &mut self.radio,
+                vec![(0, "First"), (1, "Second"), (2, "Final")],
+            )
+            .text("The same, as a combo box"),
+        );
+
+        ui.add(
+            EnumDropDown::new(
+                &mut self.searched_number,
+                (0..500).map(|i| (i, format!("Number {}", i))),
+            )
+            .searchable(true)
+            .text("Type to search long lists"),
+        );
+
+        ui.horizontal(|ui| {
+            let response = ui.button("Or pick in a popup");
+            let popup_id = ui.make_child_id("radio_popup");
+            if response.clicked {
+                ui.memory().toggle_popup(popup_id);
             }
-            if ui.add(radio(self.radio == 2, "Final")).clicked {
-                self.radio = 2;
+            let radio = &mut self.radio;
+            Popup::show_below(ui.ctx(), response.rect, popup_id, |ui| {
+                ui.radio_value(radio, 0, "First");
+                ui.radio_value(radio, 1, "Second");
+                ui.radio_value(radio, 2, "Final");
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Or as selectable labels:");
+            for (i, name) in ["First", "Second", "Final"].iter().enumerate() {
+                ui.selectable_value(&mut self.radio, i, *name);
             }
         });
 
@@ -327,13 +702,97 @@ impl Widgets {
             {
                 self.count += 1;
             }
-            ui.add(label!("The button has been clicked {} times", self.count));
+            let count = &mut self.count;
+            ui.add(label!("The button has been clicked {} times", count))
+                .tooltip_text("Right-click to reset")
+                .context_menu(|ui| {
+                    if ui.button("Reset counter").clicked {
+                        *count = 0;
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Icon buttons:");
+            if ui.add(Button::icon_only(Icon::Glyph('+'))).clicked {
+                self.count += 1;
+            }
+            if ui.add(Button::icon_only(Icon::Glyph('-'))).clicked {
+                self.count = self.count.saturating_sub(1);
+            }
+            if ui.add(Button::new("Reset").icon(Icon::Glyph('x'))).clicked {
+                self.count = 0;
+            }
         });
 
-        ui.add(Slider::usize(&mut self.slider_value, 1..=1000).text("value"));
+        if ui
+            .add(Slider::usize(&mut self.slider_value, 1..=1000).text("value"))
+            .committed
+        {
+            self.slider_commits += 1;
+        }
+        ui.add(label!(
+            "The value above changes live while dragging, and has been committed {} times",
+            self.slider_commits
+        ));
         if ui.add(Button::new("Double it")).clicked {
             self.slider_value *= 2;
         }
+        ui.add(
+            Slider::usize(&mut self.slider_value, 0..=1000)
+                .step(100.0)
+                .text("in steps of 100"),
+        );
+        ui.add(
+            Slider::f32(&mut self.log_value, 0.001..=1000.0)
+                .logarithmic(true)
+                .editable_value(true)
+                .text("logarithmic"),
+        )
+        .tooltip_text("Click the value to type in an exact number");
+        ui.add(Knob::degrees(&mut self.angle).text("angle"));
+
+        ui.add(
+            Slider::f32(&mut self.progress, 0.0..=1.0)
+                .detent(0.0, "0")
+                .tick(0.25)
+                .detent(0.5, "0.5")
+                .tick(0.75)
+                .detent(1.0, "1")
+                .text("progress"),
+        );
+        ui.horizontal(|ui| {
+            ui.set_desired_height(80.0);
+            ui.add(Slider::f32(&mut self.progress, 0.0..=1.0).vertical());
+            ui.add(
+                Slider::f32(&mut self.progress, 0.0..=1.0)
+                    .vertical()
+                    .detent(0.0, "min")
+                    .detent(1.0, "max"),
+            );
+            ui.add(label!("The same, vertically"));
+        });
+        ui.add(ProgressBar::new(self.progress).show_percentage(true));
+        ui.add(
+            RangeSlider::new(&mut self.range, 0.0..=100.0)
+                .precision(0)
+                .text("range"),
+        );
+        ui.add(ProgressBar::indeterminate().text("Loading..."));
+
+        ui.collapsing("Small widgets, for dense panels", |ui| {
+            ui.horizontal(|ui| {
+                if ui.add(Button::new("Small button").small()).clicked {
+                    self.count += 1;
+                }
+                ui.add(Checkbox::new(&mut self.checked, "small checkbox").small());
+            });
+            ui.add(
+                Slider::f32(&mut self.progress, 0.0..=1.0)
+                    .small()
+                    .text("small slider"),
+            );
+        });
 
         ui.horizontal(|ui| {
             ui.add(label!("Single line text input:"));
@@ -344,8 +803,85 @@ impl Widgets {
             );
         }); // TODO: .tooltip_text("Enter text to edit me")
 
+        ui.horizontal(|ui| {
+            ui.add(label!("Password:"));
+            ui.add(
+                TextEdit::new(&mut self.password)
+                    .multiline(false)
+                    .password(true)
+                    .id("password"),
+            );
+        });
+
         ui.add(label!("Multiline text input:"));
         ui.add(TextEdit::new(&mut self.multiline_text_input).id("multiline"));
+
+        ui.add(Separator::new());
+        ui.add(
+            MarkdownLabel::new(
+                "## Markdown\n\
+                 Labels can have **strong**, *italic* and `code` text.\n\
+                 - Bullet points\n\
+                 - that wrap onto more than one line if they are too long to fit\n\
+                 - and terms with hover cards: hover the word egui",
+            )
+            .hover_card("egui", |ui| {
+                ui.add(label!("Egui").text_style(TextStyle::Heading));
+                ui.label("An immediate mode GUI library written in Rust.");
+                ui.hyperlink("https://github.com/emilk/emigui/");
+            }),
+        );
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+struct TimelineExample {
+    time: f32,
+    /// (start, duration)
+    clips: Vec<(f32, f32)>,
+    keyframes: Vec<f32>,
+}
+
+impl Default for TimelineExample {
+    fn default() -> Self {
+        Self {
+            time: 1.0,
+            clips: vec![(0.5, 2.0), (3.0, 1.5), (6.0, 3.0)],
+            keyframes: vec![0.0, 2.5, 4.0, 7.5],
+        }
+    }
+}
+
+impl TimelineExample {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.add(label!(
+            "Drag the ruler to scrub, scroll to zoom. Time: {:.2} s",
+            self.time
+        ));
+
+        let Self {
+            time,
+            clips,
+            keyframes,
+        } = self;
+        Timeline::new("example_timeline")
+            .duration(10.0)
+            .frame_rate(24.0)
+            .show(ui, time, |timeline| {
+                timeline.track("Clips", |track| {
+                    for (i, (start, duration)) in clips.iter_mut().enumerate() {
+                        track.clip(i, start, *duration, format!("Clip {}", i + 1));
+                    }
+                });
+                timeline.track("Keyframes", |track| {
+                    for (i, keyframe) in keyframes.iter_mut().enumerate() {
+                        track.keyframe(i, keyframe);
+                    }
+                });
+            });
     }
 }
 
@@ -459,6 +995,236 @@ impl Painting {
 
 // ----------------------------------------------------------------------------
 
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+struct DragHandles {
+    /// Relative to the top left of the canvas
+    points: [Vec2; 3],
+}
+
+impl Default for DragHandles {
+    fn default() -> Self {
+        Self {
+            points: [vec2(20.0, 80.0), vec2(120.0, 20.0), vec2(220.0, 80.0)],
+        }
+    }
+}
+
+/// Do all corners turn the same way? `PaintCmd::convex_polygon` can only fill such polygons.
+/// Straight or doubled-back corners (e.g. all points on a line) don't count as convex.
+fn is_convex(points: &[Pos2]) -> bool {
+    let n = points.len();
+    let turns: Vec<f32> = (0..n)
+        .map(|i| {
+            let (a, b, c) = (points[i], points[(i + 1) % n], points[(i + 2) % n]);
+            let (ab, bc) = (b - a, c - b);
+            ab.x * bc.y - ab.y * bc.x
+        })
+        .collect();
+    n >= 3 && (turns.iter().all(|&t| t > 0.0) || turns.iter().all(|&t| t < 0.0))
+}
+
+impl DragHandles {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label("The left handle moves freely, the middle one only vertically and the right one only horizontally.");
+
+        let rect = ui.allocate_space(vec2(ui.available().width(), 100.0));
+        let mut points: Vec<Pos2> = self.points.iter().map(|p| rect.min + *p).collect();
+
+        if is_convex(&points) {
+            ui.add_paint_cmd(PaintCmd::convex_polygon(
+                &points,
+                Some(srgba(40, 60, 120, 128)),
+                None,
+            ));
+        }
+        ui.add_paint_cmd(PaintCmd::Path {
+            path: Path::from_open_points(&points),
+            closed: false,
+            outline: Some(LineStyle::new(2.0, LIGHT_GRAY)),
+            fill: None,
+        });
+
+        let id = ui.make_child_id("drag_handles");
+        drag_handle(ui, id.with(0), &mut points[0]);
+        DragHandle::new(id.with(1), &mut points[1])
+            .axis(Direction::Vertical)
+            .show(ui);
+        DragHandle::new(id.with(2), &mut points[2])
+            .axis(Direction::Horizontal)
+            .shape(HandleShape::Square)
+            .show(ui);
+
+        for (point, pos) in self.points.iter_mut().zip(points) {
+            *point = (pos - rect.min).max(Vec2::zero()).min(rect.size());
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+struct CodeExample {
+    code: String,
+}
+
+impl Default for CodeExample {
+    fn default() -> Self {
+        Self {
+            code: "// A very simple example\nfn main() {\n    let answer = 42;\n    println!(\"The answer is {}\", answer); // Long lines scroll horizontally instead of wrapping\n}\n".to_owned(),
+        }
+    }
+}
+
+impl CodeExample {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label("Hover println to see its documentation.");
+        ui.add(
+            CodeEditor::new(&mut self.code)
+                .highlighter(highlight_rust)
+                .hover_card("println", |ui| {
+                    ui.add(label!("macro println!").text_style(TextStyle::Monospace));
+                    ui.label("Prints to the standard output, with a newline.");
+                }),
+        );
+    }
+}
+
+/// A toy highlighter for the code editor example: keywords, numbers, strings and comments.
+fn highlight_rust(code: &str) -> Vec<ColorSpan> {
+    const KEYWORDS: &[&str] = &[
+        "as", "else", "enum", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+        "mut", "pub", "return", "self", "struct", "true", "use", "while",
+    ];
+
+    let chars: Vec<char> = code.chars().collect();
+    let mut spans = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let color = if chars[i] == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            Some(GRAY)
+        } else if chars[i] == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            Some(srgba(110, 255, 110, 255))
+        } else if chars[i].is_ascii_digit() {
+            while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+                i += 1;
+            }
+            Some(srgba(255, 160, 80, 255))
+        } else if chars[i].is_alphabetic() || chars[i] == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                Some(LIGHT_BLUE)
+            } else {
+                None
+            }
+        } else {
+            i += 1;
+            None
+        };
+        if let Some(color) = color {
+            spans.push(ColorSpan {
+                char_range: start..i,
+                color,
+            });
+        }
+    }
+    spans
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+struct VirtualKeyboardExample {
+    show_keyboard: bool,
+    numeric: bool,
+    text: String,
+}
+
+impl Default for VirtualKeyboardExample {
+    fn default() -> Self {
+        Self {
+            show_keyboard: false,
+            numeric: false,
+            text: "Click me, then type on the keyboard below".to_owned(),
+        }
+    }
+}
+
+impl VirtualKeyboardExample {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.add(Checkbox::new(
+            &mut self.show_keyboard,
+            "Show virtual keyboard",
+        ));
+        ui.add(Checkbox::new(&mut self.numeric, "Numeric layout"));
+        ui.add(TextEdit::new(&mut self.text).id("virtual_keyboard_text"));
+
+        if self.show_keyboard {
+            let layout = if self.numeric {
+                KeyboardLayout::Numeric
+            } else {
+                KeyboardLayout::Qwerty
+            };
+            VirtualKeyboard::new("example_virtual_keyboard")
+                .layout(layout)
+                .show(ui.ctx());
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+struct ModalExample {
+    open: bool,
+    name: String,
+}
+
+impl ModalExample {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label("While a modal window is open, nothing behind it can be clicked or typed into.");
+        if ui.button("Open modal window").clicked {
+            self.open = true;
+        }
+        if !self.name.is_empty() {
+            ui.add(label!("Hello {}!", self.name));
+        }
+
+        let Self { open, name } = self;
+        let mut close = false;
+        Window::new("Modal window")
+            .open(open)
+            .modal(true)
+            .auto_sized()
+            .show(ui.ctx(), |ui| {
+                ui.label("What is your name? Press escape to cancel.");
+                ui.add(TextEdit::new(name).id("modal_name"));
+                close = ui.button("Ok").clicked;
+            });
+        if close {
+            *open = false;
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 use crate::layout::*;
 
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
@@ -504,12 +1270,7 @@ impl LayoutExample {
         // TODO: enum iter
 
         for &dir in &[Direction::Horizontal, Direction::Vertical] {
-            if ui
-                .add(RadioButton::new(self.dir == dir, format!("{:?}", dir)))
-                .clicked
-            {
-                self.dir = dir;
-            }
+            ui.radio_value(&mut self.dir, dir, format!("{:?}", dir));
         }
 
         ui.add(Checkbox::new(&mut self.reversed, "Reversed"));
@@ -519,23 +1280,10 @@ impl LayoutExample {
         ui.add(label!("Align:"));
 
         for &align in &[Align::Min, Align::Center, Align::Max] {
-            if ui
-                .add(RadioButton::new(
-                    self.align == Some(align),
-                    format!("{:?}", align),
-                ))
-                .clicked
-            {
-                self.align = Some(align);
-            }
-        }
-        if ui
-            .add(RadioButton::new(self.align == None, "Justified"))
-            .tooltip_text("Try to fill full width/heigth (e.g. buttons)")
-            .clicked
-        {
-            self.align = None;
+            ui.radio_value(&mut self.align, Some(align), format!("{:?}", align));
         }
+        ui.radio_value(&mut self.align, None, "Justified")
+            .tooltip_text("Try to fill full width/heigth (e.g. buttons)");
     }
 }
 
@@ -562,6 +1310,17 @@ impl Tree {
         self.ui_impl(ui, 0, "root")
     }
 
+    fn tree_view_ui(&self, tree_ui: &mut TreeUi<'_>) {
+        for (i, child) in self.0.iter().enumerate() {
+            let name = format!("child #{}", i);
+            if child.0.is_empty() {
+                tree_ui.leaf(i, name);
+            } else {
+                tree_ui.node(i, name, |tree_ui| child.tree_view_ui(tree_ui));
+            }
+        }
+    }
+
     fn ui_impl(&mut self, ui: &mut Ui, depth: usize, name: &str) -> Action {
         CollapsingHeader::new(name)
             .default_open(depth < 1)
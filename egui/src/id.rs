@@ -27,9 +27,14 @@
 //! So we have two type of Ids: `PositionId` and `UniqueId`.
 //! TODO: have separate types for `PositionId` and `UniqueId`.
 
-use std::hash::Hash;
+use std::{collections::VecDeque, hash::Hash};
 
-use crate::math::Pos2;
+use ahash::{AHashMap, AHashSet};
+
+use crate::{
+    math::{Pos2, Rect},
+    Layer,
+};
 
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
@@ -65,3 +70,122 @@ impl Id {
         Id::new(&x).with(&y)
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// A widget that got a new `Id` while staying in the exact same place.
+#[derive(Clone, Copy, Debug)]
+pub struct IdChange {
+    pub rect: Rect,
+    pub old_id: Id,
+    pub new_id: Id,
+    /// `InputState::time` of the frame the change was detected.
+    pub time: f64,
+}
+
+/// Detects widgets whose `Id` changes between frames while their rect stays the same.
+/// That is a tell-tale sign of an unstable id source (e.g. a counter or a changing label),
+/// which makes widgets lose their state ("my window keeps resetting").
+#[derive(Clone, Default)]
+pub(crate) struct IdChangeTracker {
+    last_frame: AHashMap<[i32; 4], (Id, Rect)>,
+    this_frame: AHashMap<[i32; 4], (Id, Rect)>,
+    /// Human readable names of widgets (e.g. the text of a button),
+    /// for the widget search of `Style::debug_widget_search`.
+    labels_last_frame: AHashMap<Id, String>,
+    labels_this_frame: AHashMap<Id, String>,
+    /// Which layer each widget is on, e.g. for the keyboard navigation of menus.
+    layers_last_frame: AHashMap<Id, Layer>,
+    layers_this_frame: AHashMap<Id, Layer>,
+    /// Most recent last.
+    changes: VecDeque<IdChange>,
+}
+
+impl IdChangeTracker {
+    const MAX_CHANGES: usize = 32;
+
+    /// Call for every interactive widget, every frame.
+    pub fn register(&mut self, id: Id, rect: Rect, layer: Layer) {
+        if rect.is_finite() {
+            self.this_frame.insert(rect_key(rect), (id, rect));
+            self.layers_this_frame.insert(id, layer);
+        }
+    }
+
+    pub fn register_label(&mut self, id: Id, label: &str) {
+        self.labels_this_frame.insert(id, label.to_owned());
+    }
+
+    pub fn end_frame(&mut self, time: f64) {
+        let ids_last_frame: AHashSet<Id> = self.last_frame.values().map(|(id, _)| *id).collect();
+        let ids_this_frame: AHashSet<Id> = self.this_frame.values().map(|(id, _)| *id).collect();
+
+        for (key, &(new_id, rect)) in &self.this_frame {
+            if let Some(&(old_id, _)) = self.last_frame.get(key) {
+                let renamed = old_id != new_id
+                    && !ids_this_frame.contains(&old_id)
+                    && !ids_last_frame.contains(&new_id);
+                if renamed {
+                    if self.changes.len() >= Self::MAX_CHANGES {
+                        self.changes.pop_front();
+                    }
+                    self.changes.push_back(IdChange {
+                        rect,
+                        old_id,
+                        new_id,
+                        time,
+                    });
+                }
+            }
+        }
+
+        self.last_frame = std::mem::take(&mut self.this_frame);
+        self.labels_last_frame = std::mem::take(&mut self.labels_this_frame);
+        self.layers_last_frame = std::mem::take(&mut self.layers_this_frame);
+    }
+
+    /// Number of interactive widgets registered last frame.
+    pub fn num_widgets_last_frame(&self) -> usize {
+        self.last_frame.len()
+    }
+
+    /// The rects of the interactive widgets registered last frame.
+    pub fn widget_rects_last_frame(&self) -> impl Iterator<Item = Rect> + '_ {
+        self.last_frame.values().map(|(_, rect)| *rect)
+    }
+
+    /// The interactive widgets registered last frame on this layer.
+    pub fn widgets_last_frame_on(&self, layer: Layer) -> impl Iterator<Item = (Id, Rect)> + '_ {
+        self.last_frame
+            .values()
+            .filter(move |(id, _)| self.layers_last_frame.get(id) == Some(&layer))
+            .copied()
+    }
+
+    /// The interactive widgets registered last frame, with their labels (if any).
+    pub fn widgets_last_frame(&self) -> impl Iterator<Item = (Id, Rect, Option<&str>)> + '_ {
+        self.last_frame.values().map(move |&(id, rect)| {
+            let label = self.labels_last_frame.get(&id).map(String::as_str);
+            (id, rect, label)
+        })
+    }
+
+    /// Most recent last.
+    pub fn changes(&self) -> impl DoubleEndedIterator<Item = &IdChange> {
+        self.changes.iter()
+    }
+
+    pub fn clear_changes(&mut self) {
+        self.changes.clear();
+    }
+}
+
+/// Round to whole points so tiny float differences don't count as movement.
+fn rect_key(rect: Rect) -> [i32; 4] {
+    [
+        rect.min.x.round() as i32,
+        rect.min.y.round() as i32,
+        rect.max.x.round() as i32,
+        rect.max.y.round() as i32,
+    ]
+}
@@ -1,25 +1,49 @@
+use std::collections::HashSet;
+
 use crate::{math::*, movement_tracker::MovementTracker};
 
-/// If mouse moves more than this, it is no longer a click (but maybe a drag)
-const MAX_CLICK_DIST: f32 = 6.0;
 /// The new mouse press must come within this many seconds from previous mouse release
 const MAX_CLICK_DELAY: f64 = 0.3;
+/// Held keys are let go after this many seconds without key events
+/// if we would not have expected any (see `InputState::begin_frame`).
+/// We probably missed the key release, e.g. while paused in a debugger.
+const HELD_KEY_TIMEOUT: f64 = 1.0;
 
 /// What the integration gives to the gui.
 /// All coordinates in egui is in point/logical coordinates.
-#[derive(Clone, Debug, Default)]
-#[cfg_attr(feature = "with_serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "with_serde", serde(default))]
 pub struct RawInput {
     /// Is the button currently down?
     pub mouse_down: bool,
 
+    /// Is the secondary (usually right) mouse button currently down?
+    pub secondary_mouse_down: bool,
+
+    /// Is the middle mouse button (or mouse wheel) currently down?
+    pub middle_mouse_down: bool,
+
     /// Current position of the mouse in points.
+    /// May be outside of the screen, e.g. when dragging past the edge of the window.
+    /// `None` means the pointer is gone (it left the window, or the finger was lifted).
+    /// If that happens while the button is held, the drag goes on at the last known position
+    /// until the button is released.
     pub mouse_pos: Option<Pos2>,
 
-    /// How many pixels the user scrolled
+    /// How many points the user scrolled with something pixel-precise, like a trackpad.
+    /// Scaled by `style.scroll_area.precise_scroll_speed`.
     pub scroll_delta: Vec2,
 
+    /// How many notches the user turned the mouse wheel (e.g. glutin's `LineDelta`).
+    /// Converted to points using `style.scroll_area.lines_per_notch` and `points_per_line`.
+    pub scroll_notches: Vec2,
+
+    /// How much the user zoomed this frame, e.g. with a pinch gesture,
+    /// as a factor: 2.0 means everything should be twice as big.
+    /// 1.0 (the default) means no zoom.
+    pub zoom_delta: f32,
+
     /// Size of the screen in points.
     /// TODO: this should be screen_rect for easy sandboxing.
     pub screen_size: Vec2,
@@ -30,13 +54,48 @@ pub struct RawInput {
     /// Time in seconds. Relative to whatever. Used for animation.
     pub time: f64,
 
+    /// How long the coming frame will be on screen, in seconds.
+    /// Should be the refresh interval of the display, e.g. 1/60 s (the default).
+    pub predicted_dt: f32,
+
     /// Local time. Only used for the clock in the example app.
     pub seconds_since_midnight: Option<f64>,
 
+    /// Does the window have keyboard focus?
+    /// When it loses it, all held keys are let go, since we won't hear about them being released.
+    pub focused: bool,
+
+    /// Which modifier keys are held down, if the integration knows.
+    /// If `None`, they are tracked from the `Event::Key` events,
+    /// which misses keys pressed or released while the window was not in focus.
+    pub modifiers: Option<Modifiers>,
+
     /// In-order events received this frame
     pub events: Vec<Event>,
 }
 
+impl Default for RawInput {
+    fn default() -> Self {
+        Self {
+            mouse_down: false,
+            secondary_mouse_down: false,
+            middle_mouse_down: false,
+            mouse_pos: None,
+            scroll_delta: Vec2::zero(),
+            scroll_notches: Vec2::zero(),
+            zoom_delta: 1.0,
+            screen_size: Vec2::zero(),
+            pixels_per_point: None,
+            time: 0.0,
+            predicted_dt: 1.0 / 60.0,
+            seconds_since_midnight: None,
+            focused: true,
+            modifiers: None,
+            events: vec![],
+        }
+    }
+}
+
 /// What egui maintains
 #[derive(Clone, Debug, Default)]
 pub struct InputState {
@@ -45,9 +104,13 @@ pub struct InputState {
 
     pub mouse: MouseInput,
 
-    /// How many pixels the user scrolled
+    /// How many points the user scrolled, from both the wheel and e.g. a trackpad.
+    /// See `style.scroll_area` for how these are scaled.
     pub scroll_delta: Vec2,
 
+    /// How much the user zoomed this frame, as a factor (1.0 = no zoom)
+    pub zoom_delta: f32,
+
     /// Size of the screen in points.
     pub screen_size: Vec2,
 
@@ -60,13 +123,253 @@ pub struct InputState {
     /// Time since last frame, in seconds.
     pub dt: f32,
 
+    /// How long the coming frame will be on screen, in seconds.
+    /// Animations should move this far ahead to look right when the frame is shown.
+    pub predicted_dt: f32,
+
     /// Local time. Only used for the clock in the example app.
     pub seconds_since_midnight: Option<f64>,
 
+    /// Which modifier keys are held down, tracked from the `Event::Key` events.
+    pub modifiers: Modifiers,
+
+    /// All keys held down, tracked from the `Event::Key` events.
+    pub keys_down: HashSet<Key>,
+
+    /// The fingers on the touch screen, tracked from the `Event::Touch` events.
+    pub touches: Touches,
+
+    /// Held navigation keys, and when they next repeat.
+    key_repeats: Vec<(Key, f64)>,
+    /// Set once the integration sends repeated key presses itself:
+    /// then we stop making our own.
+    integration_repeats_keys: bool,
+    /// When we last got an `Event::Key`.
+    last_key_event_time: f64,
+
     /// In-order events received this frame
     pub events: Vec<Event>,
 }
 
+/// How held navigation keys (arrows, backspace, ...) repeat.
+/// Set from `Style::key_repeat`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct KeyRepeat {
+    /// Seconds a key must be held before it starts repeating.
+    pub delay: f32,
+    /// Seconds between repeats.
+    pub interval: f32,
+}
+
+impl Default for KeyRepeat {
+    fn default() -> Self {
+        Self {
+            delay: 0.5,
+            interval: 0.033,
+        }
+    }
+}
+
+/// The fingers on a touch screen.
+/// The first finger down acts as the mouse (pressing, dragging and clicking with it),
+/// until it is lifted.
+///
+/// Two or more fingers make a gesture instead: pinching zooms and moving them scrolls,
+/// through `InputState::zoom_delta` and `InputState::scroll_delta`.
+/// The mouse stays where it was during a gesture, and will not click.
+#[derive(Clone, Debug)]
+pub struct Touches {
+    /// Fingers on the screen (id and position), in the order they touched it.
+    pub active: Vec<(u64, Pos2)>,
+    /// How much the fingers spread apart this frame, as a factor (1.0 = no pinch).
+    pub zoom_delta: f32,
+    /// How much the fingers moved together this frame, in points.
+    pub pan_delta: Vec2,
+    /// Lifted the same frame they touched. Kept one more frame so that a quick tap still clicks.
+    lifted: Vec<u64>,
+    /// The finger acting as the mouse, and where it is (or was lifted).
+    pointer: Option<(u64, Pos2)>,
+}
+
+impl Default for Touches {
+    fn default() -> Self {
+        Self {
+            active: vec![],
+            zoom_delta: 1.0,
+            pan_delta: Vec2::zero(),
+            lifted: vec![],
+            pointer: None,
+        }
+    }
+}
+
+impl Touches {
+    /// Two or more fingers on the screen.
+    pub fn is_gesture(&self) -> bool {
+        self.active.len() >= 2
+    }
+
+    /// Overrides the mouse of `new` with the pointer finger, if any.
+    fn begin_frame(mut self, new: &mut RawInput) -> Self {
+        let Touches {
+            active,
+            zoom_delta,
+            pan_delta,
+            lifted,
+            pointer,
+        } = &mut self;
+        active.retain(|(id, _)| !lifted.contains(id));
+        lifted.clear();
+        let last_frame = active.clone();
+
+        let mut started = vec![];
+        for event in &new.events {
+            if let Event::Touch { id, phase, pos } = *event {
+                match phase {
+                    TouchPhase::Start => {
+                        active.retain(|(active_id, _)| *active_id != id);
+                        active.push((id, pos));
+                        started.push(id);
+                        if pointer.is_none() {
+                            *pointer = Some((id, pos));
+                        }
+                    }
+                    TouchPhase::Move => {
+                        for (active_id, active_pos) in active.iter_mut() {
+                            if *active_id == id {
+                                *active_pos = pos;
+                            }
+                        }
+                    }
+                    TouchPhase::End | TouchPhase::Cancel => {
+                        if let Some((pointer_id, pointer_pos)) = pointer {
+                            if *pointer_id == id {
+                                *pointer_pos = pos;
+                            }
+                        }
+                        if started.contains(&id) {
+                            lifted.push(id);
+                        } else {
+                            active.retain(|(active_id, _)| *active_id != id);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Compare the fingers that were there both last frame and this frame:
+        let both_frames: Vec<(Pos2, Pos2)> = last_frame
+            .iter()
+            .filter_map(|(id, last_pos)| {
+                let (_, pos) = active.iter().find(|(active_id, _)| active_id == id)?;
+                Some((*last_pos, *pos))
+            })
+            .collect();
+        *zoom_delta = 1.0;
+        *pan_delta = Vec2::zero();
+        if both_frames.len() >= 2 {
+            let (last_center, last_spread) = center_and_spread(both_frames.iter().map(|p| p.0));
+            let (center, spread) = center_and_spread(both_frames.iter().map(|p| p.1));
+            *pan_delta = center - last_center;
+            if last_spread > 0.0 && spread > 0.0 {
+                *zoom_delta = spread / last_spread;
+            }
+        }
+        let is_gesture = active.len() >= 2;
+
+        if let Some((pointer_id, last_pos)) = *pointer {
+            match active.iter().find(|(id, _)| *id == pointer_id) {
+                Some(_) if is_gesture => {
+                    new.mouse_down = true;
+                    new.mouse_pos = Some(last_pos);
+                }
+                Some(&(_, pos)) => {
+                    new.mouse_down = true;
+                    new.mouse_pos = Some(pos);
+                    *pointer = Some((pointer_id, pos));
+                }
+                None => {
+                    // Release where it was lifted, so that it clicks.
+                    // From next frame on the integration's mouse is used again.
+                    new.mouse_down = false;
+                    new.mouse_pos = Some(last_pos);
+                    *pointer = None;
+                }
+            }
+        }
+        self
+    }
+}
+
+/// The mean position, and mean distance to it.
+fn center_and_spread(points: impl Iterator<Item = Pos2> + Clone) -> (Pos2, f32) {
+    let n = points.clone().count() as f32;
+    let sum = points
+        .clone()
+        .fold(Vec2::zero(), |sum, p| sum + p.to_vec2());
+    let center = pos2(sum.x / n, sum.y / n);
+    let spread = points.map(|p| p.distance(center)).sum::<f32>() / n;
+    (center, spread)
+}
+
+/// The state of the modifier keys.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+pub struct Modifiers {
+    pub alt: bool,
+    pub ctrl: bool,
+    pub shift: bool,
+    /// Windows key or Mac Command key
+    pub logo: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self {
+        alt: false,
+        ctrl: false,
+        shift: false,
+        logo: false,
+    };
+    pub const ALT: Self = Self {
+        alt: true,
+        ..Self::NONE
+    };
+    pub const CTRL: Self = Self {
+        ctrl: true,
+        ..Self::NONE
+    };
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ..Self::NONE
+    };
+    /// Windows key or Mac Command key
+    pub const LOGO: Self = Self {
+        logo: true,
+        ..Self::NONE
+    };
+
+    /// Ctrl on Windows and Linux, Command on Mac: the key held to add to a selection.
+    pub fn command(&self) -> bool {
+        self.ctrl || self.logo
+    }
+}
+
+/// `Modifiers::CTRL | Modifiers::SHIFT`
+impl std::ops::BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            alt: self.alt || rhs.alt,
+            ctrl: self.ctrl || rhs.ctrl,
+            shift: self.shift || rhs.shift,
+            logo: self.logo || rhs.logo,
+        }
+    }
+}
+
 /// What egui maintains
 #[derive(Clone, Debug)]
 pub struct MouseInput {
@@ -81,10 +384,32 @@ pub struct MouseInput {
     /// The mouse went from down to !down
     pub released: bool,
 
+    /// Is the secondary (usually right) button down?
+    pub secondary_down: bool,
+
+    /// The secondary button went from !down to down
+    pub secondary_pressed: bool,
+
+    /// The secondary button went from down to !down
+    pub secondary_released: bool,
+
+    /// Is the middle button down?
+    pub middle_down: bool,
+
+    /// The middle button went from !down to down
+    pub middle_pressed: bool,
+
     /// If the mouse is down, will it register as a click when released?
     /// Set to true on mouse down, set to false when mouse moves too much.
     pub could_be_click: bool,
 
+    /// The mouse is down and has moved more than `Style::drag_threshold` since the press.
+    /// Until then `delta` is zero, so that small jitters during a click don't drag anything.
+    pub is_dragging: bool,
+
+    /// `is_dragging` became true this frame.
+    pub drag_started: bool,
+
     /// Was there a click?
     /// Did a mouse button get released this frame closely after going down?
     pub click: bool,
@@ -92,6 +417,12 @@ pub struct MouseInput {
     /// Was there a double-click?
     pub double_click: bool,
 
+    /// Was there a triple-click? (The third click is also a `double_click`)
+    pub triple_click: bool,
+
+    /// Clicks in quick succession so far: 1 for a single click, 2 for a double click, ...
+    pub click_count: u32,
+
     /// When did the mouse get click last?
     /// Used to check for double-clicks.
     pub last_click_time: f64,
@@ -120,9 +451,18 @@ impl Default for MouseInput {
             down: false,
             pressed: false,
             released: false,
+            secondary_down: false,
+            secondary_pressed: false,
+            secondary_released: false,
+            middle_down: false,
+            middle_pressed: false,
             could_be_click: false,
+            is_dragging: false,
+            drag_started: false,
             click: false,
             double_click: false,
+            triple_click: false,
+            click_count: 0,
             last_click_time: std::f64::NEG_INFINITY,
             pos: None,
             press_origin: None,
@@ -133,22 +473,49 @@ impl Default for MouseInput {
     }
 }
 
-#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "with_serde", derive(serde::Deserialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "with_serde", serde(rename_all = "snake_case"))]
 pub enum Event {
     Copy,
     Cut,
-    /// Text input, e.g. via keyboard or paste action
+    /// Text input, e.g. via keyboard
     Text(String),
+    /// Text pasted from the clipboard.
+    Paste(String),
+    /// An IME (input method, e.g. for CJK text) started composing text.
+    CompositionStart,
+    /// The text being composed so far (the "preedit" text). Not yet part of the edited text.
+    CompositionUpdate(String),
+    /// The composition is done, and this text should be inserted.
+    CompositionEnd(String),
+    /// A finger on a touch screen. The first finger down acts as the mouse.
+    Touch {
+        /// Unique for as long as the finger is on the screen.
+        id: u64,
+        phase: TouchPhase,
+        /// In points.
+        pos: Pos2,
+    },
     Key {
         key: Key,
         pressed: bool,
     },
 }
 
-#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "with_serde", derive(serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(rename_all = "snake_case"))]
+pub enum TouchPhase {
+    Start,
+    Move,
+    End,
+    /// E.g. the touch turned out to be a palm. Treated like `End`.
+    Cancel,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "with_serde", serde(rename_all = "snake_case"))]
 pub enum Key {
     Alt,
@@ -158,6 +525,8 @@ pub enum Key {
     Down,
     End,
     Escape,
+    /// Starts editing, e.g. a cell of a `Table`.
+    F2,
     Home,
     Insert,
     Left,
@@ -171,39 +540,208 @@ pub enum Key {
     // Space,
     Tab,
     Up,
+
+    /// Letters, for shortcuts like `Modifiers::CTRL` + `Key::S`.
+    /// Typed text arrives as `Event::Text`.
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+}
+
+fn is_repeating_key(key: Key) -> bool {
+    matches!(
+        key,
+        Key::Backspace
+            | Key::Delete
+            | Key::Down
+            | Key::Left
+            | Key::PageDown
+            | Key::PageUp
+            | Key::Right
+            | Key::Up
+    )
 }
 
 impl InputState {
+    /// Also adds presses of held navigation keys, repeated as `key_repeat` says,
+    /// unless the integration already repeats them. At most one repeat is added per frame,
+    /// so a long frame doesn't turn into a burst of e.g. backspaces.
+    /// Held keys are let go when the window loses focus, or after a long time without key events.
+    /// A press must move `drag_threshold` points before it becomes a drag.
     #[must_use]
-    pub fn begin_frame(self, new: RawInput) -> InputState {
-        let mouse = self.mouse.begin_frame(&new);
+    pub fn begin_frame(
+        self,
+        mut new: RawInput,
+        key_repeat: KeyRepeat,
+        drag_threshold: f32,
+    ) -> InputState {
+        let touches = self.touches.begin_frame(&mut new);
+        let mut mouse = self.mouse.begin_frame(&new, drag_threshold);
+        if touches.is_gesture() {
+            mouse.could_be_click = false;
+        }
         let dt = (new.time - self.raw.time) as f32;
+        let mut modifiers = self.modifiers;
+        let mut keys_down = self.keys_down;
+        let mut key_repeats = self.key_repeats;
+        let mut integration_repeats_keys = self.integration_repeats_keys;
+        let mut last_key_event_time = self.last_key_event_time;
+
+        // A repeating integration would have sent key presses by now, and a long frame
+        // (e.g. a debugger pause) may have hidden the release:
+        let no_key_events_for = new.time - last_key_event_time;
+        let lost_key_release = no_key_events_for > HELD_KEY_TIMEOUT
+            && (integration_repeats_keys || f64::from(dt) > HELD_KEY_TIMEOUT);
+        if !new.focused || lost_key_release {
+            keys_down.clear();
+            key_repeats.clear();
+            modifiers = Default::default();
+        }
+
+        for event in &new.events {
+            if let Event::Key { key, pressed } = *event {
+                last_key_event_time = new.time;
+                if is_repeating_key(key) {
+                    key_repeats.retain(|(repeating_key, _)| *repeating_key != key);
+                    if pressed {
+                        integration_repeats_keys |= keys_down.contains(&key);
+                        let first_repeat = new.time + f64::from(key_repeat.delay);
+                        key_repeats.push((key, first_repeat));
+                    }
+                }
+                if pressed {
+                    keys_down.insert(key);
+                } else {
+                    keys_down.remove(&key);
+                }
+                match key {
+                    Key::Alt => modifiers.alt = pressed,
+                    Key::Control => modifiers.ctrl = pressed,
+                    Key::Shift => modifiers.shift = pressed,
+                    Key::Logo => modifiers.logo = pressed,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut events = new.events.clone(); // TODO: remove clone() and use raw.events
+        if !integration_repeats_keys {
+            let interval = f64::from(key_repeat.interval.max(0.001));
+            for (key, next_repeat) in &mut key_repeats {
+                if *next_repeat <= new.time {
+                    events.push(Event::Key {
+                        key: *key,
+                        pressed: true,
+                    });
+                    *next_repeat += interval;
+                    if *next_repeat <= new.time {
+                        // We fell behind, so don't try to catch up:
+                        *next_repeat = new.time + interval;
+                    }
+                }
+            }
+        }
+
         InputState {
             mouse,
             scroll_delta: new.scroll_delta,
+            zoom_delta: new.zoom_delta * touches.zoom_delta,
             screen_size: new.screen_size,
             pixels_per_point: new.pixels_per_point.unwrap_or(1.0),
             time: new.time,
             dt,
+            predicted_dt: new.predicted_dt,
             seconds_since_midnight: new.seconds_since_midnight,
-            events: new.events.clone(), // TODO: remove clone() and use raw.events
+            modifiers: new.modifiers.unwrap_or(modifiers),
+            keys_down,
+            touches,
+            key_repeats,
+            integration_repeats_keys,
+            last_key_event_time,
+            events,
             raw: new,
         }
     }
+
+    /// Was the given key pressed this frame?
+    pub fn key_pressed(&self, desired_key: Key) -> bool {
+        self.events.iter().any(|event| match *event {
+            Event::Key { key, pressed } => key == desired_key && pressed,
+            _ => false,
+        })
+    }
+
+    /// Was the given key released this frame?
+    pub fn key_released(&self, desired_key: Key) -> bool {
+        self.events.iter().any(|event| match *event {
+            Event::Key { key, pressed } => key == desired_key && !pressed,
+            _ => false,
+        })
+    }
+
+    /// Is the given key currently held down?
+    pub fn key_down(&self, key: Key) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    /// All text typed (or pasted) this frame, in order.
+    pub fn text(&self) -> String {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Text(text) | Event::Paste(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl MouseInput {
     #[must_use]
-    pub fn begin_frame(mut self, new: &RawInput) -> MouseInput {
-        let delta = new
-            .mouse_pos
+    pub fn begin_frame(mut self, new: &RawInput, drag_threshold: f32) -> MouseInput {
+        // Don't let a drag freeze when the pointer leaves the window:
+        let is_dragging = self.down && new.mouse_down;
+        let new_pos = new.mouse_pos.or(if is_dragging { self.pos } else { None });
+
+        let mut delta = new_pos
             .and_then(|new| self.pos.map(|last| new - last))
             .unwrap_or_default();
         let pressed = !self.down && new.mouse_down;
 
         let released = self.down && !new.mouse_down;
+        let secondary_down = new.secondary_mouse_down && new_pos.is_some();
+        let middle_down = new.middle_mouse_down && new_pos.is_some();
         let click = released && self.could_be_click;
         let double_click = click && (new.time - self.last_click_time) < MAX_CLICK_DELAY;
+        let click_count = match (click, double_click) {
+            (true, true) => self.click_count + 1,
+            (true, false) => 1,
+            (false, _) => self.click_count,
+        };
+        let triple_click = click && click_count == 3;
         let mut press_origin = self.press_origin;
         let mut could_be_click = self.could_be_click;
         let mut last_click_time = self.last_click_time;
@@ -212,17 +750,27 @@ impl MouseInput {
         }
 
         if pressed {
-            press_origin = new.mouse_pos;
+            press_origin = new_pos;
             could_be_click = true;
         } else if !self.down || self.pos.is_none() {
             press_origin = None;
         }
 
-        if let (Some(press_origin), Some(mouse_pos)) = (new.mouse_pos, press_origin) {
-            could_be_click &= press_origin.distance(mouse_pos) < MAX_CLICK_DIST;
+        let mut is_dragging = false;
+        if let (Some(press_origin), Some(mouse_pos)) = (new_pos, press_origin) {
+            is_dragging = self.is_dragging || press_origin.distance(mouse_pos) >= drag_threshold;
+            could_be_click &= !is_dragging;
         } else {
             could_be_click = false;
         }
+        let is_dragging = is_dragging && new.mouse_down;
+        let drag_started = is_dragging && !self.is_dragging;
+        if drag_started {
+            // Catch up on the movement held back while below the threshold:
+            delta = new_pos.unwrap_or_default() - press_origin.unwrap_or_default();
+        } else if new.mouse_down && !is_dragging {
+            delta = Vec2::zero();
+        }
 
         if self.pressed {
             // Start of a drag: we want to track the velocity for during the drag
@@ -230,7 +778,7 @@ impl MouseInput {
             self.pos_tracker.clear();
         }
 
-        if let Some(mouse_pos) = new.mouse_pos {
+        if let Some(mouse_pos) = new_pos {
             self.pos_tracker.add(new.time, mouse_pos);
         } else {
             // we do not clear the `mouse_tracker` here, because it is exactly when a finger has
@@ -246,14 +794,23 @@ impl MouseInput {
         };
 
         MouseInput {
-            down: new.mouse_down && new.mouse_pos.is_some(),
+            down: new.mouse_down && new_pos.is_some(),
             pressed,
             released,
+            secondary_down,
+            secondary_pressed: !self.secondary_down && secondary_down,
+            secondary_released: self.secondary_down && !secondary_down,
+            middle_down,
+            middle_pressed: !self.middle_down && middle_down,
             could_be_click,
+            is_dragging,
+            drag_started,
             click,
             double_click,
+            triple_click,
+            click_count,
             last_click_time,
-            pos: new.mouse_pos,
+            pos: new_pos,
             press_origin,
             delta,
             velocity,
@@ -268,18 +825,28 @@ impl RawInput {
         // TODO: simpler way to show values, e.g. `ui.value("Mouse Pos:", self.mouse_pos);
         // TODO: easily change default font!
         ui.add(label!("mouse_down: {}", self.mouse_down));
+        ui.add(label!(
+            "secondary_mouse_down: {}",
+            self.secondary_mouse_down
+        ));
+        ui.add(label!("middle_mouse_down: {}", self.middle_mouse_down));
         ui.add(label!("mouse_pos: {:.1?}", self.mouse_pos));
         ui.add(label!("scroll_delta: {:?} points", self.scroll_delta));
+        ui.add(label!("scroll_notches: {:?}", self.scroll_notches));
+        ui.add(label!("zoom_delta: {:.3}", self.zoom_delta));
         ui.add(label!("screen_size: {:?} points", self.screen_size));
         ui.add(label!("pixels_per_point: {:?}", self.pixels_per_point))
             .tooltip_text(
                 "Also called hdpi factor.\nNumber of physical pixels per each logical pixel.",
             );
         ui.add(label!("time: {:.3} s", self.time));
+        ui.add(label!("predicted_dt: {:.3} s", self.predicted_dt));
         ui.add(label!(
             "seconds_since_midnight: {:?} s",
             self.seconds_since_midnight
         ));
+        ui.add(label!("focused: {}", self.focused));
+        ui.add(label!("modifiers: {:?}", self.modifiers));
         ui.add(label!("events: {:?}", self.events))
             .tooltip_text("key presses etc");
     }
@@ -298,6 +865,7 @@ impl InputState {
             });
 
         ui.add(label!("scroll_delta: {:?} points", self.scroll_delta));
+        ui.add(label!("zoom_delta: {:.3}", self.zoom_delta));
         ui.add(label!("screen_size: {:?} points", self.screen_size));
         ui.add(label!(
             "{} points for each physical pixel (hdpi factor)",
@@ -305,10 +873,14 @@ impl InputState {
         ));
         ui.add(label!("time: {:.3} s", self.time));
         ui.add(label!("dt: {:.3} s", self.dt));
+        ui.add(label!("predicted_dt: {:.3} s", self.predicted_dt));
         ui.add(label!(
             "seconds_since_midnight: {:?} s",
             self.seconds_since_midnight
         ));
+        ui.add(label!("modifiers: {:?}", self.modifiers));
+        ui.add(label!("keys_down: {:?}", self.keys_down));
+        ui.add(label!("touches: {:?}", self.touches.active));
         ui.add(label!("events: {:?}", self.events))
             .tooltip_text("key presses etc");
     }
@@ -320,9 +892,13 @@ impl MouseInput {
         ui.add(label!("down: {}", self.down));
         ui.add(label!("pressed: {}", self.pressed));
         ui.add(label!("released: {}", self.released));
+        ui.add(label!("secondary_down: {}", self.secondary_down));
+        ui.add(label!("middle_down: {}", self.middle_down));
         ui.add(label!("could_be_click: {}", self.could_be_click));
+        ui.add(label!("is_dragging: {}", self.is_dragging));
         ui.add(label!("click: {}", self.click));
         ui.add(label!("double_click: {}", self.double_click));
+        ui.add(label!("triple_click: {}", self.triple_click));
         ui.add(label!("last_click_time: {:.3}", self.last_click_time));
         ui.add(label!("pos: {:?}", self.pos));
         ui.add(label!("press_origin: {:?}", self.press_origin));
@@ -334,3 +910,138 @@ impl MouseInput {
         ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, x: f32, y: f32) -> Event {
+        Event::Touch {
+            id,
+            phase,
+            pos: pos2(x, y),
+        }
+    }
+
+    /// Runs the frames through `InputState::begin_frame`, one every 1/60 of a second.
+    fn run_frames(frames: Vec<Vec<Event>>) -> Vec<InputState> {
+        let mut input = InputState::default();
+        let mut states = vec![];
+        for (i, events) in frames.into_iter().enumerate() {
+            let raw = RawInput {
+                time: i as f64 / 60.0,
+                events,
+                ..Default::default()
+            };
+            input = input.begin_frame(raw, KeyRepeat::default(), 6.0);
+            states.push(input.clone());
+        }
+        states
+    }
+
+    #[test]
+    fn touches_pinch_and_pan() {
+        let states = run_frames(vec![
+            vec![
+                touch(1, TouchPhase::Start, 100.0, 100.0),
+                touch(2, TouchPhase::Start, 200.0, 100.0),
+            ],
+            // Spread twice as far apart:
+            vec![
+                touch(1, TouchPhase::Move, 50.0, 100.0),
+                touch(2, TouchPhase::Move, 250.0, 100.0),
+            ],
+            // Move together:
+            vec![
+                touch(1, TouchPhase::Move, 60.0, 120.0),
+                touch(2, TouchPhase::Move, 260.0, 120.0),
+            ],
+            vec![
+                touch(1, TouchPhase::End, 60.0, 120.0),
+                touch(2, TouchPhase::End, 260.0, 120.0),
+            ],
+        ]);
+
+        assert!(states[0].touches.is_gesture());
+        assert_eq!(states[0].zoom_delta, 1.0);
+
+        assert!((states[1].zoom_delta - 2.0).abs() < 1e-5);
+        assert_eq!(states[1].touches.pan_delta, Vec2::zero());
+
+        assert!((states[2].zoom_delta - 1.0).abs() < 1e-5);
+        assert_eq!(states[2].touches.pan_delta, vec2(10.0, 20.0));
+
+        assert!(!states[3].touches.is_gesture());
+        assert!(!states[3].mouse.could_be_click);
+    }
+
+    #[test]
+    fn held_key_repeats_at_most_once_per_frame() {
+        let key_repeat = KeyRepeat {
+            delay: 0.5,
+            interval: 0.1,
+        };
+        let frame = |input: InputState, time: f64, events: Vec<Event>, focused: bool| {
+            let raw = RawInput {
+                time,
+                events,
+                focused,
+                ..Default::default()
+            };
+            input.begin_frame(raw, key_repeat, 6.0)
+        };
+        let press = vec![Event::Key {
+            key: Key::Backspace,
+            pressed: true,
+        }];
+        let presses = |input: &InputState| {
+            input
+                .events
+                .iter()
+                .filter(|event| {
+                    **event
+                        == Event::Key {
+                            key: Key::Backspace,
+                            pressed: true,
+                        }
+                })
+                .count()
+        };
+
+        let input = frame(InputState::default(), 0.0, press.clone(), true);
+        assert_eq!(presses(&input), 1);
+        let input = frame(input, 0.4, vec![], true);
+        assert_eq!(presses(&input), 0, "Still within the delay");
+        let input = frame(input, 0.5, vec![], true);
+        assert_eq!(presses(&input), 1);
+        let input = frame(input, 0.95, vec![], true);
+        assert_eq!(
+            presses(&input),
+            1,
+            "A long frame should not add a burst of repeats"
+        );
+        let input = frame(input, 1.0, vec![], true);
+        assert_eq!(
+            presses(&input),
+            0,
+            "Should not catch up on the missed repeats"
+        );
+        let input = frame(input, 1.06, vec![], true);
+        assert_eq!(presses(&input), 1);
+
+        let input = frame(input, 1.1, vec![], false);
+        assert_eq!(presses(&input), 0);
+        assert!(
+            !input.key_down(Key::Backspace),
+            "Losing focus lets go of keys"
+        );
+        let input = frame(input, 1.3, vec![], true);
+        assert_eq!(presses(&input), 0);
+
+        // A long pause (e.g. in a debugger) may hide the release:
+        let input = frame(InputState::default(), 0.0, press, true);
+        let input = frame(input, 3.0, vec![], true);
+        assert_eq!(presses(&input), 0);
+        assert!(!input.key_down(Key::Backspace));
+    }
+}
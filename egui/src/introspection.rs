@@ -58,6 +58,16 @@ impl Texture {
                 ui.add_paint_cmd(PaintCmd::Triangles(triangles));
             });
         }
+
+        ui.collapsing("Inspect", |ui| {
+            ui.add(label!("Scroll to zoom, drag to pan, double-click to reset").auto_shrink());
+            let size = vec2(self.width as f32, self.height as f32);
+            ui.add(
+                ImageInspector::new("font_texture", paint::TextureId::Egui, size)
+                    .desired_size(vec2(ui.available().width(), 256.0))
+                    .pixel_color(|x, y| paint::color::gray(self[(x as usize, y as usize)], 255)),
+            );
+        });
     }
 }
 
@@ -39,11 +39,20 @@ type PaintList = Vec<(Rect, PaintCmd)>;
 
 /// TODO: improve this
 #[derive(Clone, Default)]
-pub struct GraphicLayers(AHashMap<Layer, PaintList>);
+pub struct GraphicLayers {
+    lists: AHashMap<Layer, PaintList>,
+    /// Multiplies all colors of a layer. Reset each frame.
+    opacity: AHashMap<Layer, f32>,
+}
 
 impl GraphicLayers {
     pub fn layer(&mut self, layer: Layer) -> &mut PaintList {
-        self.0.entry(layer).or_default()
+        self.lists.entry(layer).or_default()
+    }
+
+    /// Fade out everything painted to this layer this frame, e.g. a window being dragged.
+    pub fn set_opacity(&mut self, layer: Layer, opacity: f32) {
+        self.opacity.insert(layer, opacity);
     }
 
     pub fn drain(
@@ -53,15 +62,22 @@ impl GraphicLayers {
         let mut all_commands: Vec<_> = Default::default();
 
         for layer in area_order {
-            if let Some(commands) = self.0.get_mut(layer) {
+            if let Some(commands) = self.lists.get_mut(layer) {
+                if let Some(&opacity) = self.opacity.get(layer) {
+                    for (_, cmd) in commands.iter_mut() {
+                        cmd.fade(opacity);
+                    }
+                }
                 all_commands.extend(commands.drain(..));
             }
         }
 
-        if let Some(commands) = self.0.get_mut(&Layer::debug()) {
+        if let Some(commands) = self.lists.get_mut(&Layer::debug()) {
             all_commands.extend(commands.drain(..));
         }
 
+        self.opacity.clear();
+
         all_commands.into_iter()
     }
 }
@@ -31,17 +31,22 @@ mod introspection;
 mod layers;
 mod layout;
 pub mod math;
+mod measure;
 mod memory;
 mod movement_tracker;
+mod notifications;
 pub mod paint;
+mod recording;
+pub mod storage;
 mod style;
 mod types;
 mod ui;
+mod widget_search;
 pub mod widgets;
 
 pub use {
     containers::*,
-    context::Context,
+    context::{CommandSender, Context, FrameGuard},
     id::Id,
     input::*,
     layers::*,
@@ -49,7 +54,10 @@ pub use {
     math::*,
     memory::Memory,
     movement_tracker::MovementTracker,
+    notifications::Level,
     paint::{color, Color, TextStyle, Texture},
+    recording::{InputPlayer, InputRecording},
+    storage::Storage,
     style::Style,
     types::*,
     ui::Ui,
@@ -1,4 +1,6 @@
-use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, RangeInclusive, Sub, SubAssign};
+use std::ops::{
+    Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, RangeInclusive, Sub, SubAssign,
+};
 
 #[derive(Clone, Copy, Default)]
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
@@ -70,6 +72,11 @@ impl Vec2 {
         vec2(angle.cos(), angle.sin())
     }
 
+    /// The inverse of `angled`: zero to the right, and `TAU / 4.0` downwards.
+    pub fn angle(self) -> f32 {
+        self.y.atan2(self.x)
+    }
+
     /// Use this vector as a rotor, rotating something else.
     /// Example: `Vec2::angled(angle).rotate_other(some_vec)`
     #[must_use]
@@ -125,6 +132,29 @@ impl PartialEq for Vec2 {
 }
 impl Eq for Vec2 {}
 
+/// `v[0]` is `x` and `v[1]` is `y`. Useful for code that works along either axis.
+impl Index<usize> for Vec2 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Vec2 index out of bounds: {}", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Vec2 index out of bounds: {}", index),
+        }
+    }
+}
+
 impl Neg for Vec2 {
     type Output = Vec2;
 
@@ -293,6 +323,29 @@ impl PartialEq for Pos2 {
 }
 impl Eq for Pos2 {}
 
+/// `v[0]` is `x` and `v[1]` is `y`. Useful for code that works along either axis.
+impl Index<usize> for Pos2 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Pos2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
 impl AddAssign<Vec2> for Pos2 {
     fn add_assign(&mut self, rhs: Vec2) {
         *self = Pos2 {
@@ -0,0 +1,132 @@
+use crate::{paint::*, *};
+
+/// How close (in points) to a widget edge the ends of a measurement snap to it.
+const SNAP_DISTANCE: f32 = 6.0;
+
+/// The measure tool of `Style::debug_measure`:
+/// drag anywhere to measure the distance between two points,
+/// e.g. the gap between two widgets.
+///
+/// While it is on, the mouse button is only used for measuring.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct MeasureTool {
+    /// The mouse button is taken from `RawInput`, so we need to remember it ourselves.
+    mouse_was_down: bool,
+    /// The last measurement (still shown after releasing the mouse button).
+    measurement: Option<(Pos2, Pos2)>,
+}
+
+impl MeasureTool {
+    /// Call at the start of the frame, with the rects of the interactive widgets of last frame.
+    /// Takes the mouse button away from the rest of egui.
+    pub fn begin_frame(&mut self, raw: &mut RawInput, widget_rects: &[Rect]) {
+        if let Some(mouse_pos) = raw.mouse_pos {
+            let pos = snap_to_edges(mouse_pos, widget_rects);
+            if raw.mouse_down && !self.mouse_was_down {
+                self.measurement = Some((pos, pos));
+            } else if raw.mouse_down {
+                if let Some((_, end)) = &mut self.measurement {
+                    *end = pos;
+                }
+            }
+        }
+        self.mouse_was_down = raw.mouse_down;
+        raw.mouse_down = false;
+    }
+
+    pub fn paint(&self, ctx: &Context, widget_rects: &[Rect]) {
+        let layer = Layer::debug();
+
+        for &rect in widget_rects {
+            ctx.add_paint_cmd(
+                layer,
+                PaintCmd::Rect {
+                    rect,
+                    corner_radius: 0.0,
+                    fill: None,
+                    outline: Some(LineStyle::new(1.0, color::gray(128, 64))),
+                },
+            );
+        }
+
+        let (start, end) = match self.measurement {
+            Some(measurement) => measurement,
+            None => return,
+        };
+
+        ctx.add_paint_cmd(
+            layer,
+            PaintCmd::Rect {
+                rect: Rect::from_min_max(start.min(end), start.max(end)),
+                corner_radius: 0.0,
+                fill: None,
+                outline: Some(LineStyle::new(1.0, color::LIGHT_BLUE)),
+            },
+        );
+        ctx.add_paint_cmd(
+            layer,
+            PaintCmd::line_segment([start, end], color::YELLOW, 1.0),
+        );
+
+        let delta = end - start;
+        let pixels_per_point = ctx.pixels_per_point();
+        let text = format!(
+            "dx: {:.1} pt ({:.0} px)\ndy: {:.1} pt ({:.0} px)\nlength: {:.1} pt",
+            delta.x.abs(),
+            delta.x.abs() * pixels_per_point,
+            delta.y.abs(),
+            delta.y.abs() * pixels_per_point,
+            delta.length(),
+        );
+        let text_style = TextStyle::Monospace;
+        let galley = ctx.fonts()[text_style].layout_multiline(text, f32::INFINITY);
+        let text_rect = Rect::from_min_size(end + vec2(12.0, 12.0), galley.size);
+        ctx.add_paint_cmd(
+            layer,
+            PaintCmd::Rect {
+                rect: text_rect.expand(2.0),
+                corner_radius: 0.0,
+                fill: Some(color::gray(0, 240)),
+                outline: Some(LineStyle::new(1.0, color::YELLOW)),
+            },
+        );
+        ctx.add_galley(
+            layer,
+            text_rect.min,
+            galley,
+            text_style,
+            Some(color::YELLOW),
+        );
+    }
+}
+
+/// Move `pos` to the closest widget edge(s) within `SNAP_DISTANCE`.
+fn snap_to_edges(pos: Pos2, widget_rects: &[Rect]) -> Pos2 {
+    let mut snapped = pos;
+    let mut best_x = SNAP_DISTANCE;
+    let mut best_y = SNAP_DISTANCE;
+
+    for rect in widget_rects {
+        let near_y = rect.top() - SNAP_DISTANCE <= pos.y && pos.y <= rect.bottom() + SNAP_DISTANCE;
+        let near_x = rect.left() - SNAP_DISTANCE <= pos.x && pos.x <= rect.right() + SNAP_DISTANCE;
+
+        if near_y {
+            for &x in &[rect.left(), rect.right()] {
+                if (x - pos.x).abs() < best_x {
+                    best_x = (x - pos.x).abs();
+                    snapped.x = x;
+                }
+            }
+        }
+        if near_x {
+            for &y in &[rect.top(), rect.bottom()] {
+                if (y - pos.y).abs() < best_y {
+                    best_y = (y - pos.y).abs();
+                    snapped.y = y;
+                }
+            }
+        }
+    }
+
+    snapped
+}
@@ -1,9 +1,13 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    containers::{area, collapsing_header, menu, resize, scroll_area, window},
-    widgets::text_edit,
-    Id, Layer, Pos2, Rect,
+    containers::{
+        area, collapsing_header, hover_card, list_box, menu, move_animation, popup, resize,
+        scroll_area, side_panel, splitter, table, timeline, top_bottom_panel, tree_view,
+        virtual_keyboard, window,
+    },
+    widgets::{code_editor, color_edit, enum_drop_down, image_inspector, plot, slider, text_edit},
+    Align, Id, Key, Layer, Pos2, Rect,
 };
 
 #[derive(Clone, Debug, Default)]
@@ -17,16 +21,70 @@ pub struct Memory {
     #[cfg_attr(feature = "with_serde", serde(skip))]
     pub(crate) kb_focus_id: Option<Id>,
 
+    /// Tab and shift+tab moving the keyboard focus.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) kb_focus: KbFocus,
+
+    /// The item of an open menu picked with the arrow keys. It is drawn as hovered.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) menu_highlight: Option<Id>,
+
     // states of various types of widgets
+    pub(crate) code_editors: HashMap<Id, code_editor::State>,
     pub(crate) collapsing_headers: HashMap<Id, collapsing_header::State>,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) color_edits: HashMap<Id, color_edit::State>,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) drop_down_searches: HashMap<Id, enum_drop_down::SearchState>,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) hover_cards: HashMap<Id, hover_card::State>,
+    pub(crate) image_inspectors: HashMap<Id, image_inspector::State>,
+    pub(crate) list_boxes: HashMap<Id, list_box::State>,
     pub(crate) menu_bar: HashMap<Id, menu::BarState>,
+    pub(crate) move_animations: HashMap<Id, move_animation::State>,
+    pub(crate) plots: HashMap<Id, plot::State>,
     pub(crate) resize: HashMap<Id, resize::State>,
     pub(crate) scroll_areas: HashMap<Id, scroll_area::State>,
+    pub(crate) side_panels: HashMap<Id, side_panel::State>,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) sliders: HashMap<Id, slider::State>,
+    pub(crate) splitters: HashMap<Id, splitter::State>,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) tables: HashMap<Id, table::State>,
     pub(crate) text_edit: HashMap<Id, text_edit::State>,
+    pub(crate) timelines: HashMap<Id, timeline::State>,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) top_bottom_panels: HashMap<Id, top_bottom_panel::State>,
+    pub(crate) tree_views: HashMap<Id, tree_view::State>,
+    pub(crate) virtual_keyboards: HashMap<Id, virtual_keyboard::State>,
 
     #[cfg_attr(feature = "with_serde", serde(skip))]
     pub(crate) window_interaction: Option<window::WindowInteraction>,
 
+    /// Which popup (e.g. a combo box) is open, if any.
+    /// Only one can be open at a time.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    popup: Option<Id>,
+
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) tooltip: popup::TooltipState,
+
+    /// Where the open context menu (if any) was opened.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) context_menu_pos: Pos2,
+
+    /// Any `ScrollArea` containing this rect scrolls so that it becomes visible,
+    /// aligned like this (or as little as possible if `None`). See `scroll_to`.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub(crate) scroll_target: Option<(Rect, Option<Align>)>,
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    next_scroll_target: Option<(Rect, Option<Align>)>,
+
+    /// The open modal layers (e.g. a modal `Window`), topmost last.
+    /// While any is open, everything beneath the topmost one is ignored by the mouse and keyboard.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    modals: Vec<Layer>,
+
     pub(crate) areas: Areas,
 }
 
@@ -52,6 +110,10 @@ pub struct Interaction {
     /// and then do content layout.
     pub drag_is_window: bool,
 
+    /// Which of the handles of `drag_id` is being dragged,
+    /// for widgets with more than one (e.g. a `RangeSlider`).
+    pub drag_handle: Option<usize>,
+
     /// Any interest in catching clicks this frame?
     /// Cleared to false at start of each frame.
     pub click_interest: bool,
@@ -59,6 +121,20 @@ pub struct Interaction {
     /// Any interest in catching clicks this frame?
     /// Cleared to false at start of each frame.
     pub drag_interest: bool,
+
+    /// A widget interested in secondary clicks that has a secondary mouse press on it.
+    pub secondary_click_id: Option<Id>,
+
+    /// The `ScrollArea` being dragged with the middle mouse button.
+    pub middle_drag_id: Option<Id>,
+
+    /// The widget that got the scrolling this frame.
+    /// Cleared at start of each frame.
+    pub scroll_id: Option<Id>,
+
+    /// The widget that got the zooming this frame.
+    /// Cleared at start of each frame.
+    pub zoom_id: Option<Id>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -79,10 +155,38 @@ pub struct Areas {
     wants_to_be_on_top: HashSet<Layer>,
 }
 
+/// Moving the keyboard focus with tab and shift+tab, in the order widgets call
+/// `Memory::interested_in_kb_focus`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct KbFocus {
+    /// Tab was pressed this frame, and not yet used to move the focus.
+    tab: bool,
+    /// Shift+tab was pressed this frame, and not yet used to move the focus.
+    shift_tab: bool,
+    /// The next interested widget gets the focus.
+    give_to_next: bool,
+    /// The last interested widget of this frame gets the focus.
+    give_to_last: bool,
+    /// The last interested widget so far this frame. Shift+tab moves the focus here.
+    last_interested: Option<Id>,
+}
+
 impl Memory {
     pub(crate) fn begin_frame(&mut self, prev_input: &crate::input::InputState) {
         self.interaction.click_interest = false;
         self.interaction.drag_interest = false;
+        self.interaction.scroll_id = None;
+        self.interaction.zoom_id = None;
+        self.tooltip.begin_frame();
+        self.scroll_target = self.next_scroll_target.take();
+
+        if !prev_input.mouse.secondary_down {
+            self.interaction.secondary_click_id = None;
+        }
+
+        if !prev_input.mouse.middle_down {
+            self.interaction.middle_drag_id = None;
+        }
 
         if !prev_input.mouse.could_be_click {
             self.interaction.click_id = None;
@@ -92,6 +196,7 @@ impl Memory {
             // mouse was not down last frame
             self.interaction.click_id = None;
             self.interaction.drag_id = None;
+            self.interaction.drag_handle = None;
 
             let window_interaction = self.window_interaction.take();
             if let Some(window_interaction) = window_interaction {
@@ -108,12 +213,128 @@ impl Memory {
         }
     }
 
+    /// Call after the new input is known.
+    pub(crate) fn begin_kb_focus_frame(&mut self, input: &crate::input::InputState) {
+        let kb_focus = &mut self.kb_focus;
+        let tab = input.key_pressed(Key::Tab);
+        kb_focus.tab = tab && !input.modifiers.shift;
+        kb_focus.shift_tab = tab && input.modifiers.shift;
+        kb_focus.last_interested = None;
+        if self.kb_focus_id.is_none() {
+            kb_focus.give_to_next |= kb_focus.tab;
+            kb_focus.give_to_last |= kb_focus.shift_tab;
+        }
+    }
+
+    /// Call for every widget that can get keyboard focus, in layout order, to be part of
+    /// the tab order. Widgets with `Sense::click` do this in `Context::interact`.
+    pub(crate) fn interested_in_kb_focus(&mut self, id: Id) {
+        let kb_focus = &mut self.kb_focus;
+        if kb_focus.give_to_next {
+            self.kb_focus_id = Some(id);
+            kb_focus.give_to_next = false;
+        } else if self.kb_focus_id == Some(id) {
+            if kb_focus.tab {
+                self.kb_focus_id = None;
+                kb_focus.give_to_next = true;
+            } else if kb_focus.shift_tab {
+                self.kb_focus_id = kb_focus.last_interested;
+                kb_focus.give_to_last = kb_focus.last_interested.is_none();
+            }
+            kb_focus.tab = false;
+            kb_focus.shift_tab = false;
+        }
+        kb_focus.last_interested = Some(id);
+    }
+
     pub(crate) fn end_frame(&mut self) {
+        if self.kb_focus.give_to_last {
+            // Shift+tab from the first widget wraps around to the last:
+            self.kb_focus_id = self.kb_focus.last_interested;
+            self.kb_focus.give_to_last = false;
+        }
+        // (Tab from the last widget wraps around to the first next frame, via `give_to_next`)
+
+        // Modals that were not shown this frame are closed:
+        let areas = &self.areas;
+        self.modals
+            .retain(|layer| areas.visible_current_frame.contains(layer));
         self.areas.end_frame()
     }
 
     pub fn layer_at(&self, pos: Pos2, resize_interact_radius_side: f32) -> Option<Layer> {
-        self.areas.layer_at(pos, resize_interact_radius_side)
+        self.areas
+            .layer_at(pos, resize_interact_radius_side)
+            .filter(|layer| !self.is_beneath_modal(*layer))
+    }
+
+    /// Open a modal layer (or keep it open). Call this each frame the modal is shown.
+    /// While open, it is kept on top of all layers of the same `Order`.
+    pub fn open_modal(&mut self, layer: Layer) {
+        if !self.modals.contains(&layer) {
+            self.modals.push(layer);
+        }
+        self.areas.move_to_top(layer);
+    }
+
+    pub fn close_modal(&mut self, layer: Layer) {
+        self.modals.retain(|modal| *modal != layer);
+    }
+
+    /// The topmost open modal layer, if any.
+    pub fn top_modal(&self) -> Option<Layer> {
+        self.modals.last().copied()
+    }
+
+    /// Is this layer covered by an open modal, so that it should ignore all input?
+    /// Layers above the topmost modal (e.g. popups opened from it) are not.
+    pub fn is_beneath_modal(&self, layer: Layer) -> bool {
+        let modal = match self.top_modal() {
+            Some(modal) if modal != layer => modal,
+            _ => return false,
+        };
+        let order = self.areas.order();
+        let index_of = |layer| order.iter().position(|x| *x == layer);
+        match (index_of(layer), index_of(modal)) {
+            (Some(layer_index), Some(modal_index)) => layer_index < modal_index,
+            _ => true,
+        }
+    }
+
+    /// Next frame, scroll any `ScrollArea`:s containing this rect (in the coordinates of this frame)
+    /// so that it becomes visible.
+    pub fn scroll_to(&mut self, rect: Rect) {
+        self.next_scroll_target = Some((rect, None));
+    }
+
+    /// Like `scroll_to`, but puts the rect at the top/left (`Align::Min`), center or bottom/right
+    /// of the visible area, even if it is already visible.
+    pub fn scroll_to_align(&mut self, rect: Rect, align: Align) {
+        self.next_scroll_target = Some((rect, Some(align)));
+    }
+
+    pub fn is_popup_open(&self, popup_id: Id) -> bool {
+        self.popup == Some(popup_id)
+    }
+
+    pub fn is_any_popup_open(&self) -> bool {
+        self.popup.is_some()
+    }
+
+    pub fn open_popup(&mut self, popup_id: Id) {
+        self.popup = Some(popup_id);
+    }
+
+    pub fn close_popup(&mut self) {
+        self.popup = None;
+    }
+
+    pub fn toggle_popup(&mut self, popup_id: Id) {
+        if self.is_popup_open(popup_id) {
+            self.close_popup();
+        } else {
+            self.open_popup(popup_id);
+        }
     }
 }
 
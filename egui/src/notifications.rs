@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use crate::{paint::*, widgets::*, *};
+
+/// How important a notification is. Decides the color of its text and outline.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Level {
+    fn color(self) -> Color {
+        match self {
+            Level::Info => color::LIGHT_BLUE,
+            Level::Warning => color::YELLOW,
+            Level::Error => color::srgba(255, 100, 100, 255),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Toast {
+    /// Unique among the toasts ever shown, so that each gets its own `Area`.
+    id: u64,
+    text: String,
+    level: Level,
+    /// When it was first shown. `None` until then.
+    shown_time: Option<f64>,
+}
+
+/// The toasts of `Context::notify`, stacked in the bottom right corner of the screen.
+/// Each disappears after `Style::notification_duration` seconds, or when closed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Notifications {
+    toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+impl Notifications {
+    pub fn push(&mut self, text: String, level: Level) {
+        self.toasts.push(Toast {
+            id: self.next_id,
+            text,
+            level,
+            shown_time: None,
+        });
+        self.next_id += 1;
+    }
+
+    pub fn show(&mut self, ctx: &Arc<Context>) {
+        let time = ctx.input().time;
+        let duration = f64::from(ctx.style().notification_duration);
+        self.toasts.retain(|toast| match toast.shown_time {
+            Some(shown_time) => time - shown_time < duration,
+            None => true,
+        });
+
+        let margin = 8.0;
+        let spacing = 4.0;
+        let screen = ctx.rect();
+        let mut bottom = screen.bottom() - margin;
+        let mut closed = vec![];
+
+        // Newest at the bottom:
+        for toast in self.toasts.iter_mut().rev() {
+            toast.shown_time.get_or_insert(time);
+
+            let area_id = Id::new("notification").with(toast.id);
+            // We only know the size after the first frame:
+            let size = ctx
+                .memory()
+                .areas
+                .get(area_id)
+                .map(|area| area.size)
+                .unwrap_or_default();
+            let pos = pos2(screen.right() - margin - size.x, bottom - size.y);
+            bottom -= size.y + spacing;
+
+            let toast_color = toast.level.color();
+            let text = &toast.text;
+            Area::new(area_id)
+                .order(Order::Foreground)
+                .fixed_pos(pos)
+                .show(ctx, |ui| {
+                    let frame =
+                        Frame::popup(ui.style()).outline(Some(LineStyle::new(1.0, toast_color)));
+                    frame.show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(Label::new(text.as_str()).text_color(toast_color));
+                            if ui.add(Button::new("x").small()).clicked {
+                                closed.push(toast.id);
+                            }
+                        });
+                    });
+                });
+        }
+
+        self.toasts.retain(|toast| !closed.contains(&toast.id));
+    }
+}
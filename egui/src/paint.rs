@@ -7,8 +7,9 @@ mod texture_atlas;
 
 pub use {
     color::Color,
-    command::{LineStyle, PaintCmd},
+    command::{LineStyle, PaintCmd, Shadow, StrokeAlignment},
+    font::ColorSpan,
     fonts::{FontDefinitions, Fonts, TextStyle},
-    mesher::{PaintBatches, PaintOptions, Path, Triangles, Vertex},
+    mesher::{PaintBatches, PaintOptions, Path, TextureId, Triangles, Vertex},
     texture_atlas::Texture,
 };
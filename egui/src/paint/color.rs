@@ -9,6 +9,33 @@ pub struct Color {
     pub a: u8,
 }
 
+impl Color {
+    /// Multiply all channels (i.e. fade it out, since the alpha is premultiplied).
+    pub fn multiply(self, factor: f32) -> Color {
+        let mul = |c: u8| crate::math::clamp((c as f32 * factor).round(), 0.0..=255.0) as u8;
+        srgba(mul(self.r), mul(self.g), mul(self.b), mul(self.a))
+    }
+
+    /// E.g. `"#ff8000ff"`.
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+
+    /// Parses `"#rrggbb"` or `"#rrggbbaa"` (the `#` is optional).
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None; // `from_str_radix` would accept a sign
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok();
+        match hex.len() {
+            6 => Some(srgba(channel(0)?, channel(1)?, channel(2)?, 255)),
+            8 => Some(srgba(channel(0)?, channel(1)?, channel(2)?, channel(3)?)),
+            _ => None,
+        }
+    }
+}
+
 pub const fn srgba(r: u8, g: u8, b: u8, a: u8) -> Color {
     Color { r, g, b, a }
 }
@@ -59,3 +86,27 @@ pub const GREEN: Color = srgba(0, 255, 0, 255);
 pub const BLUE: Color = srgba(0, 0, 255, 255);
 pub const YELLOW: Color = srgba(255, 255, 0, 255);
 pub const LIGHT_BLUE: Color = srgba(140, 160, 255, 255);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        let color = srgba(255, 128, 0, 64);
+        assert_eq!(color.to_hex(), "#ff800040");
+        assert_eq!(Color::from_hex(&color.to_hex()), Some(color));
+    }
+
+    #[test]
+    fn from_hex() {
+        assert_eq!(Color::from_hex("#ff8000"), Some(srgba(255, 128, 0, 255)));
+        assert_eq!(Color::from_hex(" FF8000 "), Some(srgba(255, 128, 0, 255)));
+        assert_eq!(Color::from_hex("ff800080"), Some(srgba(255, 128, 0, 128)));
+        assert_eq!(Color::from_hex(""), None);
+        assert_eq!(Color::from_hex("#fff"), None);
+        assert_eq!(Color::from_hex("#ff80zz"), None);
+        assert_eq!(Color::from_hex("#+f8000"), None);
+        assert_eq!(Color::from_hex("#ff80000"), None);
+    }
+}
@@ -1,6 +1,6 @@
 use {
     super::{font::Galley, fonts::TextStyle, Color, Path, Triangles},
-    crate::math::{Pos2, Rect},
+    crate::math::{Pos2, Rect, Vec2},
 };
 
 // TODO: rename, e.g. `paint::Cmd`?
@@ -28,6 +28,14 @@ pub enum PaintCmd {
         fill: Option<Color>,
         outline: Option<LineStyle>,
     },
+    /// A soft shadow: `rect` filled with `color`, fading out to transparent
+    /// over `blur` points outside of it.
+    Shadow {
+        rect: Rect,
+        corner_radius: f32,
+        blur: f32,
+        color: Color,
+    },
     Text {
         /// Top left corner of the first character.
         pos: Pos2,
@@ -46,6 +54,94 @@ impl PaintCmd {
             style: LineStyle::new(width, color),
         }
     }
+
+    /// A filled and/or outlined convex polygon, e.g. an arrow head or a marker.
+    /// The points can go either way around. Concave polygons are not painted correctly.
+    /// Fewer than three points paint nothing.
+    pub fn convex_polygon(
+        points: &[Pos2],
+        fill: Option<Color>,
+        outline: Option<LineStyle>,
+    ) -> Self {
+        // The anti-aliasing needs the points to go clockwise (on screen, where y is down):
+        let n = points.len();
+        if n < 3 {
+            return Self::Path {
+                path: Path::default(),
+                closed: true,
+                fill,
+                outline,
+            };
+        }
+        let signed_area: f32 = (0..n)
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % n]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+        let path = if signed_area < 0.0 {
+            let reversed: Vec<Pos2> = points.iter().rev().copied().collect();
+            Path::from_point_loop(&reversed)
+        } else {
+            Path::from_point_loop(points)
+        };
+        Self::Path {
+            path,
+            closed: true,
+            fill,
+            outline,
+        }
+    }
+
+    /// Multiply all colors with `opacity`, e.g. to fade it in.
+    pub fn fade(&mut self, opacity: f32) {
+        match self {
+            PaintCmd::Circle { fill, outline, .. }
+            | PaintCmd::Path { fill, outline, .. }
+            | PaintCmd::Rect { fill, outline, .. } => {
+                if let Some(fill) = fill {
+                    *fill = fill.multiply(opacity);
+                }
+                if let Some(outline) = outline {
+                    outline.color = outline.color.multiply(opacity);
+                }
+            }
+            PaintCmd::LineSegment { style, .. } => style.color = style.color.multiply(opacity),
+            PaintCmd::Shadow { color, .. } => *color = color.multiply(opacity),
+            PaintCmd::Text { galley, color, .. } => {
+                *color = color.multiply(opacity);
+                for span in &mut galley.color_spans {
+                    span.color = span.color.multiply(opacity);
+                }
+            }
+            PaintCmd::Triangles(triangles) => {
+                for vertex in &mut triangles.vertices {
+                    vertex.color = vertex.color.multiply(opacity);
+                }
+            }
+        }
+    }
+
+    /// Move the whole thing by this much.
+    pub fn translate(&mut self, delta: Vec2) {
+        match self {
+            PaintCmd::Circle { center, .. } => *center += delta,
+            PaintCmd::LineSegment { points, .. } => {
+                points[0] += delta;
+                points[1] += delta;
+            }
+            PaintCmd::Path { path, .. } => path.translate(delta),
+            PaintCmd::Rect { rect, .. } | PaintCmd::Shadow { rect, .. } => {
+                *rect = rect.translate(delta)
+            }
+            PaintCmd::Text { pos, .. } => *pos += delta,
+            PaintCmd::Triangles(triangles) => {
+                for vertex in &mut triangles.vertices {
+                    vertex.pos += delta;
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -53,6 +149,9 @@ impl PaintCmd {
 pub struct LineStyle {
     pub width: f32,
     pub color: Color,
+    /// Only used for the outlines of `PaintCmd::Rect` and `PaintCmd::Circle`.
+    #[cfg_attr(feature = "with_serde", serde(default))]
+    pub alignment: StrokeAlignment,
 }
 
 impl LineStyle {
@@ -60,6 +159,54 @@ impl LineStyle {
         Self {
             width: width.into(),
             color: color.into(),
+            alignment: Default::default(),
         }
     }
+
+    pub fn alignment(mut self, alignment: StrokeAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+}
+
+/// A shadow under a rectangle, e.g. a `Window`. See `PaintCmd::Shadow`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Shadow {
+    /// Where the shadow is, relative to the rectangle (e.g. down and to the right).
+    pub offset: Vec2,
+    /// How many points it takes to fade out.
+    pub blur: f32,
+    pub color: Color,
+}
+
+impl Shadow {
+    pub fn paint_cmd(&self, rect: Rect, corner_radius: f32) -> PaintCmd {
+        PaintCmd::Shadow {
+            rect: rect.translate(self.offset),
+            corner_radius,
+            blur: self.blur,
+            color: self.color,
+        }
+    }
+
+    /// How far outside of the rectangle the shadow reaches.
+    pub fn extent(&self) -> f32 {
+        self.blur + self.offset.x.abs().max(self.offset.y.abs())
+    }
+}
+
+/// Where the outline of a shape goes, relative to the edge of the shape.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum StrokeAlignment {
+    /// Fully inside the shape.
+    /// The edges of rectangles are snapped to the pixel grid and the width to whole pixels,
+    /// so thin outlines are crisp at any `pixels_per_point`.
+    Inside,
+    /// Straddling the edge. Thin outlines at integer positions will be blurry.
+    #[default]
+    Center,
+    /// Fully outside the shape, snapped to pixels just like `Inside`.
+    Outside,
 }
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 use {
     ahash::AHashMap,
@@ -8,7 +11,7 @@ use {
 
 use crate::math::{vec2, Vec2};
 
-use super::texture_atlas::TextureAtlas;
+use super::{texture_atlas::TextureAtlas, Color};
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct GalleyCursor {
@@ -32,6 +35,19 @@ pub struct Galley {
 
     // Optimization: calculate once and reuse.
     pub size: Vec2,
+
+    /// Colors for some of the chars, e.g. from syntax highlighting.
+    /// Sorted by start and not overlapping.
+    /// Chars outside of all spans get the color the galley is painted with.
+    pub color_spans: Vec<ColorSpan>,
+}
+
+/// A color for a range of chars in a `Galley`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorSpan {
+    /// Character based, NOT bytes.
+    pub char_range: std::ops::Range<usize>,
+    pub color: Color,
 }
 
 /// A typeset piece of text on a single line.
@@ -184,6 +200,8 @@ pub struct Font {
     pixels_per_point: f32,
     glyph_infos: AHashMap<char, GlyphInfo>, // TODO: see if we can optimize if we switch to a binary search
     atlas: Arc<Mutex<TextureAtlas>>,
+    /// Incremented for every galley laid out. Shared by all the fonts of a `Fonts`.
+    pub(crate) num_galleys: Arc<AtomicUsize>,
 }
 
 impl Font {
@@ -202,6 +220,7 @@ impl Font {
             pixels_per_point,
             glyph_infos: Default::default(),
             atlas,
+            num_galleys: Default::default(),
         };
 
         /// Printable ASCII characters [32, 126], which excludes control codes.
@@ -323,8 +342,10 @@ impl Font {
             text,
             lines: vec![line],
             size,
+            color_spans: vec![],
         };
         galley.sanity_check();
+        self.num_galleys.fetch_add(1, Ordering::Relaxed);
         galley
     }
 
@@ -375,8 +396,14 @@ impl Font {
         }
         let size = vec2(widest_line, lines.last().unwrap().y_max);
 
-        let galley = Galley { text, lines, size };
+        let galley = Galley {
+            text,
+            lines,
+            size,
+            color_spans: vec![],
+        };
         galley.sanity_check();
+        self.num_galleys.fetch_add(1, Ordering::Relaxed);
         galley
     }
 
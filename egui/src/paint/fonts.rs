@@ -1,7 +1,10 @@
 use std::{
     collections::BTreeMap,
     hash::{Hash, Hasher},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use parking_lot::Mutex;
@@ -15,6 +18,8 @@ use super::{
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum TextStyle {
+    /// For dense panels, e.g. the `small()` variants of widgets.
+    Small,
     Body,
     Button,
     Heading,
@@ -45,6 +50,7 @@ impl Default for FontDefinitions {
 impl FontDefinitions {
     pub fn with_pixels_per_point(pixels_per_point: f32) -> Self {
         let mut fonts = BTreeMap::new();
+        fonts.insert(TextStyle::Small, (FontFamily::VariableWidth, 11.0));
         fonts.insert(TextStyle::Body, (FontFamily::VariableWidth, 14.0));
         fonts.insert(TextStyle::Button, (FontFamily::VariableWidth, 16.0));
         fonts.insert(TextStyle::Heading, (FontFamily::VariableWidth, 24.0));
@@ -63,6 +69,8 @@ pub struct Fonts {
     definitions: FontDefinitions,
     fonts: BTreeMap<TextStyle, Font>,
     texture: Texture,
+    /// Galleys laid out by any of the fonts, since `take_num_galleys` was last called.
+    num_galleys: Arc<AtomicUsize>,
 }
 
 impl Fonts {
@@ -107,6 +115,7 @@ impl Fonts {
             pixels_per_point,
             fonts,
         } = definitions;
+        let num_galleys = &self.num_galleys;
         self.fonts = fonts
             .into_iter()
             .map(|(text_style, (family, size))| {
@@ -115,10 +124,9 @@ impl Fonts {
                     FontFamily::VariableWidth => variable_typeface_data,
                 };
 
-                (
-                    text_style,
-                    Font::new(atlas.clone(), typeface_data, size, pixels_per_point),
-                )
+                let mut font = Font::new(atlas.clone(), typeface_data, size, pixels_per_point);
+                font.num_galleys = num_galleys.clone();
+                (text_style, font)
             })
             .collect();
         self.texture = atlas.lock().texture().clone();
@@ -131,6 +139,11 @@ impl Fonts {
     pub fn texture(&self) -> &Texture {
         &self.texture
     }
+
+    /// How many galleys have been laid out since the last call to this.
+    pub fn take_num_galleys(&self) -> usize {
+        self.num_galleys.swap(0, Ordering::Relaxed)
+    }
 }
 
 impl std::ops::Index<TextStyle> for Fonts {
@@ -7,19 +7,33 @@ use {
     super::{
         color::{self, srgba, Color},
         fonts::Fonts,
-        LineStyle, PaintCmd,
+        LineStyle, PaintCmd, StrokeAlignment,
     },
     crate::math::*,
 };
 
 const WHITE_UV: (u16, u16) = (1, 1);
 
+/// Which texture some `Triangles` should be painted with.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TextureId {
+    /// The egui font texture (`Context::texture`).
+    /// `Vertex::uv` are texel indices into it.
+    #[default]
+    Egui,
+
+    /// A texture registered with the backend (e.g. `egui_glium::Painter::new_user_texture`).
+    /// `Vertex::uv` are normalized, so that `u16::MAX` means `1.0`.
+    User(u64),
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Vertex {
     /// Pixel coordinates
     pub pos: Pos2,
-    /// Texel indices into the texture
+    /// Texel indices into the texture (see `TextureId` for details)
     pub uv: (u16, u16),
     /// sRGBA, premultiplied alpha
     pub color: Color,
@@ -31,20 +45,43 @@ pub struct Triangles {
     /// Draw as triangles (i.e. the length is a multiple of three)
     pub indices: Vec<u32>,
     pub vertices: Vec<Vertex>,
+    /// All the triangles are painted with this texture.
+    #[cfg_attr(feature = "with_serde", serde(default))]
+    pub texture_id: TextureId,
 }
 
-/// Grouped by clip rectangles, in pixel coordinates
+/// Grouped by clip rectangles (in pixel coordinates) and texture
 pub type PaintBatches = Vec<(Rect, Triangles)>;
 
 // ----------------------------------------------------------------------------
 
 impl Triangles {
-    pub fn append(&mut self, triangles: &Triangles) {
+    pub fn with_texture(texture_id: TextureId) -> Self {
+        Self {
+            texture_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Add `triangles` to these, unless they are painted with a different texture.
+    /// Returns false (and changes nothing) if they are.
+    pub fn append(&mut self, triangles: &Triangles) -> bool {
+        if self.is_empty() {
+            self.texture_id = triangles.texture_id;
+        } else if self.texture_id != triangles.texture_id {
+            return false;
+        }
+
         let index_offset = self.vertices.len() as u32;
         for index in &triangles.indices {
             self.indices.push(index_offset + index);
         }
         self.vertices.extend(triangles.vertices.iter());
+        true
     }
 
     fn triangle(&mut self, a: u32, b: u32, c: u32) {
@@ -133,6 +170,7 @@ impl Triangles {
                     .map(|vi| vi - min_vindex)
                     .collect(),
                 vertices: self.vertices[(min_vindex as usize)..=(max_vindex as usize)].to_vec(),
+                texture_id: self.texture_id,
             });
         }
         output
@@ -185,6 +223,13 @@ impl Path {
         self.0.reserve(additional)
     }
 
+    /// Move all points by this much.
+    pub fn translate(&mut self, delta: Vec2) {
+        for point in &mut self.0 {
+            point.pos += delta;
+        }
+    }
+
     #[inline(always)]
     pub fn add_point(&mut self, pos: Pos2, normal: Vec2) {
         self.0.push(PathPoint { pos, normal });
@@ -319,6 +364,8 @@ pub struct PaintOptions {
     pub anti_alias: bool,
     /// Size of a pixel in points, e.g. 0.5
     pub aa_size: f32,
+    /// Used for snapping outlines to pixels, see `StrokeAlignment`.
+    pub pixels_per_point: f32,
     pub debug_paint_clip_rects: bool,
 }
 
@@ -327,6 +374,7 @@ impl Default for PaintOptions {
         Self {
             anti_alias: true,
             aa_size: 1.0,
+            pixels_per_point: 1.0,
             debug_paint_clip_rects: false,
         }
     }
@@ -578,11 +626,23 @@ pub fn paint_command_into_triangles(
                 fill_closed_path(out, options, &path.0, fill);
             }
             if let Some(outline) = outline {
+                let radius = match outline.alignment {
+                    StrokeAlignment::Inside => radius - 0.5 * outline.width,
+                    StrokeAlignment::Center => radius,
+                    StrokeAlignment::Outside => radius + 0.5 * outline.width,
+                };
+                path.clear();
+                path.add_circle(center, radius.max(0.0));
                 paint_path_outline(out, options, Closed, &path.0, outline);
             }
         }
         PaintCmd::Triangles(triangles) => {
-            out.append(&triangles);
+            // `paint_commands_into_triangles` starts a new batch for each new texture,
+            // so only other callers can get this wrong:
+            if !out.append(&triangles) {
+                #[cfg(feature = "paint_asserts")]
+                debug_assert!(false, "Triangles painted with a different texture");
+            }
         }
         PaintCmd::LineSegment { points, style } => {
             path.add_line_segment(points);
@@ -623,10 +683,51 @@ pub fn paint_command_into_triangles(
             if let Some(fill) = fill {
                 fill_closed_path(out, options, &path.0, fill);
             }
-            if let Some(outline) = outline {
+            if let Some(mut outline) = outline {
+                if outline.alignment != StrokeAlignment::Center {
+                    let pixels_per_point = options.pixels_per_point;
+                    let round_to_pixel =
+                        |point: f32| (point * pixels_per_point).round() / pixels_per_point;
+                    rect = Rect::from_min_max(
+                        pos2(round_to_pixel(rect.min.x), round_to_pixel(rect.min.y)),
+                        pos2(round_to_pixel(rect.max.x), round_to_pixel(rect.max.y)),
+                    );
+                    // Whole pixels, but at least one:
+                    outline.width =
+                        (outline.width * pixels_per_point).round().max(1.0) / pixels_per_point;
+                }
+                let (rect, corner_radius) = match outline.alignment {
+                    StrokeAlignment::Inside => (
+                        rect.shrink(0.5 * outline.width),
+                        (corner_radius - 0.5 * outline.width).max(0.0),
+                    ),
+                    StrokeAlignment::Center => (rect, corner_radius),
+                    StrokeAlignment::Outside => (
+                        rect.expand(0.5 * outline.width),
+                        corner_radius + 0.5 * outline.width,
+                    ),
+                };
+                path.clear();
+                path.add_rounded_rectangle(rect, corner_radius);
                 paint_path_outline(out, options, Closed, &path.0, outline);
             }
         }
+        PaintCmd::Shadow {
+            rect,
+            corner_radius,
+            blur,
+            color,
+        } => {
+            // The feathered edge of the fill goes from `rect` out to `rect.expand(blur)`:
+            let blur = blur.max(options.aa_size);
+            path.add_rounded_rectangle(rect.expand(0.5 * blur), corner_radius + 0.5 * blur);
+            let options = PaintOptions {
+                anti_alias: true,
+                aa_size: blur,
+                ..options
+            };
+            fill_closed_path(out, options, &path.0, color);
+        }
         PaintCmd::Text {
             pos,
             galley,
@@ -643,9 +744,22 @@ pub fn paint_command_into_triangles(
 
             let font = &fonts[text_style];
             let mut chars = galley.text.chars();
+            let mut char_idx = 0;
+            let mut color_spans = galley.color_spans.iter().peekable();
             for line in &galley.lines {
                 for x_offset in line.x_offsets.iter().take(line.x_offsets.len() - 1) {
                     let c = chars.next().unwrap();
+                    while let Some(span) = color_spans.peek() {
+                        if char_idx < span.char_range.end {
+                            break;
+                        }
+                        color_spans.next();
+                    }
+                    let color = match color_spans.peek() {
+                        Some(span) if span.char_range.start <= char_idx => span.color,
+                        _ => color,
+                    };
+                    char_idx += 1;
                     if let Some(glyph) = font.uv_rect(c) {
                         let mut top_left = Vertex {
                             pos: pos + glyph.offset + vec2(*x_offset, line.y_min) + text_offset,
@@ -668,20 +782,71 @@ pub fn paint_command_into_triangles(
     }
 }
 
-/// Turns `PaintCmd`:s into sets of triangles
+fn is_nan_pos(pos: Pos2) -> bool {
+    pos.x.is_nan() || pos.y.is_nan()
+}
+
+fn is_degenerate_rect(rect: Rect) -> bool {
+    is_nan_pos(rect.min) || is_nan_pos(rect.max) || rect.is_empty()
+}
+
+/// Commands with NaN coordinates or negative sizes would only produce garbage triangles.
+/// These usually come from doing layout math on empty content.
+/// Infinite coordinates are fine: they are clamped when tessellated.
+pub fn is_degenerate(cmd: &PaintCmd) -> bool {
+    match cmd {
+        PaintCmd::Circle { center, radius, .. } => {
+            is_nan_pos(*center) || radius.is_nan() || *radius < 0.0
+        }
+        PaintCmd::LineSegment { points, .. } => points.iter().copied().any(is_nan_pos),
+        PaintCmd::Path { path, .. } => path.0.iter().any(|p| is_nan_pos(p.pos)),
+        PaintCmd::Rect { rect, .. } => is_degenerate_rect(*rect),
+        PaintCmd::Shadow { rect, blur, .. } => is_degenerate_rect(*rect) || blur.is_nan(),
+        PaintCmd::Text { pos, .. } => is_nan_pos(*pos),
+        PaintCmd::Triangles(triangles) => triangles.vertices.iter().any(|v| is_nan_pos(v.pos)),
+    }
+}
+
+/// Should this command be tessellated at all?
+/// Returns `false` for degenerate commands and for commands clipped to nothing.
+pub fn is_paintable(clip_rect: Rect, cmd: &PaintCmd) -> bool {
+    #[cfg(feature = "paint_asserts")]
+    debug_assert!(!is_degenerate(cmd), "Degenerate paint command: {:?}", cmd);
+
+    !is_degenerate_rect(clip_rect) && !is_degenerate(cmd)
+}
+
+/// Turns `PaintCmd`:s into sets of triangles.
+/// Commands that are not `is_paintable` are skipped, and counted in the returned `usize`.
 pub fn paint_commands_into_triangles(
     options: PaintOptions,
     fonts: &Fonts,
     commands: Vec<(Rect, PaintCmd)>,
-) -> Vec<(Rect, Triangles)> {
+) -> (PaintBatches, usize) {
     let mut reused_path = Path::default();
 
     let mut batches = PaintBatches::default();
+    let mut num_rejected = 0;
     for (clip_rect, cmd) in commands {
         // TODO: cull(clip_rect, cmd)
+        if !is_paintable(clip_rect, &cmd) {
+            num_rejected += 1;
+            continue;
+        }
 
-        if batches.is_empty() || batches.last().unwrap().0 != clip_rect {
-            batches.push((clip_rect, Triangles::default()));
+        let texture_id = match &cmd {
+            PaintCmd::Triangles(triangles) => triangles.texture_id,
+            _ => TextureId::Egui,
+        };
+
+        let start_new_batch = match batches.last() {
+            Some((last_clip_rect, last_triangles)) => {
+                *last_clip_rect != clip_rect || last_triangles.texture_id != texture_id
+            }
+            None => true,
+        };
+        if start_new_batch {
+            batches.push((clip_rect, Triangles::with_texture(texture_id)));
         }
 
         let out = &mut batches.last_mut().unwrap().1;
@@ -690,6 +855,9 @@ pub fn paint_commands_into_triangles(
 
     if options.debug_paint_clip_rects {
         for (clip_rect, triangles) in &mut batches {
+            if triangles.texture_id != TextureId::Egui {
+                continue; // The outline needs the font texture. The other batches will show the clip rect.
+            }
             paint_command_into_triangles(
                 &mut reused_path,
                 options,
@@ -705,5 +873,5 @@ pub fn paint_commands_into_triangles(
         }
     }
 
-    batches
+    (batches, num_rejected)
 }
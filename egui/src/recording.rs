@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use crate::*;
+
+/// Every `RawInput` of a session, in order, for reproducing bugs and testing interactions.
+///
+/// Call `record` with the input of each frame (before giving it to `Context::begin_frame`),
+/// save it (with the `with_serde` feature), and later `play` it back into a fresh `Context`.
+/// Since the input includes `RawInput::time`, the playback is deterministic.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct InputRecording {
+    pub frames: Vec<RawInput>,
+}
+
+impl InputRecording {
+    pub fn record(&mut self, raw_input: &RawInput) {
+        self.frames.push(raw_input.clone());
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Start playing back the frames from the beginning.
+    pub fn player(&self) -> InputPlayer<'_> {
+        InputPlayer {
+            recording: self,
+            next_frame: 0,
+        }
+    }
+
+    /// Run all the recorded frames through `ctx`, calling `run_ui` for each of them.
+    /// Returns the `Output` of each frame.
+    pub fn play(&self, ctx: &mut Arc<Context>, mut run_ui: impl FnMut(&mut Ui)) -> Vec<Output> {
+        let mut player = self.player();
+        let mut outputs = Vec::with_capacity(self.len());
+        while let Some(raw_input) = player.next_input() {
+            let mut frame = ctx.begin_frame(raw_input);
+            run_ui(&mut frame);
+            let (output, _paint_batches) = frame.end();
+            outputs.push(output);
+        }
+        outputs
+    }
+}
+
+/// Feeds an `InputRecording` one frame at a time, e.g. into an integration instead of the live input.
+#[derive(Clone, Debug)]
+pub struct InputPlayer<'r> {
+    recording: &'r InputRecording,
+    next_frame: usize,
+}
+
+impl<'r> InputPlayer<'r> {
+    /// The input of the next frame, or `None` when the recording is over.
+    pub fn next_input(&mut self) -> Option<RawInput> {
+        let raw_input = self.recording.frames.get(self.next_frame)?.clone();
+        self.next_frame += 1;
+        Some(raw_input)
+    }
+
+    /// Has every frame been played?
+    pub fn is_done(&self) -> bool {
+        self.next_frame >= self.recording.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replayed_click_clicks_button() {
+        let screen_size = vec2(400.0, 300.0);
+        let frame = |time: f64, mouse_pos: Pos2, mouse_down: bool| RawInput {
+            screen_size,
+            pixels_per_point: Some(1.0),
+            time,
+            mouse_pos: Some(mouse_pos),
+            mouse_down,
+            ..Default::default()
+        };
+
+        // Find out where the button goes:
+        let mut ctx = Context::new();
+        let mut guard = ctx.begin_frame(frame(0.0, pos2(-1.0, -1.0), false));
+        let button_center = guard.button("Click me").rect.center();
+        guard.end();
+
+        let mut recording = InputRecording::default();
+        recording.record(&frame(0.0, button_center, false));
+        recording.record(&frame(0.1, button_center, true));
+        recording.record(&frame(0.2, button_center, false));
+        recording.record(&frame(0.3, button_center, false));
+
+        let mut ctx = Context::new();
+        let mut clicks = 0;
+        let outputs = recording.play(&mut ctx, |ui| {
+            if ui.button("Click me").clicked {
+                clicks += 1;
+            }
+        });
+        assert_eq!(outputs.len(), recording.len());
+        assert_eq!(clicks, 1);
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "with_serde")]
+use crate::Context;
+
+/// Key under which the integrations persist `Memory`.
+pub const MEMORY_KEY: &str = "egui_memory_json";
+
+/// Key under which the integrations persist the `Style`.
+pub const STYLE_KEY: &str = "egui_style_json";
+
+/// Somewhere to persist state between runs of an app, as key-value pairs of strings.
+///
+/// The integrations implement this (e.g. with a file on desktop and `localStorage` on the web)
+/// and use it to save and restore `Memory` and `Style`, so persistence works the same everywhere.
+/// Your app can store its own state in it too.
+pub trait Storage {
+    fn get_string(&self, key: &str) -> Option<String>;
+
+    fn set_string(&mut self, key: &str, value: String);
+
+    /// Write out any changes, e.g. to disk. Some storages write on every `set_string`.
+    fn flush(&mut self);
+}
+
+/// Keeps everything in memory, with nothing to flush. Useful for tests.
+impl Storage for HashMap<String, String> {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.insert(key.to_owned(), value);
+    }
+
+    fn flush(&mut self) {}
+}
+
+/// Read a value that was stored as json with `set_value`.
+/// A value that can't be parsed (e.g. one saved by an older version of the app) counts as missing.
+#[cfg(feature = "with_serde")]
+pub fn get_value<T: serde::de::DeserializeOwned>(storage: &dyn Storage, key: &str) -> Option<T> {
+    serde_json::from_str(&storage.get_string(key)?).ok()
+}
+
+/// Store a value as json.
+#[cfg(feature = "with_serde")]
+pub fn set_value<T: serde::Serialize>(storage: &mut dyn Storage, key: &str, value: &T) {
+    if let Ok(json) = serde_json::to_string(value) {
+        storage.set_string(key, json);
+    }
+}
+
+/// Restore the `Memory` and `Style` saved with `save_memory_and_style`, if any.
+#[cfg(feature = "with_serde")]
+pub fn load_memory_and_style(ctx: &Context, storage: &dyn Storage) {
+    if let Some(memory) = get_value(storage, MEMORY_KEY) {
+        *ctx.memory() = memory;
+    }
+    if let Some(style) = get_value(storage, STYLE_KEY) {
+        ctx.set_style(style);
+    }
+}
+
+/// Save the `Memory` and `Style`. Call `storage.flush()` afterwards.
+#[cfg(feature = "with_serde")]
+pub fn save_memory_and_style(ctx: &Context, storage: &mut dyn Storage) {
+    set_value(storage, MEMORY_KEY, &*ctx.memory());
+    set_value(storage, STYLE_KEY, &ctx.style());
+}
@@ -1,6 +1,13 @@
 #![allow(clippy::if_same_then_else)]
 
-use crate::{color::*, math::*, paint::LineStyle, types::*};
+use crate::{
+    color::*,
+    input::KeyRepeat,
+    layout::Align,
+    math::*,
+    paint::{LineStyle, Shadow, StrokeAlignment, TextStyle},
+    types::*,
+};
 
 // TODO: split into Spacing and Style?
 #[derive(Clone, Debug)]
@@ -38,6 +45,9 @@ pub struct Style {
     // TODO: an WidgetStyle ?
     pub text_color: Color,
 
+    /// Emphasized text, e.g. `**bold**` in `Markdown`.
+    pub strong_text_color: Color,
+
     /// For stuff like check marks in check boxes.
     pub line_width: f32,
 
@@ -49,17 +59,45 @@ pub struct Style {
     /// e.g. the background of the slider or text edit
     pub dark_bg_color: Color,
 
+    /// The background of selected things, e.g. a selected `SelectableLabel`
+    pub selection_color: Color,
+
     pub cursor_blink_hz: f32,
     pub text_cursor_width: f32,
 
+    /// Drawn around the widget with keyboard focus (e.g. after pressing tab).
+    pub kb_focus_outline: Option<LineStyle>,
+
+    /// How held arrow keys, backspace etc repeat.
+    pub key_repeat: KeyRepeat,
+
+    /// How many points a pressed mouse must move before it starts dragging (instead of clicking).
+    pub drag_threshold: f32,
+
     // TODO: add ability to disable animations!
     /// How many seconds a typical animation should last
     pub animation_time: f32,
 
+    /// How many seconds a widget must be hovered before its tooltip is shown
+    pub tooltip_delay: f32,
+
+    /// How many seconds tooltips take to fade in. Zero for no fade.
+    pub tooltip_fade_time: f32,
+
+    /// How many seconds the toasts of `Context::notify` are shown
+    pub notification_duration: f32,
+
     pub window: Window,
 
     pub menu_bar: MenuBar,
 
+    pub scroll_area: ScrollArea,
+
+    pub group_box: GroupBox,
+
+    /// Spacing of the `small()` variants of widgets. See `with_compact_spacing`.
+    pub compact_spacing: CompactSpacing,
+
     /// Allow child widgets to be just on the border and still have an outline with some thickness
     pub clip_rect_margin: f32,
 
@@ -67,6 +105,12 @@ pub struct Style {
     // Debug rendering:
     pub debug_widget_rects: bool,
     pub debug_resize: bool,
+    /// Drag anywhere to measure distances, snapping to the edges of widgets.
+    /// The mouse button is then only used for measuring. Press escape to turn it off.
+    pub debug_measure: bool,
+    /// Show a palette for finding widgets by name. Pick one to highlight it and scroll to it.
+    /// Press escape to close it.
+    pub debug_widget_search: bool,
 }
 
 impl Default for Style {
@@ -82,18 +126,31 @@ impl Default for Style {
             resize_interact_radius_corner: 10.0,
             interact: Default::default(),
             text_color: gray(160, 255),
+            strong_text_color: WHITE,
             line_width: 1.0,
             thin_outline: LineStyle::new(0.5, GRAY),
             background_fill: gray(32, 250),
             dark_bg_color: gray(0, 140),
+            selection_color: srgba(70, 90, 160, 255),
             cursor_blink_hz: 1.0,
             text_cursor_width: 2.0,
+            kb_focus_outline: Some(LineStyle::new(1.0, LIGHT_BLUE)),
+            key_repeat: Default::default(),
+            drag_threshold: 6.0,
             animation_time: 1.0 / 15.0,
+            tooltip_delay: 0.3,
+            tooltip_fade_time: 0.1,
+            notification_duration: 4.0,
             window: Window::default(),
             menu_bar: MenuBar::default(),
+            scroll_area: ScrollArea::default(),
+            group_box: GroupBox::default(),
+            compact_spacing: CompactSpacing::default(),
             clip_rect_margin: 3.0,
             debug_widget_rects: false,
             debug_resize: false,
+            debug_measure: false,
+            debug_widget_search: false,
         }
     }
 }
@@ -104,6 +161,8 @@ pub struct Interact {
     pub active: WidgetStyle,
     pub hovered: WidgetStyle,
     pub inactive: WidgetStyle,
+    /// Every widget in a disabled `Ui` looks like this. See `Ui::set_enabled`.
+    pub disabled: WidgetStyle,
 }
 
 impl Default for Interact {
@@ -114,7 +173,7 @@ impl Default for Interact {
                 fill: srgba(120, 120, 200, 255),
                 stroke_color: WHITE,
                 stroke_width: 2.0,
-                rect_outline: Some(LineStyle::new(2.0, WHITE)),
+                rect_outline: Some(LineStyle::new(2.0, WHITE).alignment(StrokeAlignment::Inside)),
                 corner_radius: 0.0,
             },
             hovered: WidgetStyle {
@@ -122,7 +181,7 @@ impl Default for Interact {
                 fill: srgba(100, 100, 150, 255),
                 stroke_color: gray(240, 255),
                 stroke_width: 1.5,
-                rect_outline: Some(LineStyle::new(1.0, WHITE)),
+                rect_outline: Some(LineStyle::new(1.0, WHITE).alignment(StrokeAlignment::Inside)),
                 corner_radius: 2.0,
             },
             inactive: WidgetStyle {
@@ -130,7 +189,19 @@ impl Default for Interact {
                 fill: srgba(60, 60, 80, 255),
                 stroke_color: gray(210, 255), // Mustn't look grayed out!
                 stroke_width: 1.0,
-                rect_outline: Some(LineStyle::new(1.0, white(128))),
+                rect_outline: Some(
+                    LineStyle::new(1.0, white(128)).alignment(StrokeAlignment::Inside),
+                ),
+                corner_radius: 4.0,
+            },
+            disabled: WidgetStyle {
+                bg_fill: None,
+                fill: srgba(50, 50, 60, 255),
+                stroke_color: gray(100, 255),
+                stroke_width: 1.0,
+                rect_outline: Some(
+                    LineStyle::new(1.0, white(48)).alignment(StrokeAlignment::Inside),
+                ),
                 corner_radius: 4.0,
             },
         }
@@ -177,12 +248,37 @@ pub struct WidgetStyle {
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Window {
     pub corner_radius: f32,
+
+    /// Height of the title bar. `None` means the height of the title text.
+    pub title_bar_height: Option<f32>,
+
+    pub title_text_style: TextStyle,
+
+    /// Where in the title bar the title goes: `Align::Min` is next to the collapse button,
+    /// `Align::Center` in the middle of the window.
+    pub title_align: Align,
+
+    /// Opacity of a window while it is being dragged, so you can see what is below it.
+    /// 1.0 turns this off.
+    pub drag_opacity: f32,
+
+    /// Under windows and popups, to set them apart from what is behind them.
+    pub shadow: Option<Shadow>,
 }
 
 impl Default for Window {
     fn default() -> Self {
         Self {
             corner_radius: 10.0,
+            title_bar_height: None,
+            title_text_style: TextStyle::Heading,
+            title_align: Align::Min,
+            drag_opacity: 1.0,
+            shadow: Some(Shadow {
+                offset: vec2(2.0, 4.0),
+                blur: 16.0,
+                color: black(96),
+            }),
         }
     }
 }
@@ -199,7 +295,107 @@ impl Default for MenuBar {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ScrollArea {
+    /// If true, clicking the scroll bar outside the handle scrolls a page towards the click
+    /// (repeatedly, while held). If false, it jumps straight to where you clicked.
+    pub page_on_track_click: bool,
+
+    /// Seconds the scroll bar must be held before paging repeats.
+    pub page_repeat_delay: f32,
+
+    /// Seconds between repeated pages.
+    pub page_repeat_interval: f32,
+
+    /// Drag anywhere in the contents with the middle mouse button to scroll.
+    pub middle_drag_pans: bool,
+
+    /// How far one line of mouse wheel scrolling goes, in points.
+    /// `None` means the height of a line of `TextStyle::Body` text.
+    pub points_per_line: Option<f32>,
+
+    /// How many lines one notch of the mouse wheel scrolls.
+    pub lines_per_notch: f32,
+
+    /// Multiplies pixel-precise scrolling (e.g. from a trackpad), which is already in points.
+    pub precise_scroll_speed: f32,
+}
+
+impl Default for ScrollArea {
+    fn default() -> Self {
+        Self {
+            page_on_track_click: true,
+            page_repeat_delay: 0.4,
+            page_repeat_interval: 0.05,
+            middle_drag_pans: true,
+            points_per_line: None,
+            lines_per_notch: 3.0,
+            precise_scroll_speed: 1.0,
+        }
+    }
+}
+
+/// The look of a `GroupBox` (see `Frame::group`).
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GroupBox {
+    /// Between the border and the contents, on each side.
+    pub margin: Vec2,
+    pub corner_radius: f32,
+    pub fill: Option<Color>,
+    pub outline: Option<LineStyle>,
+}
+
+impl Default for GroupBox {
+    fn default() -> Self {
+        Self {
+            margin: vec2(6.0, 4.0),
+            corner_radius: 4.0,
+            fill: None,
+            outline: Some(LineStyle::new(1.0, white(64))),
+        }
+    }
+}
+
+/// Tighter spacing, for dense property panels.
+/// Used by the `small()` variants of widgets (e.g. `Button::small`),
+/// together with `TextStyle::Small`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CompactSpacing {
+    pub button_padding: Vec2,
+    pub clickable_diameter: f32,
+    pub start_icon_width: f32,
+}
+
+impl Default for CompactSpacing {
+    fn default() -> Self {
+        Self {
+            button_padding: vec2(3.0, 1.0),
+            clickable_diameter: 16.0,
+            start_icon_width: 11.0,
+        }
+    }
+}
+
 impl Style {
+    /// This style, but with the spacing of `compact_spacing`.
+    /// Also useful for a whole dense panel: `ui.set_style(ui.style().with_compact_spacing())`.
+    pub fn with_compact_spacing(&self) -> Style {
+        let CompactSpacing {
+            button_padding,
+            clickable_diameter,
+            start_icon_width,
+        } = self.compact_spacing;
+        Style {
+            button_padding,
+            clickable_diameter,
+            start_icon_width,
+            ..self.clone()
+        }
+    }
+
     /// Use this style for interactive things
     pub fn interact(&self, interact: &InteractInfo) -> &WidgetStyle {
         self.interact.style(interact)
@@ -213,7 +409,7 @@ impl Style {
             vec2(box_side, box_side),
         );
 
-        let small_rect_side = 8.0; // TODO: make a parameter
+        let small_rect_side = (0.6 * box_side).round(); // TODO: make a parameter
         let small_icon_rect =
             Rect::from_center_size(big_icon_rect.center(), Vec2::splat(small_rect_side));
 
@@ -222,6 +418,16 @@ impl Style {
 }
 
 impl Style {
+    /// Make everything look like `interact.disabled`, with text in its stroke color.
+    pub(crate) fn fade_out(&mut self) {
+        let disabled = self.interact.disabled;
+        self.interact.active = disabled;
+        self.interact.hovered = disabled;
+        self.interact.inactive = disabled;
+        self.text_color = disabled.stroke_color;
+        self.strong_text_color = disabled.stroke_color;
+    }
+
     #[rustfmt::skip]
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         use crate::{widgets::*};
@@ -231,6 +437,8 @@ impl Style {
 
         ui.add(Checkbox::new(&mut self.debug_widget_rects, "Paint debug rectangles around widgets"));
         ui.add(Checkbox::new(&mut self.debug_resize, "Debug Resize"));
+        ui.add(Checkbox::new(&mut self.debug_measure, "Measure tool (escape to exit)"));
+        ui.add(Checkbox::new(&mut self.debug_widget_search, "Widget search (escape to exit)"));
 
         ui.add(Slider::f32(&mut self.item_spacing.x,     0.0..=10.0).text("item_spacing.x").precision(0));
         ui.add(Slider::f32(&mut self.item_spacing.y,     0.0..=10.0).text("item_spacing.y").precision(0));
@@ -241,7 +449,46 @@ impl Style {
         ui.add(Slider::f32(&mut self.button_padding.y,   0.0..=20.0).text("button_padding.y").precision(0));
         ui.add(Slider::f32(&mut self.clickable_diameter, 0.0..=60.0).text("clickable_diameter").precision(0));
         ui.add(Slider::f32(&mut self.start_icon_width,   0.0..=60.0).text("start_icon_width").precision(0));
+        ui.add(Slider::f32(&mut self.compact_spacing.button_padding.x,   0.0..=20.0).text("compact button_padding.x").precision(0));
+        ui.add(Slider::f32(&mut self.compact_spacing.button_padding.y,   0.0..=20.0).text("compact button_padding.y").precision(0));
+        ui.add(Slider::f32(&mut self.compact_spacing.clickable_diameter, 0.0..=60.0).text("compact clickable_diameter").precision(0));
+        ui.add(Slider::f32(&mut self.compact_spacing.start_icon_width,   0.0..=60.0).text("compact start_icon_width").precision(0));
         ui.add(Slider::f32(&mut self.line_width,         0.0..=10.0).text("line_width").precision(1));
         ui.add(Slider::f32(&mut self.animation_time,     0.0..=1.0).text("animation_time").precision(2));
+        ui.add(Slider::f32(&mut self.tooltip_delay,      0.0..=2.0).text("tooltip_delay").precision(2));
+        ui.add(Slider::f32(&mut self.tooltip_fade_time,  0.0..=1.0).text("tooltip_fade_time").precision(2));
+        ui.add(Slider::f32(&mut self.notification_duration, 0.5..=20.0).text("notification_duration").precision(1));
+        ui.add(Slider::f32(&mut self.key_repeat.delay,      0.1..=2.0).text("key repeat delay").precision(2));
+        ui.add(Slider::f32(&mut self.key_repeat.interval,   0.01..=0.5).text("key repeat interval").precision(3));
+        ui.add(Slider::f32(&mut self.drag_threshold,     0.0..=20.0).text("drag_threshold").precision(1));
+
+        ui.horizontal(|ui| {
+            ui.label("Window titles:");
+            ui.radio_value(&mut self.window.title_align, Align::Min, "left");
+            ui.radio_value(&mut self.window.title_align, Align::Center, "centered");
+            ui.radio_value(&mut self.window.title_text_style, TextStyle::Heading, "heading");
+            ui.radio_value(&mut self.window.title_text_style, TextStyle::Button, "button");
+        });
+        let mut custom_title_bar_height = self.window.title_bar_height.is_some();
+        ui.add(Checkbox::new(&mut custom_title_bar_height, "Custom title bar height"));
+        if custom_title_bar_height {
+            let height = self.window.title_bar_height.get_or_insert(24.0);
+            ui.add(Slider::f32(height, 8.0..=60.0).text("title_bar_height").precision(0));
+        } else {
+            self.window.title_bar_height = None;
+        }
+        ui.add(Slider::f32(&mut self.window.drag_opacity, 0.0..=1.0).text("window drag_opacity").precision(2));
+        if let Some(shadow) = &mut self.window.shadow {
+            ui.add(Slider::f32(&mut shadow.blur,     0.0..=64.0).text("window shadow blur").precision(0));
+            ui.add(Slider::f32(&mut shadow.offset.x, -20.0..=20.0).text("window shadow offset.x").precision(0));
+            ui.add(Slider::f32(&mut shadow.offset.y, -20.0..=20.0).text("window shadow offset.y").precision(0));
+        }
+
+        ui.add(Slider::f32(&mut self.group_box.corner_radius, 0.0..=20.0).text("group box corner_radius").precision(0));
+
+        ui.add(Checkbox::new(&mut self.scroll_area.page_on_track_click, "Click the scroll bar to scroll a page"));
+        ui.add(Checkbox::new(&mut self.scroll_area.middle_drag_pans, "Drag with the middle mouse button to scroll"));
+        ui.add(Slider::f32(&mut self.scroll_area.lines_per_notch,      0.5..=10.0).text("lines scrolled per mouse wheel notch").precision(1));
+        ui.add(Slider::f32(&mut self.scroll_area.precise_scroll_speed, 0.1..=5.0).text("trackpad scroll speed").precision(1));
     }
 }
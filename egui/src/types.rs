@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use crate::{math::Rect, Context, Ui};
+use crate::{
+    math::{Pos2, Rect, Vec2},
+    Context, Id, Ui,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -14,6 +17,16 @@ pub struct Output {
 
     /// Response to Event::Copy or Event::Cut. Ignore if empty.
     pub copied_text: String,
+
+    /// Where the text cursor of the text widget with keyboard focus is (its bottom),
+    /// e.g. for placing the candidate window of an IME. `None` if no text has focus.
+    pub text_cursor: Option<Pos2>,
+
+    /// See `Context::wants_pointer_input`.
+    pub wants_pointer_input: bool,
+
+    /// See `Context::wants_keyboard_input`.
+    pub wants_keyboard_input: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -28,6 +41,10 @@ pub enum CursorIcon {
     ResizeNwSe,
     ResizeVertical,
     Text,
+    /// Over something you can drag around, e.g. to pan an `ImageInspector`
+    Grab,
+    /// While dragging it
+    Grabbing,
 }
 
 impl Default for CursorIcon {
@@ -45,13 +62,62 @@ pub struct InteractInfo {
     pub hovered: bool,
 
     /// The mouse pressed this thing ealier, and now released on this thing too.
+    /// Or it has keyboard focus and return or space was pressed.
     pub clicked: bool,
 
     pub double_clicked: bool,
 
+    /// The third click in quick succession. Also `double_clicked`.
+    pub triple_clicked: bool,
+
     /// The mouse is interacting with this thing (e.g. dragging it or holding it)
     pub active: bool,
 
+    /// The secondary (usually right) mouse button was pressed and released on this thing.
+    /// Only for `Sense::secondary_click`.
+    pub secondary_clicked: bool,
+
+    /// How much the user scrolled on this thing this frame.
+    /// Only for `Sense::scroll`.
+    pub scroll_delta: Vec2,
+
+    /// How much the user zoomed on this thing this frame, as a factor (1.0 = no zoom).
+    /// Only for `Sense::zoom`.
+    pub zoom_delta: f32,
+
+    /// The value of the widget (e.g. a slider or text edit) was edited this frame.
+    /// Use this for live previews.
+    pub changed: bool,
+
+    /// The user finished editing the value this frame: they released the drag,
+    /// pressed enter or moved the keyboard focus away.
+    /// The value may or may not have changed since the edit started.
+    /// Use this e.g. to push undo entries or start expensive recomputations.
+    pub committed: bool,
+
+    /// This has keyboard focus, e.g. after tabbing to it. See `Ui::request_kb_focus`.
+    pub has_kb_focus: bool,
+
+    /// The mouse pressed this thing and has now moved more than `Style::drag_threshold`.
+    /// Only for `Sense::drag`.
+    pub drag_started: bool,
+
+    /// The mouse stopped dragging this thing this frame. Only for `Sense::drag`.
+    pub drag_released: bool,
+
+    /// How much the mouse moved this frame while dragging this thing, in points.
+    pub drag_delta: Vec2,
+
+    /// Where the mouse was pressed to start dragging this thing, while dragged.
+    pub drag_origin: Option<Pos2>,
+
+    /// Where the mouse is, if it is hovering this thing.
+    pub hover_pos: Option<Pos2>,
+
+    /// Where the mouse is while interacting with this thing (pressing or dragging it),
+    /// even when that is outside of the `rect`.
+    pub interact_pointer_pos: Option<Pos2>,
+
     /// The region of the screen we are talking about
     pub rect: Rect,
 }
@@ -62,7 +128,20 @@ impl InteractInfo {
             hovered: false,
             clicked: false,
             double_clicked: false,
+            triple_clicked: false,
             active: false,
+            secondary_clicked: false,
+            scroll_delta: Vec2::zero(),
+            zoom_delta: 1.0,
+            changed: false,
+            committed: false,
+            has_kb_focus: false,
+            drag_started: false,
+            drag_released: false,
+            drag_delta: Vec2::zero(),
+            drag_origin: None,
+            hover_pos: None,
+            interact_pointer_pos: None,
             rect: Rect::nothing(),
         }
     }
@@ -72,10 +151,33 @@ impl InteractInfo {
             hovered: self.hovered || other.hovered,
             clicked: self.clicked || other.clicked,
             double_clicked: self.double_clicked || other.double_clicked,
+            triple_clicked: self.triple_clicked || other.triple_clicked,
             active: self.active || other.active,
+            secondary_clicked: self.secondary_clicked || other.secondary_clicked,
+            scroll_delta: self.scroll_delta + other.scroll_delta,
+            zoom_delta: self.zoom_delta * other.zoom_delta,
+            changed: self.changed || other.changed,
+            committed: self.committed || other.committed,
+            has_kb_focus: self.has_kb_focus || other.has_kb_focus,
+            drag_started: self.drag_started || other.drag_started,
+            drag_released: self.drag_released || other.drag_released,
+            drag_delta: self.drag_delta + other.drag_delta,
+            drag_origin: self.drag_origin.or(other.drag_origin),
+            hover_pos: self.hover_pos.or(other.hover_pos),
+            interact_pointer_pos: self.interact_pointer_pos.or(other.interact_pointer_pos),
             rect: self.rect.union(other.rect),
         }
     }
+
+    /// `hover_pos` relative to the top left corner of `rect`.
+    pub fn hover_pos_in_rect(&self) -> Option<Vec2> {
+        self.hover_pos.map(|pos| pos - self.rect.min)
+    }
+
+    /// `interact_pointer_pos` relative to the top left corner of `rect`.
+    pub fn interact_pointer_pos_in_rect(&self) -> Option<Vec2> {
+        self.interact_pointer_pos.map(|pos| pos - self.rect.min)
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -86,13 +188,52 @@ pub struct GuiResponse {
     pub hovered: bool,
 
     /// The mouse clicked this thing this frame
+    /// (or it has keyboard focus and return or space was pressed)
     pub clicked: bool,
 
     pub double_clicked: bool,
 
+    pub triple_clicked: bool,
+
     /// The mouse is interacting with this thing (e.g. dragging it)
     pub active: bool,
 
+    /// The secondary (usually right) mouse button clicked this thing this frame
+    pub secondary_clicked: bool,
+
+    /// How much the user scrolled on this thing this frame
+    pub scroll_delta: Vec2,
+
+    /// How much the user zoomed on this thing this frame (1.0 = no zoom)
+    pub zoom_delta: f32,
+
+    /// The value of this thing was edited this frame
+    pub changed: bool,
+
+    /// The user finished editing the value of this thing this frame (see `InteractInfo::committed`)
+    pub committed: bool,
+
+    /// This has keyboard focus
+    pub has_kb_focus: bool,
+
+    /// The mouse started dragging this thing this frame
+    pub drag_started: bool,
+
+    /// The mouse stopped dragging this thing this frame
+    pub drag_released: bool,
+
+    /// How much the mouse moved this frame while dragging this thing
+    pub drag_delta: Vec2,
+
+    /// Where the drag of this thing started, while dragged
+    pub drag_origin: Option<Pos2>,
+
+    /// Where the mouse is, if it is hovering this thing
+    pub hover_pos: Option<Pos2>,
+
+    /// Where the mouse is while interacting with this thing
+    pub interact_pointer_pos: Option<Pos2>,
+
     /// The area of the screen we are talking about
     pub rect: Rect,
 
@@ -101,20 +242,42 @@ pub struct GuiResponse {
 }
 
 impl GuiResponse {
-    /// Show some stuff if the item was hovered
-    pub fn tooltip(&mut self, add_contents: impl FnOnce(&mut Ui)) -> &mut Self {
+    /// Show a tooltip with any widgets in it (images, several labels, ...) if the item was hovered.
+    /// The tooltip is identified by the position of the item.
+    pub fn on_hover_ui(&mut self, add_contents: impl FnOnce(&mut Ui)) -> &mut Self {
         if self.hovered {
-            crate::containers::show_tooltip(&self.ctx, add_contents);
+            let widget_id = Id::new("tooltip").with(Id::from_pos(self.rect.min));
+            crate::containers::show_tooltip_for(&self.ctx, widget_id, add_contents);
         }
         self
     }
 
+    /// Same as `on_hover_ui`.
+    pub fn tooltip(&mut self, add_contents: impl FnOnce(&mut Ui)) -> &mut Self {
+        self.on_hover_ui(add_contents)
+    }
+
     /// Show this text if the item was hovered
     pub fn tooltip_text(&mut self, text: impl Into<String>) -> &mut Self {
-        self.tooltip(|popup| {
+        self.on_hover_ui(|popup| {
             popup.add(crate::widgets::Label::new(text));
         })
     }
+
+    /// Next frame, scroll any `ScrollArea` this is in so that it becomes visible.
+    /// E.g. to jump to the selected item in a long list.
+    pub fn scroll_to_me(&self) {
+        self.ctx.memory().scroll_to(self.rect);
+    }
+
+    /// Show a menu at the mouse position when the item is right-clicked.
+    /// The menu is identified by the position of the item,
+    /// so it closes if the item moves (e.g. is scrolled).
+    pub fn context_menu(&mut self, add_contents: impl FnOnce(&mut Ui)) -> &mut Self {
+        let popup_id = Id::new("context_menu").with(Id::from_pos(self.rect.min));
+        crate::containers::context_menu(&self.ctx, popup_id, self.hovered, add_contents);
+        self
+    }
 }
 
 impl Into<InteractInfo> for GuiResponse {
@@ -123,7 +286,20 @@ impl Into<InteractInfo> for GuiResponse {
             hovered: self.hovered,
             clicked: self.clicked,
             double_clicked: self.double_clicked,
+            triple_clicked: self.triple_clicked,
             active: self.active,
+            secondary_clicked: self.secondary_clicked,
+            scroll_delta: self.scroll_delta,
+            zoom_delta: self.zoom_delta,
+            changed: self.changed,
+            committed: self.committed,
+            has_kb_focus: self.has_kb_focus,
+            drag_started: self.drag_started,
+            drag_released: self.drag_released,
+            drag_delta: self.drag_delta,
+            drag_origin: self.drag_origin,
+            hover_pos: self.hover_pos,
+            interact_pointer_pos: self.interact_pointer_pos,
             rect: self.rect,
         }
     }
@@ -132,6 +308,8 @@ impl Into<InteractInfo> for GuiResponse {
 // ----------------------------------------------------------------------------
 
 /// What sort of interaction is a widget sensitive to?
+///
+/// Combine them with `union`, e.g. `Sense::click().union(Sense::secondary_click())`.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Sense {
     /// buttons, sliders, windows ...
@@ -139,6 +317,15 @@ pub struct Sense {
 
     /// sliders, windows, scroll bars, scroll areas ...
     pub drag: bool,
+
+    /// scroll areas, zoomable canvases ...
+    pub scroll: bool,
+
+    /// pinch zooming of canvases and plots
+    pub zoom: bool,
+
+    /// context menus ...
+    pub secondary_click: bool,
 }
 
 impl Sense {
@@ -146,20 +333,24 @@ impl Sense {
         Self {
             click: false,
             drag: false,
+            scroll: false,
+            zoom: false,
+            secondary_click: false,
         }
     }
 
+    /// Also clicked by return or space while it has keyboard focus (see `Ui::request_kb_focus`).
     pub fn click() -> Self {
         Self {
             click: true,
-            drag: false,
+            ..Self::nothing()
         }
     }
 
     pub fn drag() -> Self {
         Self {
-            click: false,
             drag: true,
+            ..Self::nothing()
         }
     }
 
@@ -168,6 +359,44 @@ impl Sense {
         Self {
             click: true,
             drag: true,
+            ..Self::nothing()
+        }
+    }
+
+    /// The mouse wheel (or two-finger scrolling on a touchpad).
+    /// Only the first widget under the mouse that is interested in scrolling gets it.
+    pub fn scroll() -> Self {
+        Self {
+            scroll: true,
+            ..Self::nothing()
+        }
+    }
+
+    /// Pinch zooming.
+    /// Only the first widget under the mouse that is interested in zooming gets it.
+    pub fn zoom() -> Self {
+        Self {
+            zoom: true,
+            ..Self::nothing()
+        }
+    }
+
+    /// The secondary (usually right) mouse button.
+    pub fn secondary_click() -> Self {
+        Self {
+            secondary_click: true,
+            ..Self::nothing()
+        }
+    }
+
+    /// Sensitive to anything either of them is sensitive to.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            click: self.click || other.click,
+            drag: self.drag || other.drag,
+            scroll: self.scroll || other.scroll,
+            zoom: self.zoom || other.zoom,
+            secondary_click: self.secondary_click || other.secondary_click,
         }
     }
 }
@@ -49,6 +49,9 @@ pub struct Ui {
     /// If something has already been added, this will point ot style.item_spacing beyond the latest child.
     /// The cursor can thus be style.item_spacing pixels outside of the child_bounds.
     cursor: Pos2, // TODO: move into Layout?
+
+    /// If false, the widgets in this ui ignore the mouse and keyboard. See `set_enabled`.
+    enabled: bool,
 }
 
 impl Ui {
@@ -67,6 +70,7 @@ impl Ui {
             style,
             layout: Default::default(),
             cursor: rect.min,
+            enabled: true,
         }
     }
 
@@ -85,6 +89,7 @@ impl Ui {
             style: self.style.clone(),
             layout: self.layout,
             cursor: child_rect.min,
+            enabled: self.enabled,
         }
     }
 
@@ -115,6 +120,21 @@ impl Ui {
         self.style = style
     }
 
+    /// False if this ui, or one of its parents, has been disabled with `set_enabled(false)`.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// A disabled ui (and its children) ignores the mouse and keyboard,
+    /// and its widgets are painted with `style.interact.disabled`.
+    /// A ui can't be enabled again once its parent is disabled.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled && !enabled {
+            self.enabled = false;
+            self.style.fade_out();
+        }
+    }
+
     pub fn ctx(&self) -> &Arc<Context> {
         &self.ctx
     }
@@ -266,12 +286,33 @@ impl Ui {
         self.ctx.contains_mouse(self.layer, self.clip_rect, rect)
     }
 
+    /// Give an interactive widget a human readable name (e.g. the text of a button),
+    /// so it can be found with the widget search of `Style::debug_widget_search`.
+    pub fn register_widget_label(&self, id: Id, label: &str) {
+        self.ctx.register_widget_label(id, label)
+    }
+
+    /// Always false when this `Ui` is disabled or beneath an open modal.
     pub fn has_kb_focus(&self, id: Id) -> bool {
-        self.memory().kb_focus_id == Some(id)
+        let memory = self.memory();
+        self.enabled && memory.kb_focus_id == Some(id) && !memory.is_beneath_modal(self.layer)
     }
 
     pub fn request_kb_focus(&self, id: Id) {
-        self.memory().kb_focus_id = Some(id);
+        let mut memory = self.memory();
+        if self.enabled && !memory.is_beneath_modal(self.layer) {
+            memory.kb_focus_id = Some(id);
+        }
+    }
+
+    /// Let tab and shift+tab move the keyboard focus to and from this widget.
+    /// Call each frame, before `has_kb_focus`.
+    /// Widgets with `Sense::click` (but not `drag`) are already in the tab order.
+    pub fn interested_in_kb_focus(&self, id: Id) {
+        let mut memory = self.memory();
+        if self.enabled && !memory.is_beneath_modal(self.layer) {
+            memory.interested_in_kb_focus(id);
+        }
     }
 
     // ------------------------------------------------------------------------
@@ -300,12 +341,40 @@ impl Ui {
         self.id.with(id_seed)
     }
 
+    /// Next frame, scroll any `ScrollArea` this `Ui` is in so that the current cursor
+    /// (where the next widget goes) ends up at the top (`Align::Min`), center or bottom of it.
+    pub fn scroll_to_cursor(&self, align: Align) {
+        let rect = Rect::from_min_size(self.cursor, Vec2::zero());
+        self.memory().scroll_to_align(rect, align);
+    }
+
     // ------------------------------------------------------------------------
     // Interaction
 
+    /// Only hovering is sensed while this ui is disabled.
     pub fn interact(&self, rect: Rect, id: Id, sense: Sense) -> InteractInfo {
-        self.ctx
-            .interact(self.layer, self.clip_rect, rect, Some(id), sense)
+        let sense = if self.enabled {
+            sense
+        } else {
+            Sense::nothing()
+        };
+        let info = self
+            .ctx
+            .interact(self.layer, self.clip_rect, rect, Some(id), sense);
+        if info.has_kb_focus && self.memory().menu_highlight != Some(id) {
+            if let Some(outline) = self.style().kb_focus_outline {
+                self.ctx.graphics().layer(self.layer).push((
+                    self.clip_rect,
+                    PaintCmd::Rect {
+                        rect: rect.expand(3.0),
+                        corner_radius: self.style().interact.inactive.corner_radius,
+                        fill: None,
+                        outline: Some(outline),
+                    },
+                ));
+            }
+        }
+        info
     }
 
     pub fn interact_hover(&self, rect: Rect) -> InteractInfo {
@@ -324,7 +393,20 @@ impl Ui {
             hovered: interact.hovered,
             clicked: interact.clicked,
             double_clicked: interact.double_clicked,
+            triple_clicked: interact.triple_clicked,
             active: interact.active,
+            secondary_clicked: interact.secondary_clicked,
+            scroll_delta: interact.scroll_delta,
+            zoom_delta: interact.zoom_delta,
+            changed: interact.changed,
+            committed: interact.committed,
+            has_kb_focus: interact.has_kb_focus,
+            drag_started: interact.drag_started,
+            drag_released: interact.drag_released,
+            drag_delta: interact.drag_delta,
+            drag_origin: interact.drag_origin,
+            hover_pos: interact.hover_pos,
+            interact_pointer_pos: interact.interact_pointer_pos,
             rect: interact.rect,
             ctx: self.ctx.clone(),
         }
@@ -385,6 +467,60 @@ impl Ui {
         rect
     }
 
+    /// Take a strip of this width off the left or right side of the available space,
+    /// so that the widgets added after this go beside it. Used by `SidePanel`.
+    pub(crate) fn allocate_side_strip(&mut self, on_the_left: bool, width: f32) -> Rect {
+        let available = self.available_finite();
+        let width = self.round_to_pixel(width.min(available.width()).max(0.0));
+        let spacing = self.style.item_spacing.x;
+        if on_the_left {
+            let strip = Rect::from_min_max(
+                available.min,
+                pos2(available.left() + width, available.bottom()),
+            );
+            self.cursor.x = strip.right() + spacing;
+            self.expand_to_include_child(strip);
+            strip
+        } else {
+            let strip = Rect::from_min_max(
+                pos2(available.right() - width, available.top()),
+                available.max,
+            );
+            // What is left for the widgets after this:
+            let right = (strip.left() - spacing).max(self.cursor.x);
+            self.desired_rect.max.x = right;
+            self.child_bounds.max.x = self.child_bounds.max.x.min(right);
+            strip
+        }
+    }
+
+    /// Take a strip of this height off the top or bottom of the available space,
+    /// so that the widgets added after this go above or below it. Used by `TopPanel` and `BottomPanel`.
+    pub(crate) fn allocate_top_or_bottom_strip(&mut self, at_the_top: bool, height: f32) -> Rect {
+        let available = self.available_finite();
+        let height = self.round_to_pixel(height.min(available.height()).max(0.0));
+        let spacing = self.style.item_spacing.y;
+        if at_the_top {
+            let strip = Rect::from_min_max(
+                available.min,
+                pos2(available.right(), available.top() + height),
+            );
+            self.cursor.y = strip.bottom() + spacing;
+            self.expand_to_include_child(strip);
+            strip
+        } else {
+            let strip = Rect::from_min_max(
+                pos2(available.left(), available.bottom() - height),
+                available.max,
+            );
+            // What is left for the widgets after this:
+            let bottom = (strip.top() - spacing).max(self.cursor.y);
+            self.desired_rect.max.y = bottom;
+            self.child_bounds.max.y = self.child_bounds.max.y.min(bottom);
+            strip
+        }
+    }
+
     /// Reserve this much space and move the cursor.
     /// Returns where to put the widget.
     fn reserve_space_impl(&mut self, child_size: Vec2) -> Rect {
@@ -429,6 +565,14 @@ impl Ui {
         self.ctx.graphics().layer(self.layer).len()
     }
 
+    /// Move the paint commands added since the paint list had length `start`.
+    pub fn translate_paint_cmds(&mut self, start: usize, delta: Vec2) {
+        let mut graphics = self.ctx.graphics();
+        for (_, cmd) in graphics.layer(self.layer).iter_mut().skip(start) {
+            cmd.translate(delta);
+        }
+    }
+
     /// Paint some debug text at current cursor
     pub fn debug_text(&self, text: impl Into<String>) {
         self.debug_text_at(self.cursor, text);
@@ -494,6 +638,19 @@ impl Ui {
         self.response(interact)
     }
 
+    /// Add a widget that is disabled (see `set_enabled`) unless `enabled` is true.
+    pub fn add_enabled(&mut self, enabled: bool, widget: impl Widget) -> GuiResponse {
+        if enabled || !self.enabled {
+            return self.add(widget);
+        }
+        let style = self.style.clone();
+        self.set_enabled(false);
+        let response = self.add(widget);
+        self.enabled = true;
+        self.style = style;
+        response
+    }
+
     // Convenience functions:
 
     pub fn label(&mut self, label: impl Into<Label>) -> GuiResponse {
@@ -513,11 +670,72 @@ impl Ui {
         self.add(Checkbox::new(checked, text))
     }
 
+    /// Text that toggles `selected` when clicked, and is highlighted when `selected`.
+    pub fn selectable_label(
+        &mut self,
+        text: impl Into<String>,
+        selected: &mut bool,
+    ) -> GuiResponse {
+        self.add(SelectableLabel::new(selected, text))
+    }
+
+    /// A `SelectableLabel` that is selected when `*current_value == selected_value`,
+    /// and sets `*current_value = selected_value` when clicked.
+    ///
+    /// Use one for each alternative to make e.g. a tab bar:
+    ///
+    /// ```ignore
+    /// ui.horizontal(|ui| {
+    ///     ui.selectable_value(&mut tab, Tab::Settings, "Settings");
+    ///     ui.selectable_value(&mut tab, Tab::About, "About");
+    /// });
+    /// ```
+    pub fn selectable_value<Value: PartialEq>(
+        &mut self,
+        current_value: &mut Value,
+        selected_value: Value,
+        text: impl Into<String>,
+    ) -> GuiResponse {
+        let mut selected = *current_value == selected_value;
+        let response = self.selectable_label(text, &mut selected);
+        if response.clicked {
+            *current_value = selected_value;
+        }
+        response
+    }
+
     // TODO: argument order?
     pub fn radio(&mut self, text: impl Into<String>, checked: bool) -> GuiResponse {
         self.add(RadioButton::new(checked, text))
     }
 
+    /// A radio button that is checked when `*current_value == selected_value`,
+    /// and sets `*current_value = selected_value` when clicked.
+    ///
+    /// Use one for each alternative of an enum to make a radio group:
+    ///
+    /// ```ignore
+    /// ui.radio_value(&mut my_enum, Enum::First, "First");
+    /// ui.radio_value(&mut my_enum, Enum::Second, "Second");
+    /// ```
+    pub fn radio_value<Value: PartialEq>(
+        &mut self,
+        current_value: &mut Value,
+        selected_value: Value,
+        text: impl Into<String>,
+    ) -> GuiResponse {
+        let response = self.radio(text, *current_value == selected_value);
+        if response.clicked {
+            *current_value = selected_value;
+        }
+        response
+    }
+
+    /// A swatch with draggable R, G, B and A values and a hex field. See `ColorEdit`.
+    pub fn color_edit_button_srgba(&mut self, color: &mut Color) -> GuiResponse {
+        self.add(ColorEdit::new(color))
+    }
+
     pub fn separator(&mut self) -> GuiResponse {
         self.add(Separator::new())
     }
@@ -533,6 +751,15 @@ impl Ui {
         CollapsingHeader::new(text).show(self, add_contents)
     }
 
+    /// Show a menu at the mouse position when the user right-clicks anywhere in this ui
+    /// (i.e. in what has been added to it so far).
+    /// For a context menu on a single widget, use `GuiResponse::context_menu`.
+    pub fn context_menu(&mut self, add_contents: impl FnOnce(&mut Ui)) {
+        let hovered = self.hovered(self.rect_finite());
+        let popup_id = self.id.with("context_menu");
+        containers::context_menu(&self.ctx, popup_id, hovered, add_contents);
+    }
+
     /// Create a child ui at the current cursor.
     /// `size` is the desired size.
     /// Actual size may be much smaller if `avilable_size()` is not enough.
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use crate::{paint::*, widgets::*, *};
+
+/// At most this many widgets are listed.
+const MAX_MATCHES: usize = 20;
+
+/// For how long (in seconds) a picked widget is highlighted.
+const HIGHLIGHT_TIME: f64 = 2.0;
+
+/// The widget search of `Style::debug_widget_search`:
+/// a palette listing the interactive widgets of last frame, filtered by a fuzzy search.
+/// Picking one (with the mouse or the arrow keys and return) highlights it
+/// and scrolls any `ScrollArea` it is in to show it.
+///
+/// Widgets are listed by their label (see `Ui::register_widget_label`), or else their `Id`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct WidgetSearch {
+    query: String,
+    /// Index into the listed widgets.
+    selected: usize,
+    /// The widget picked last, and when.
+    picked: Option<(Id, f64)>,
+}
+
+/// A widget from last frame: its id, rect and label.
+pub(crate) type FoundWidget = (Id, Rect, Option<String>);
+
+impl WidgetSearch {
+    pub fn show(&mut self, ctx: &Arc<Context>, widgets: &[FoundWidget]) {
+        let area_id = Id::new("widget_search");
+        let time = ctx.input().time;
+
+        // Don't list the widgets of the palette itself:
+        let own_rect = ctx.memory().areas.get(area_id).map(|area| area.rect());
+        let mut found: Vec<(i32, Id, Rect, String)> = widgets
+            .iter()
+            .filter(|(_, rect, _)| !matches!(own_rect, Some(own) if own.contains(rect.center())))
+            .filter_map(|(id, rect, label)| {
+                let name = label.clone().unwrap_or_else(|| format!("{:?}", id));
+                fuzzy_score(&self.query, &name).map(|score| (score, *id, *rect, name))
+            })
+            .collect();
+        found.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.3.cmp(&b.3)));
+        found.truncate(MAX_MATCHES);
+        self.selected = self.selected.min(found.len().saturating_sub(1));
+
+        let mut picked = None;
+        for event in &ctx.input().events {
+            match event {
+                Event::Key {
+                    key: Key::Up,
+                    pressed: true,
+                } => self.selected = self.selected.saturating_sub(1),
+                Event::Key {
+                    key: Key::Down,
+                    pressed: true,
+                } if self.selected + 1 < found.len() => self.selected += 1,
+                Event::Key {
+                    key: Key::Return,
+                    pressed: true,
+                } => picked = Some(self.selected),
+                _ => {}
+            }
+        }
+
+        let screen = ctx.rect();
+        let width = 300.0;
+        let pos = pos2(screen.center().x - 0.5 * width, screen.top() + 40.0);
+        Area::new(area_id)
+            .order(Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_desired_width(width);
+                    ui.add(label!("Jump to widget").text_style(TextStyle::Heading));
+
+                    let query_id_source = Id::new("widget_search_query");
+                    let query_id = TextEdit::id_for(ui, query_id_source);
+                    ui.request_kb_focus(query_id);
+                    ui.add(
+                        TextEdit::new(&mut self.query)
+                            .id(query_id_source)
+                            .multiline(false),
+                    );
+
+                    for (index, (_, _, rect, name)) in found.iter().enumerate() {
+                        let mut selected = index == self.selected;
+                        let response = ui.selectable_label(name.as_str(), &mut selected);
+                        if response.clicked {
+                            picked = Some(index);
+                        }
+                        if response.hovered {
+                            ui.ctx().debug_rect(*rect, color::LIGHT_BLUE, name.as_str());
+                        }
+                    }
+                    if found.is_empty() {
+                        ui.label("No matching widgets");
+                    }
+                    ui.add(label!("Arrow keys and return to pick, escape to close").auto_shrink());
+                })
+            });
+
+        if let Some((_, id, rect, _)) = picked.and_then(|index| found.get(index)) {
+            self.picked = Some((*id, time));
+            ctx.memory().scroll_to(*rect);
+        }
+
+        if let Some((id, picked_time)) = self.picked {
+            if time - picked_time < HIGHLIGHT_TIME {
+                // Where it is now, after any scrolling:
+                if let Some((_, rect, label)) = widgets.iter().find(|widget| widget.0 == id) {
+                    let name = label.clone().unwrap_or_else(|| format!("{:?}", id));
+                    ctx.debug_rect(*rect, color::YELLOW, name);
+                }
+            } else {
+                self.picked = None;
+            }
+        }
+    }
+}
+
+/// Does `text` contain the characters of `query` in the same order (ignoring case)?
+/// If so, returns a score that is higher for consecutive characters and matches at the start.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let mut score = 0;
+    let mut start = 0;
+    let mut last_match: Option<usize> = None;
+    for c in query.chars().map(|c| c.to_ascii_lowercase()) {
+        let index = start + text[start..].iter().position(|&t| t == c)?;
+        score += match last_match {
+            Some(last) if last + 1 == index => 10,
+            _ if index == 0 => 10,
+            _ => 1,
+        };
+        last_match = Some(index);
+        start = index + 1;
+    }
+    Some(score)
+}
@@ -2,10 +2,25 @@
 
 use crate::{layout::Direction, *};
 
-mod slider;
+pub mod code_editor;
+pub mod color_edit;
+mod drag_handle;
+pub mod enum_drop_down;
+mod image;
+pub mod image_inspector;
+mod knob;
+mod markdown;
+pub mod plot;
+mod progress_bar;
+mod range_slider;
+pub mod slider;
 pub mod text_edit;
 
-pub use {paint::*, slider::*, text_edit::*};
+pub use {
+    code_editor::CodeEditor, color_edit::ColorEdit, drag_handle::*, enum_drop_down::*, image::*,
+    image_inspector::ImageInspector, knob::*, markdown::*, paint::*, plot::*, progress_bar::*,
+    range_slider::*, slider::*, text_edit::*,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -156,11 +171,12 @@ impl Widget for Hyperlink {
         let color = color::LIGHT_BLUE;
         let text_style = TextStyle::Body;
         let id = ui.make_child_id(&url);
+        ui.register_widget_label(id, &text);
         let font = &ui.fonts()[text_style];
         let galley = font.layout_multiline(text, ui.available().width());
         let rect = ui.allocate_space(galley.size);
         let interact = ui.interact(rect, id, Sense::click());
-        if interact.hovered {
+        if interact.hovered && ui.enabled() {
             ui.ctx().output().cursor_icon = CursorIcon::PointingHand;
         }
         if interact.clicked {
@@ -191,12 +207,26 @@ impl Widget for Hyperlink {
 
 // ----------------------------------------------------------------------------
 
+/// An icon shown left of the text of a `Button`.
+#[derive(Clone, Copy, Debug)]
+pub enum Icon {
+    /// A texture (e.g. one you have registered with your backend), shown at the given size.
+    Texture(TextureId, Vec2),
+    /// A glyph, in the font and color of the button text.
+    Glyph(char),
+}
+
 pub struct Button {
     text: String,
     text_color: Option<Color>,
     text_style: TextStyle,
     /// None means default for interact
     fill: Option<Color>,
+    icon: Option<Icon>,
+    /// None means `style.item_spacing.x`
+    icon_spacing: Option<f32>,
+    small: bool,
+    shortcut_text: Option<String>,
 }
 
 impl Button {
@@ -206,9 +236,18 @@ impl Button {
             text_color: None,
             text_style: TextStyle::Button,
             fill: None,
+            icon: None,
+            icon_spacing: None,
+            small: false,
+            shortcut_text: None,
         }
     }
 
+    /// A button showing only an icon, e.g. for tool bars.
+    pub fn icon_only(icon: Icon) -> Self {
+        Self::new("").icon(icon)
+    }
+
     pub fn text_color(mut self, text_color: Color) -> Self {
         self.text_color = Some(text_color);
         self
@@ -223,6 +262,32 @@ impl Button {
         self.fill = fill;
         self
     }
+
+    /// Show an icon left of the text.
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Space between the icon and the text.
+    pub fn icon_spacing(mut self, icon_spacing: f32) -> Self {
+        self.icon_spacing = Some(icon_spacing);
+        self
+    }
+
+    /// Smaller text and tighter padding (see `Style::compact_spacing`), for dense panels.
+    pub fn small(mut self) -> Self {
+        self.text_style = TextStyle::Small;
+        self.small = true;
+        self
+    }
+
+    /// Shown dimmed at the right of the button, e.g. "Ctrl+S" for a menu item.
+    /// See `Context::consume_shortcut`.
+    pub fn shortcut_text(mut self, shortcut_text: impl Into<String>) -> Self {
+        self.shortcut_text = Some(shortcut_text.into());
+        self
+    }
 }
 
 impl Widget for Button {
@@ -232,17 +297,54 @@ impl Widget for Button {
             text_color,
             text_style,
             fill,
+            icon,
+            icon_spacing,
+            small,
+            shortcut_text,
         } = self;
 
+        let style = if small {
+            ui.style().with_compact_spacing()
+        } else {
+            ui.style().clone()
+        };
         let id = ui.make_position_id();
+        ui.register_widget_label(id, &text);
         let font = &ui.fonts()[text_style];
-        let galley = font.layout_multiline(text, ui.available().width());
-        let padding = ui.style().button_padding;
-        let mut size = galley.size + 2.0 * padding;
-        size.y = size.y.max(ui.style().clickable_diameter);
+        let icon_galley = match icon {
+            Some(Icon::Glyph(c)) => Some(font.layout_single_line(c.to_string())),
+            _ => None,
+        };
+        let icon_size = match (icon, &icon_galley) {
+            (Some(Icon::Texture(_, size)), _) => size,
+            (_, Some(icon_galley)) => icon_galley.size,
+            _ => Vec2::zero(),
+        };
+        let icon_spacing = match icon {
+            Some(_) if !text.is_empty() => icon_spacing.unwrap_or(ui.style().item_spacing.x),
+            _ => 0.0,
+        };
+        let icon_width = icon_size.x + icon_spacing;
+        let shortcut_galley = shortcut_text.map(|text| font.layout_single_line(text));
+        let shortcut_width = match &shortcut_galley {
+            Some(galley) => 2.0 * ui.style().item_spacing.x + galley.size.x,
+            None => 0.0,
+        };
+
+        let galley =
+            font.layout_multiline(text, ui.available().width() - icon_width - shortcut_width);
+        let padding = style.button_padding;
+        let content_size = vec2(
+            icon_width + galley.size.x + shortcut_width,
+            icon_size.y.max(galley.size.y),
+        );
+        let mut size = content_size + 2.0 * padding;
+        size.y = size.y.max(style.clickable_diameter);
         let rect = ui.allocate_space(size);
         let interact = ui.interact(rect, id, Sense::click());
-        let text_cursor = interact.rect.left_center() + vec2(padding.x, -0.5 * galley.size.y);
+        if interact.hovered && ui.enabled() {
+            ui.output().cursor_icon = CursorIcon::PointingHand;
+        }
         let bg_fill = fill.or(ui.style().interact(&interact).bg_fill);
         ui.add_paint_cmd(PaintCmd::Rect {
             corner_radius: ui.style().interact(&interact).corner_radius,
@@ -252,7 +354,105 @@ impl Widget for Button {
         });
         let stroke_color = ui.style().interact(&interact).stroke_color;
         let text_color = text_color.unwrap_or(stroke_color);
+
+        let left_center = interact.rect.left_center() + vec2(padding.x, 0.0);
+        let icon_min = left_center - vec2(0.0, 0.5 * icon_size.y);
+        if let Some(icon_galley) = icon_galley {
+            ui.add_galley(icon_min, icon_galley, text_style, Some(text_color));
+        } else if let Some(Icon::Texture(texture_id, _)) = icon {
+            let icon_rect = Rect::from_min_size(icon_min, icon_size);
+            let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+            paint_texture(ui, icon_rect, texture_id, uv, color::WHITE);
+        }
+
+        let text_cursor = left_center + vec2(icon_width, -0.5 * galley.size.y);
         ui.add_galley(text_cursor, galley, text_style, Some(text_color));
+
+        if let Some(shortcut_galley) = shortcut_galley {
+            let pos = pos2(
+                interact.rect.right() - padding.x - shortcut_galley.size.x,
+                interact.rect.center().y - 0.5 * shortcut_galley.size.y,
+            );
+            ui.add_galley(
+                pos,
+                shortcut_galley,
+                text_style,
+                Some(text_color.multiply(0.6)),
+            );
+        }
+        interact
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Text that is highlighted when selected. Clicking it toggles the selection.
+/// Useful for lists of things to select from, and for toolbars.
+#[derive(Debug)]
+pub struct SelectableLabel<'a> {
+    selected: &'a mut bool,
+    text: String,
+    text_style: TextStyle,
+}
+
+impl<'a> SelectableLabel<'a> {
+    pub fn new(selected: &'a mut bool, text: impl Into<String>) -> Self {
+        Self {
+            selected,
+            text: text.into(),
+            text_style: TextStyle::Button,
+        }
+    }
+
+    pub fn text_style(mut self, text_style: TextStyle) -> Self {
+        self.text_style = text_style;
+        self
+    }
+}
+
+impl<'a> Widget for SelectableLabel<'a> {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let SelectableLabel {
+            selected,
+            text,
+            text_style,
+        } = self;
+
+        let id = ui.make_position_id();
+        ui.register_widget_label(id, &text);
+        let font = &ui.fonts()[text_style];
+        let galley = font.layout_multiline(text, ui.available().width());
+        let padding = ui.style().button_padding;
+        let mut size = galley.size + 2.0 * padding;
+        size.y = size.y.max(ui.style().clickable_diameter);
+        let rect = ui.allocate_space(size);
+        let interact = ui.interact(rect, id, Sense::click());
+        if interact.hovered && ui.enabled() {
+            ui.output().cursor_icon = CursorIcon::PointingHand;
+        }
+        if interact.clicked {
+            *selected = !*selected;
+        }
+
+        let style = *ui.style().interact(&interact);
+        let fill = if *selected {
+            Some(ui.style().selection_color)
+        } else if interact.hovered || interact.active {
+            Some(color::gray(128, 32))
+        } else {
+            None
+        };
+        if fill.is_some() {
+            ui.add_paint_cmd(PaintCmd::Rect {
+                corner_radius: style.corner_radius,
+                fill,
+                outline: None,
+                rect: interact.rect,
+            });
+        }
+
+        let text_cursor = interact.rect.left_center() + vec2(padding.x, -0.5 * galley.size.y);
+        ui.add_galley(text_cursor, galley, text_style, Some(style.stroke_color));
         interact
     }
 }
@@ -264,6 +464,7 @@ pub struct Checkbox<'a> {
     checked: &'a mut bool,
     text: String,
     text_color: Option<Color>,
+    small: bool,
 }
 
 impl<'a> Checkbox<'a> {
@@ -272,6 +473,7 @@ impl<'a> Checkbox<'a> {
             checked,
             text: text.into(),
             text_color: None,
+            small: false,
         }
     }
 
@@ -279,6 +481,12 @@ impl<'a> Checkbox<'a> {
         self.text_color = Some(text_color);
         self
     }
+
+    /// Smaller text and box (see `Style::compact_spacing`), for dense panels.
+    pub fn small(mut self) -> Self {
+        self.small = true;
+        self
+    }
 }
 
 impl<'a> Widget for Checkbox<'a> {
@@ -287,32 +495,41 @@ impl<'a> Widget for Checkbox<'a> {
             checked,
             text,
             text_color,
+            small,
         } = self;
 
+        let (style, text_style) = if small {
+            (ui.style().with_compact_spacing(), TextStyle::Small)
+        } else {
+            (ui.style().clone(), TextStyle::Button)
+        };
         let id = ui.make_position_id();
-        let text_style = TextStyle::Button;
+        ui.register_widget_label(id, &text);
         let font = &ui.fonts()[text_style];
         let galley = font.layout_single_line(text);
-        let size = ui.style().button_padding
-            + vec2(ui.style().start_icon_width, 0.0)
+        let size = style.button_padding
+            + vec2(style.start_icon_width, 0.0)
             + galley.size
-            + ui.style().button_padding;
+            + style.button_padding;
         let rect = ui.allocate_space(size);
         let interact = ui.interact(rect, id, Sense::click());
+        if interact.hovered && ui.enabled() {
+            ui.output().cursor_icon = CursorIcon::PointingHand;
+        }
         let text_cursor =
-            interact.rect.min + ui.style().button_padding + vec2(ui.style().start_icon_width, 0.0);
+            interact.rect.min + style.button_padding + vec2(style.start_icon_width, 0.0);
         if interact.clicked {
             *checked = !*checked;
         }
-        let (small_icon_rect, big_icon_rect) = ui.style().icon_rectangles(interact.rect);
+        let (small_icon_rect, big_icon_rect) = style.icon_rectangles(interact.rect);
         ui.add_paint_cmd(PaintCmd::Rect {
-            corner_radius: ui.style().interact(&interact).corner_radius,
-            fill: ui.style().interact(&interact).bg_fill,
-            outline: ui.style().interact(&interact).rect_outline,
+            corner_radius: style.interact(&interact).corner_radius,
+            fill: style.interact(&interact).bg_fill,
+            outline: style.interact(&interact).rect_outline,
             rect: big_icon_rect,
         });
 
-        let stroke_color = ui.style().interact(&interact).stroke_color;
+        let stroke_color = style.interact(&interact).stroke_color;
 
         if *checked {
             ui.add_paint_cmd(PaintCmd::Path {
@@ -322,7 +539,7 @@ impl<'a> Widget for Checkbox<'a> {
                     pos2(small_icon_rect.right(), small_icon_rect.top()),
                 ]),
                 closed: false,
-                outline: Some(LineStyle::new(ui.style().line_width, stroke_color)),
+                outline: Some(LineStyle::new(style.line_width, stroke_color)),
                 fill: None,
             });
         }
@@ -369,6 +586,7 @@ impl Widget for RadioButton {
             text_color,
         } = self;
         let id = ui.make_position_id();
+        ui.register_widget_label(id, &text);
         let text_style = TextStyle::Button;
         let font = &ui.fonts()[text_style];
         let galley = font.layout_multiline(text, ui.available().width());
@@ -378,6 +596,9 @@ impl Widget for RadioButton {
             + ui.style().button_padding;
         let rect = ui.allocate_space(size);
         let interact = ui.interact(rect, id, Sense::click());
+        if interact.hovered && ui.enabled() {
+            ui.output().cursor_icon = CursorIcon::PointingHand;
+        }
         let text_cursor =
             interact.rect.min + ui.style().button_padding + vec2(ui.style().start_icon_width, 0.0);
 
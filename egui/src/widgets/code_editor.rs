@@ -0,0 +1,376 @@
+use crate::{
+    containers::hover_card::CardContents,
+    paint::*,
+    widgets::text_edit::{insert_text, on_key_press},
+    *,
+};
+
+/// What the tab key inserts.
+const TAB: &str = "    ";
+
+/// Height of the horizontal scroll bar.
+const SCROLL_BAR_HEIGHT: f32 = 6.0;
+
+type Highlighter<'t> = Box<dyn Fn(&str) -> Vec<ColorSpan> + 't>;
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+pub(crate) struct State {
+    /// Charctaer based, NOT bytes.
+    pub cursor: Option<usize>,
+    /// How far the text is scrolled to the left, in points.
+    pub scroll_x: f32,
+}
+
+/// Multi-line editing of code, in monospace with line numbers.
+///
+/// Lines are never wrapped: instead the text scrolls horizontally,
+/// with the mouse wheel, the scroll bar, or by moving the cursor.
+///
+/// Colors come from an optional highlighter, which gets the whole text
+/// and returns what colors to paint parts of it with:
+///
+/// ```ignore
+/// ui.add(CodeEditor::new(&mut code).highlighter(|code| {
+///     code.match_indices("fn")
+///         .map(|(start, _)| ColorSpan {
+///             char_range: start..start + 2, // assumes ASCII
+///             color: color::LIGHT_BLUE,
+///         })
+///         .collect()
+/// }));
+/// ```
+pub struct CodeEditor<'t> {
+    text: &'t mut String,
+    id: Option<Id>,
+    highlighter: Option<Highlighter<'t>>,
+    line_numbers: bool,
+    desired_rows: usize,
+    hover_cards: Vec<(String, CardContents<'t>)>,
+}
+
+impl<'t> std::fmt::Debug for CodeEditor<'t> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CodeEditor")
+            .field("text", &self.text)
+            .field("id", &self.id)
+            .field("highlighter", &self.highlighter.is_some())
+            .field("line_numbers", &self.line_numbers)
+            .field("desired_rows", &self.desired_rows)
+            .field(
+                "hover_cards",
+                &self
+                    .hover_cards
+                    .iter()
+                    .map(|(symbol, _)| symbol)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<'t> CodeEditor<'t> {
+    pub fn new(text: &'t mut String) -> Self {
+        Self {
+            text,
+            id: None,
+            highlighter: None,
+            line_numbers: true,
+            desired_rows: 8,
+            hover_cards: vec![],
+        }
+    }
+
+    pub fn id(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id = Some(Id::new(id_source));
+        self
+    }
+
+    /// Called with the whole text each frame.
+    /// The spans are in characters (NOT bytes) and may be returned in any order,
+    /// but must not overlap.
+    pub fn highlighter(mut self, highlighter: impl Fn(&str) -> Vec<ColorSpan> + 't) -> Self {
+        self.highlighter = Some(Box::new(highlighter));
+        self
+    }
+
+    /// Show line numbers to the left of the code. On by default.
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// The editor is at least this many lines high, and grows to fit longer texts.
+    pub fn desired_rows(mut self, desired_rows: usize) -> Self {
+        self.desired_rows = desired_rows;
+        self
+    }
+
+    /// Show a `HoverCard` with `add_contents` when the identifier `symbol` is hovered,
+    /// e.g. with the documentation of a function.
+    pub fn hover_card(
+        mut self,
+        symbol: impl Into<String>,
+        add_contents: impl Fn(&mut Ui) + 't,
+    ) -> Self {
+        self.hover_cards
+            .push((symbol.into(), Box::new(add_contents)));
+        self
+    }
+}
+
+impl<'t> Widget for CodeEditor<'t> {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let CodeEditor {
+            text,
+            id,
+            highlighter,
+            line_numbers,
+            desired_rows,
+            hover_cards,
+        } = self;
+
+        let id = ui.make_child_id(id);
+        let mut state = ui
+            .memory()
+            .code_editors
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+
+        let text_style = TextStyle::Monospace;
+        let font = &ui.fonts()[text_style];
+        let line_spacing = font.line_spacing();
+        let mut galley = font.layout_multiline(text.clone(), f32::INFINITY);
+
+        let gutter_width = if line_numbers {
+            let widest_number = galley.lines.len().to_string();
+            font.layout_single_line(widest_number).size.x + 8.0
+        } else {
+            0.0
+        };
+
+        // Based on last frame's text, but that is good enough for the scroll bar.
+        let width = ui.available().width();
+        let text_width = width - gutter_width;
+        let has_scroll_bar = galley.size.x > text_width;
+        let text_height = galley.size.y.max(desired_rows as f32 * line_spacing);
+        let scroll_bar_height = if has_scroll_bar {
+            SCROLL_BAR_HEIGHT
+        } else {
+            0.0
+        };
+
+        let rect = ui.allocate_space(vec2(width, text_height + scroll_bar_height));
+        let text_rect = Rect::from_min_size(
+            pos2(rect.left() + gutter_width, rect.top()),
+            vec2(text_width, text_height),
+        );
+        let interact = ui.interact(text_rect, id, Sense::click_and_drag());
+        let where_to_put_background = ui.paint_list_len();
+
+        if interact.clicked {
+            ui.request_kb_focus(id);
+            if let Some(mouse_pos) = ui.input().mouse.pos {
+                let galley_pos = text_rect.min - vec2(state.scroll_x, 0.0);
+                state.cursor = Some(galley.char_at(mouse_pos - galley_pos).char_idx);
+            }
+        }
+        if interact.hovered {
+            ui.output().cursor_icon = CursorIcon::Text;
+        }
+        if ui.contains_mouse(text_rect) {
+            state.scroll_x -= ui.input().scroll_delta.x;
+        }
+
+        let has_kb_focus = ui.has_kb_focus(id);
+        let mut cursor_moved = false;
+
+        if has_kb_focus {
+            let mut cursor = state.cursor.unwrap_or_else(|| text.chars().count());
+            cursor = clamp(cursor, 0..=text.chars().count());
+
+            for event in &ui.input().events {
+                match event {
+                    Event::Copy => {
+                        ui.ctx().output().copied_text = text.clone();
+                    }
+                    // There is no selection to cut yet, and cutting everything would be too easy by mistake.
+                    Event::Cut => {}
+                    Event::Text(text_to_insert) | Event::Paste(text_to_insert) => {
+                        insert_text(&mut cursor, text, text_to_insert);
+                        cursor_moved = true;
+                    }
+                    Event::Key {
+                        key: Key::Tab,
+                        pressed: true,
+                    } => {
+                        insert_text(&mut cursor, text, TAB);
+                        cursor_moved = true;
+                    }
+                    Event::Key { key, pressed: true } => {
+                        on_key_press(&mut cursor, text, *key);
+                        cursor_moved = true;
+                    }
+                    _ => {}
+                }
+            }
+            state.cursor = Some(cursor);
+
+            // layout again to avoid frame delay:
+            galley = ui.fonts()[text_style].layout_multiline(text.clone(), f32::INFINITY);
+        }
+
+        if let Some(highlighter) = highlighter {
+            let mut color_spans = highlighter(text);
+            color_spans.sort_by_key(|span| span.char_range.start);
+            galley.color_spans = color_spans;
+        }
+
+        // Keep the cursor in view while typing:
+        let cursor_width = ui.style().text_cursor_width;
+        if let Some(cursor) = state.cursor.filter(|_| cursor_moved) {
+            let margin = 4.0 * cursor_width;
+            let cursor_x = galley.char_start_pos(cursor).x;
+            if cursor_x - state.scroll_x > text_width - margin {
+                state.scroll_x = cursor_x - text_width + margin;
+            } else if cursor_x - state.scroll_x < 0.0 {
+                state.scroll_x = cursor_x - margin;
+            }
+        }
+        let max_scroll_x = (galley.size.x + cursor_width - text_width).max(0.0);
+
+        if has_scroll_bar {
+            let bar_rect = Rect::from_min_max(
+                pos2(text_rect.left(), text_rect.bottom()),
+                pos2(text_rect.right(), rect.bottom()),
+            );
+            let content_width = text_width + max_scroll_x;
+            let handle_width = text_width * text_width / content_width;
+            let bar_interact = ui.interact(bar_rect, id.with("scroll_bar"), Sense::drag());
+            if bar_interact.active {
+                state.scroll_x += ui.input().mouse.delta.x * content_width / text_width;
+            }
+            state.scroll_x = clamp(state.scroll_x, 0.0..=max_scroll_x);
+
+            let handle_left = bar_rect.left() + state.scroll_x * text_width / content_width;
+            let handle_rect = Rect::from_min_size(
+                pos2(handle_left, bar_rect.top()),
+                vec2(handle_width, bar_rect.height()),
+            );
+            let style = *ui.style().interact(&bar_interact);
+            ui.add_paint_cmd(PaintCmd::Rect {
+                rect: handle_rect,
+                corner_radius: 0.5 * SCROLL_BAR_HEIGHT,
+                fill: Some(style.fill),
+                outline: None,
+            });
+        }
+        state.scroll_x = clamp(state.scroll_x, 0.0..=max_scroll_x);
+
+        {
+            let bg_rect = rect.expand(2.0); // breathing room for content
+            ui.insert_paint_cmd(
+                where_to_put_background,
+                PaintCmd::Rect {
+                    rect: bg_rect,
+                    corner_radius: ui.style().interact.style(&interact).corner_radius,
+                    fill: Some(ui.style().dark_bg_color),
+                    outline: ui.style().interact.style(&interact).rect_outline,
+                },
+            );
+        }
+
+        if line_numbers {
+            for (line_nr, line) in galley.lines.iter().enumerate() {
+                ui.floating_text(
+                    pos2(text_rect.left() - 4.0, text_rect.top() + line.y_min),
+                    (line_nr + 1).to_string(),
+                    text_style,
+                    (Align::Max, Align::Min),
+                    Some(color::GRAY),
+                );
+            }
+        }
+
+        if !hover_cards.is_empty() {
+            let galley_pos = text_rect.min - vec2(state.scroll_x, 0.0);
+            let hovered_symbol = ui
+                .input()
+                .mouse
+                .pos
+                .filter(|_| ui.hovered(text_rect))
+                .and_then(|mouse_pos| symbol_at(text, &galley, mouse_pos - galley_pos))
+                .map(|(symbol, rect)| (symbol, rect.translate(galley_pos.to_vec2())));
+
+            for (symbol, add_contents) in &hover_cards {
+                let hovered_rect = match &hovered_symbol {
+                    Some((hovered, rect)) if hovered == symbol => Some(*rect),
+                    _ => None,
+                };
+                HoverCard::new(id.with(symbol)).show(ui.ctx(), hovered_rect, |ui| add_contents(ui));
+            }
+        }
+
+        // The text is clipped to its rect, so it can scroll under the line numbers:
+        let mut text_ui = ui.child_ui(text_rect);
+        text_ui.set_clip_rect(text_rect.intersect(ui.clip_rect()));
+        let galley_pos = text_rect.min - vec2(state.scroll_x, 0.0);
+
+        if has_kb_focus {
+            let cursor_blink_hz = ui.style().cursor_blink_hz;
+            let show_cursor =
+                (ui.input().time * cursor_blink_hz as f64 * 3.0).floor() as i64 % 3 != 0;
+            if show_cursor {
+                if let Some(cursor) = state.cursor {
+                    let cursor_pos = galley_pos + galley.char_start_pos(cursor);
+                    text_ui.add_paint_cmd(PaintCmd::line_segment(
+                        [cursor_pos, cursor_pos + vec2(0.0, line_spacing)],
+                        color::WHITE,
+                        cursor_width,
+                    ));
+                }
+            }
+        }
+
+        text_ui.add_galley(galley_pos, galley, text_style, None);
+        ui.memory().code_editors.insert(id, state);
+        interact
+    }
+}
+
+/// The identifier (letters, digits and underscores) at `pos` in the galley, and where it is.
+fn symbol_at(text: &str, galley: &font::Galley, pos: Vec2) -> Option<(String, Rect)> {
+    let cursor = galley.char_at(pos);
+    let line = &galley.lines[cursor.line];
+    if pos.y < line.y_min || line.y_max < pos.y {
+        return None;
+    }
+
+    let is_symbol_char = |c: char| c.is_alphanumeric() || c == '_';
+    let line_start = cursor.char_idx - cursor.column;
+    let chars: Vec<char> = text
+        .chars()
+        .skip(line_start)
+        .take(line.char_count())
+        .collect();
+
+    let mut start = cursor.column;
+    while start > 0 && is_symbol_char(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = cursor.column;
+    while end < chars.len() && is_symbol_char(chars[end]) {
+        end += 1;
+    }
+
+    let rect = Rect::from_min_max(
+        pos2(line.x_offsets[start], line.y_min),
+        pos2(line.x_offsets[end], line.y_max),
+    );
+    if start == end || !rect.contains(pos2(pos.x, pos.y)) {
+        return None;
+    }
+    Some((chars[start..end].iter().collect(), rect))
+}
@@ -0,0 +1,196 @@
+use crate::{paint::*, *};
+
+/// Change of a channel per point the mouse is dragged.
+const DRAG_SPEED: f32 = 1.0;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct State {
+    /// The value of the channel being dragged, when the drag started.
+    drag_start_value: u8,
+    /// The text of the hex field, while it has keyboard focus.
+    hex: Option<String>,
+    /// The channel something was pasted over that wasn't a value for it, and when.
+    rejected_paste: Option<(Id, f64)>,
+}
+
+/// A compact row for editing a color: a swatch, a draggable value for each of R, G, B and A,
+/// and a field for typing in the color as hex (`#rrggbbaa`).
+///
+/// Drag a value left or right to change it, or paste a number over it.
+/// Click the swatch to type in (or paste) a hex color.
+///
+/// ```ignore
+/// ui.color_edit_button_srgba(&mut my_color);
+/// ```
+pub struct ColorEdit<'a> {
+    color: &'a mut Color,
+}
+
+impl<'a> ColorEdit<'a> {
+    pub fn new(color: &'a mut Color) -> Self {
+        Self { color }
+    }
+}
+
+impl<'a> Widget for ColorEdit<'a> {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let ColorEdit { color } = self;
+        let id = ui.make_position_id();
+        let mut state = ui
+            .memory()
+            .color_edits
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+        let color_before = *color;
+        let mut committed = false;
+
+        let rect = ui
+            .horizontal(|ui| {
+                let hex_id_source = id.with("hex");
+                let hex_id = TextEdit::id_for(ui, hex_id_source);
+
+                if swatch_ui(ui, id.with("swatch"), *color).clicked {
+                    ui.request_kb_focus(hex_id);
+                }
+
+                let mut channels = [
+                    ("R", &mut color.r),
+                    ("G", &mut color.g),
+                    ("B", &mut color.b),
+                    ("A", &mut color.a),
+                ];
+                for (name, value) in &mut channels {
+                    committed |= drag_channel_ui(ui, id.with(*name), name, value, &mut state);
+                }
+
+                if ui.has_kb_focus(hex_id) {
+                    let hex = state.hex.get_or_insert_with(|| color.to_hex());
+                    let response = ui.add(
+                        TextEdit::new(hex)
+                            .id(hex_id_source)
+                            .multiline(false)
+                            .single_line_paste(SingleLinePaste::Value(lenient_hex))
+                            .text_style(TextStyle::Monospace),
+                    );
+                    committed |= response.committed;
+                    if let Some(new_color) = Color::from_hex(hex) {
+                        *color = new_color;
+                    }
+                } else {
+                    // Show the current color, and start from it when clicked:
+                    state.hex = None;
+                    let mut hex = color.to_hex();
+                    let response = ui.add(
+                        TextEdit::new(&mut hex)
+                            .id(hex_id_source)
+                            .multiline(false)
+                            .text_style(TextStyle::Monospace),
+                    );
+                    // Losing the focus on this frame:
+                    committed |= response.committed;
+                }
+            })
+            .1;
+
+        ui.memory().color_edits.insert(id, state);
+        InteractInfo {
+            changed: *color != color_before,
+            committed,
+            ..ui.interact_hover(rect)
+        }
+    }
+}
+
+fn swatch_ui(ui: &mut Ui, id: Id, color: Color) -> InteractInfo {
+    let size = Vec2::splat(ui.style().clickable_diameter);
+    let rect = ui.allocate_space(size);
+    let interact = ui.interact(rect, id, Sense::click());
+    let style = *ui.style().interact(&interact);
+
+    // Show the opaque color on the left, so you can see it even when it is almost transparent:
+    let opaque = color::srgba(color.r, color.g, color.b, 255);
+    let left = Rect::from_min_max(rect.min, pos2(rect.center().x, rect.max.y));
+    ui.add_paint_cmd(PaintCmd::Rect {
+        rect: left,
+        corner_radius: 0.0,
+        fill: Some(opaque),
+        outline: None,
+    });
+    ui.add_paint_cmd(PaintCmd::Rect {
+        rect,
+        corner_radius: 0.0,
+        fill: Some(color),
+        outline: Some(LineStyle::new(style.stroke_width, style.stroke_color)),
+    });
+    interact
+}
+
+/// Clean up a pasted hex color, for `SingleLinePaste::Value`.
+/// Ignores whitespace, and accepts `0x` instead of `#`.
+fn lenient_hex(text: &str) -> Option<String> {
+    let hex: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+    Color::from_hex(hex).map(Color::to_hex)
+}
+
+/// Returns true if the drag was released (or a value pasted) this frame.
+fn drag_channel_ui(ui: &mut Ui, id: Id, name: &str, value: &mut u8, state: &mut State) -> bool {
+    let text_style = TextStyle::Monospace;
+    let text = format!("{} {:3}", name, value);
+    let galley = ui.fonts()[text_style].layout_single_line(text);
+    let padding = ui.style().button_padding;
+    let mut size = galley.size + 2.0 * padding;
+    size.y = size.y.max(ui.style().clickable_diameter);
+    let rect = ui.allocate_space(size);
+    let interact = ui.interact(rect, id, Sense::drag());
+
+    if interact.hovered || interact.active {
+        ui.output().cursor_icon = CursorIcon::ResizeHorizontal;
+    }
+    let mut pasted = false;
+    if interact.hovered {
+        for event in &ui.input().events {
+            if let Event::Paste(text) = event {
+                let new_value = lenient_number(text).and_then(|number| number.parse::<f32>().ok());
+                match new_value.filter(|v| (0.0..=255.0).contains(v)) {
+                    Some(new_value) => {
+                        *value = new_value.round() as u8;
+                        pasted = true;
+                    }
+                    None => state.rejected_paste = Some((id, ui.input().time)),
+                }
+            }
+        }
+    }
+    if interact.active {
+        let input = ui.input();
+        if input.mouse.pressed {
+            state.drag_start_value = *value;
+        }
+        if let (Some(pos), Some(origin)) = (input.mouse.pos, input.mouse.press_origin) {
+            let new_value = state.drag_start_value as f32 + DRAG_SPEED * (pos.x - origin.x);
+            *value = clamp(new_value.round(), 0.0..=255.0) as u8;
+        }
+    }
+
+    let style = *ui.style().interact(&interact);
+    ui.add_paint_cmd(PaintCmd::Rect {
+        rect,
+        corner_radius: style.corner_radius,
+        fill: style.bg_fill,
+        outline: style.rect_outline,
+    });
+    let text_pos = pos2(
+        rect.left() + padding.x,
+        rect.center().y - 0.5 * galley.size.y,
+    );
+    let text_color = style.stroke_color;
+    ui.add_galley(text_pos, galley, text_style, Some(text_color));
+    if let Some((rejected_id, rejected_time)) = state.rejected_paste {
+        if rejected_id == id {
+            paint_rejected_paste(ui, rect, rejected_time);
+        }
+    }
+    pasted || (interact.active && ui.input().mouse.released)
+}
@@ -0,0 +1,127 @@
+use crate::{paint::*, *};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HandleShape {
+    Circle,
+    Square,
+}
+
+/// A small handle at a position that can be dragged around,
+/// e.g. the control points of a curve or the stops of a gradient.
+///
+/// The handle is painted on top of whatever is at `pos`, and does not allocate any space:
+/// it is up to the caller to make sure there is room for it.
+/// NOTE: `pos` is in screen coordinates!
+///
+/// Usage:
+/// ```ignore
+/// let delta = DragHandle::new(ui.make_child_id("start"), &mut start)
+///     .axis(Direction::Horizontal)
+///     .show(ui)
+///     .1;
+/// ```
+pub struct DragHandle<'a> {
+    id: Id,
+    pos: &'a mut Pos2,
+    shape: HandleShape,
+    radius: f32,
+    axis: Option<Direction>,
+}
+
+impl<'a> DragHandle<'a> {
+    pub fn new(id: Id, pos: &'a mut Pos2) -> Self {
+        Self {
+            id,
+            pos,
+            shape: HandleShape::Circle,
+            radius: 4.0,
+            axis: None,
+        }
+    }
+
+    pub fn shape(mut self, shape: HandleShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Half the size of the handle
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Only allow dragging the handle along this axis.
+    pub fn axis(mut self, axis: Direction) -> Self {
+        self.axis = Some(axis);
+        self
+    }
+
+    /// Returns how much the handle was dragged this frame.
+    pub fn show(self, ui: &mut Ui) -> (GuiResponse, Vec2) {
+        let Self {
+            id,
+            pos,
+            shape,
+            radius,
+            axis,
+        } = self;
+
+        let rect = Rect::from_center_size(*pos, Vec2::splat(2.0 * radius));
+        let interact = ui.interact(rect, id, Sense::drag());
+
+        let mut delta = if interact.active {
+            ui.input().mouse.delta
+        } else {
+            Vec2::zero()
+        };
+        match axis {
+            Some(Direction::Horizontal) => delta.y = 0.0,
+            Some(Direction::Vertical) => delta.x = 0.0,
+            None => {}
+        }
+        *pos += delta;
+
+        if interact.hovered || interact.active {
+            match axis {
+                Some(Direction::Horizontal) => {
+                    ui.output().cursor_icon = CursorIcon::ResizeHorizontal
+                }
+                Some(Direction::Vertical) => ui.output().cursor_icon = CursorIcon::ResizeVertical,
+                None if interact.active => ui.output().cursor_icon = CursorIcon::Grabbing,
+                None => ui.output().cursor_icon = CursorIcon::Grab,
+            }
+        }
+
+        let style = *ui.style().interact(&interact);
+        let radius = if interact.hovered || interact.active {
+            radius + 1.0
+        } else {
+            radius
+        };
+        let fill = Some(style.fill);
+        let outline = Some(LineStyle::new(style.stroke_width, style.stroke_color));
+        let center = *pos;
+        ui.add_paint_cmd(match shape {
+            HandleShape::Circle => PaintCmd::Circle {
+                center,
+                radius,
+                fill,
+                outline,
+            },
+            HandleShape::Square => PaintCmd::Rect {
+                rect: Rect::from_center_size(center, Vec2::splat(2.0 * radius)),
+                corner_radius: 0.0,
+                fill,
+                outline,
+            },
+        });
+
+        (ui.response(interact), delta)
+    }
+}
+
+/// A round `DragHandle` that can be dragged freely.
+/// Returns how much it was dragged this frame.
+pub fn drag_handle(ui: &mut Ui, id: Id, pos: &mut Pos2) -> Vec2 {
+    DragHandle::new(id, pos).show(ui).1
+}
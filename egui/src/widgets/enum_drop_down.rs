@@ -0,0 +1,230 @@
+use crate::{
+    containers::{combo_box, combo_box::combo_box_impl},
+    widgets::*,
+};
+
+/// The search of a `searchable` drop down, while it is open.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SearchState {
+    query: String,
+    /// Index into the options matching the query.
+    highlighted: usize,
+}
+
+/// Implement this for (fieldless) enums to edit them with `EnumDropDown::from_enum`:
+///
+/// ```ignore
+/// impl EnumVariants for Fruit {
+///     fn variants() -> Vec<(Self, String)> {
+///         vec![
+///             (Fruit::Apple, "Apple".to_owned()),
+///             (Fruit::Banana, "Banana".to_owned()),
+///         ]
+///     }
+/// }
+/// ```
+pub trait EnumVariants: Clone + PartialEq {
+    /// All values with their labels, in the order they should be listed.
+    fn variants() -> Vec<(Self, String)>;
+}
+
+/// Pick one of a list of values with a combo box.
+///
+/// Usage: `ui.add(EnumDropDown::new(&mut value, vec![(1, "One"), (2, "Two")]).text("value"))`
+pub struct EnumDropDown<'a, T> {
+    value: &'a mut T,
+    variants: Vec<(T, String)>,
+    text: Option<String>,
+    id: Option<Id>,
+    searchable: bool,
+}
+
+impl<'a, T: Clone + PartialEq> EnumDropDown<'a, T> {
+    pub fn new(
+        value: &'a mut T,
+        variants: impl IntoIterator<Item = (T, impl Into<String>)>,
+    ) -> Self {
+        Self {
+            value,
+            variants: variants
+                .into_iter()
+                .map(|(value, label)| (value, label.into()))
+                .collect(),
+            text: None,
+            id: None,
+            searchable: false,
+        }
+    }
+
+    /// Show a label after the combo box
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Needed if there are several drop downs with the same text in the same `Ui`
+    pub fn id(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id = Some(Id::new(id_source));
+        self
+    }
+
+    /// Show a text field at the top of the popup to narrow down long lists of options by typing.
+    /// Pick one with the arrow keys and return, or with the mouse.
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
+        self
+    }
+}
+
+impl<'a, T: EnumVariants> EnumDropDown<'a, T> {
+    pub fn from_enum(value: &'a mut T) -> Self {
+        Self::new(value, T::variants())
+    }
+}
+
+impl<'a, T: Clone + PartialEq> Widget for EnumDropDown<'a, T> {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let EnumDropDown {
+            value,
+            variants,
+            text,
+            id,
+            searchable,
+        } = self;
+
+        let id = match (id, &text) {
+            (Some(id_source), _) => ui.make_child_id(id_source),
+            (None, Some(text)) => ui.make_unique_child_id(text),
+            (None, None) => ui.make_position_id(),
+        };
+
+        let selected_label = variants
+            .iter()
+            .find(|(variant, _)| variant == value)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_default();
+
+        ui.inner_layout(Layout::horizontal(Align::Center), |ui| {
+            let response = if searchable {
+                let response = combo_box_impl(ui, id, selected_label, |ui| {
+                    search_ui(ui, id, value, &variants)
+                });
+                if !ui.memory().is_popup_open(id) {
+                    ui.memory().drop_down_searches.remove(&id);
+                }
+                response
+            } else {
+                combo_box(ui, id, selected_label, |ui| {
+                    for (variant, label) in &variants {
+                        if ui.radio(label.as_str(), variant == value).clicked {
+                            *value = variant.clone();
+                        }
+                    }
+                })
+            };
+            if let Some(text) = text {
+                ui.add(Label::new(text).multiline(false));
+            }
+            response.into()
+        })
+        .0
+    }
+}
+
+/// The contents of the popup of a `searchable` drop down.
+/// Returns true if it was clicked somewhere other than on an option, so it should stay open.
+fn search_ui<T: Clone + PartialEq>(
+    ui: &mut Ui,
+    id: Id,
+    value: &mut T,
+    variants: &[(T, String)],
+) -> bool {
+    let state = ui.memory().drop_down_searches.get(&id).cloned();
+    let mut state = state.unwrap_or_else(|| SearchState {
+        query: String::new(),
+        highlighted: variants
+            .iter()
+            .position(|(variant, _)| variant == value)
+            .unwrap_or(0),
+    });
+
+    let query_id_source = id.with("query");
+    let query_id = TextEdit::id_for(ui, query_id_source);
+    ui.request_kb_focus(query_id);
+    let query_response = ui.add(
+        TextEdit::new(&mut state.query)
+            .id(query_id_source)
+            .multiline(false),
+    );
+    if query_response.changed {
+        state.highlighted = 0;
+    }
+
+    let query = state.query.to_lowercase();
+    let matching: Vec<usize> = variants
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, label))| label.to_lowercase().contains(&query))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut moved_highlight = false;
+    let mut picked = None;
+    for event in &ui.input().events {
+        match event {
+            Event::Key {
+                key: Key::Up,
+                pressed: true,
+            } => {
+                state.highlighted = state.highlighted.saturating_sub(1);
+                moved_highlight = true;
+            }
+            Event::Key {
+                key: Key::Down,
+                pressed: true,
+            } if state.highlighted + 1 < matching.len() => {
+                state.highlighted += 1;
+                moved_highlight = true;
+            }
+            Event::Key {
+                key: Key::Return,
+                pressed: true,
+            } => picked = Some(state.highlighted),
+            _ => {}
+        }
+    }
+    state.highlighted = state.highlighted.min(matching.len().saturating_sub(1));
+
+    let mut clicked_option = false;
+    ScrollArea::default()
+        .id_source(id.with("options"))
+        .show(ui, |ui| {
+            for (index, &variant_index) in matching.iter().enumerate() {
+                let mut highlighted = index == state.highlighted;
+                let response =
+                    ui.selectable_label(variants[variant_index].1.as_str(), &mut highlighted);
+                if response.clicked {
+                    picked = Some(index);
+                    clicked_option = true;
+                }
+                if moved_highlight && index == state.highlighted {
+                    ui.memory().scroll_to(response.rect);
+                }
+            }
+            if matching.is_empty() {
+                ui.label("No matches");
+            }
+        });
+
+    if let Some(&variant_index) = picked.and_then(|index| matching.get(index)) {
+        *value = variants[variant_index].0.clone();
+        ui.memory().close_popup();
+    }
+    ui.memory().drop_down_searches.insert(id, state);
+
+    let clicked_inside = match ui.input().mouse.pos {
+        Some(mouse_pos) => ui.input().mouse.click && ui.rect().contains(mouse_pos),
+        None => false,
+    };
+    clicked_inside && !clicked_option
+}
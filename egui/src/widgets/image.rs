@@ -0,0 +1,102 @@
+use crate::{paint::*, widgets::Widget, *};
+
+/// Paints a texture, e.g. one you have registered with your backend.
+///
+/// Usage: `ui.add(Image::new(TextureId::User(my_id), vec2(64.0, 64.0)))`
+pub struct Image {
+    texture_id: TextureId,
+    /// Normalized, i.e. `(0,0)` is the top left and `(1,1)` the bottom right of the texture.
+    uv: Rect,
+    desired_size: Vec2,
+    bg_fill: Option<Color>,
+    tint: Color,
+}
+
+impl Image {
+    pub fn new(texture_id: TextureId, desired_size: impl Into<Vec2>) -> Self {
+        Self {
+            texture_id,
+            uv: Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            desired_size: desired_size.into(),
+            bg_fill: None,
+            tint: color::WHITE,
+        }
+    }
+
+    /// Select a part of the texture, in normalized coordinates (`0..=1`).
+    pub fn uv(mut self, uv: Rect) -> Self {
+        self.uv = uv;
+        self
+    }
+
+    /// Painted behind the image (useful for images with transparency).
+    pub fn bg_fill(mut self, bg_fill: impl Into<Color>) -> Self {
+        self.bg_fill = Some(bg_fill.into());
+        self
+    }
+
+    /// Multiply the image with this color (premultiplied alpha).
+    pub fn tint(mut self, tint: impl Into<Color>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+}
+
+impl Widget for Image {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let Image {
+            texture_id,
+            uv,
+            desired_size,
+            bg_fill,
+            tint,
+        } = self;
+
+        let rect = ui.allocate_space(desired_size);
+
+        if let Some(bg_fill) = bg_fill {
+            ui.add_paint_cmd(PaintCmd::Rect {
+                rect,
+                corner_radius: 0.0,
+                fill: Some(bg_fill),
+                outline: None,
+            });
+        }
+
+        paint_texture(ui, rect, texture_id, uv, tint);
+
+        ui.interact_hover(rect)
+    }
+}
+
+/// Paint the part `uv` (normalized, `0..=1`) of a texture into `rect`.
+pub(crate) fn paint_texture(ui: &mut Ui, rect: Rect, texture_id: TextureId, uv: Rect, tint: Color) {
+    // `Vertex::uv` is in texels for the font texture, but normalized for user textures:
+    let uv_scale = match texture_id {
+        TextureId::Egui => {
+            let texture = ui.ctx().texture();
+            vec2(texture.width as f32 - 1.0, texture.height as f32 - 1.0)
+        }
+        TextureId::User(_) => Vec2::splat(u16::MAX as f32),
+    };
+    let to_texel = |uv: Pos2| {
+        (
+            (clamp(uv.x, 0.0..=1.0) * uv_scale.x).round() as u16,
+            (clamp(uv.y, 0.0..=1.0) * uv_scale.y).round() as u16,
+        )
+    };
+
+    let top_left = Vertex {
+        pos: rect.min,
+        uv: to_texel(uv.min),
+        color: tint,
+    };
+    let bottom_right = Vertex {
+        pos: rect.max,
+        uv: to_texel(uv.max),
+        color: tint,
+    };
+    let mut triangles = Triangles::with_texture(texture_id);
+    triangles.add_rect(top_left, bottom_right);
+    ui.add_paint_cmd(PaintCmd::Triangles(triangles));
+}
@@ -0,0 +1,246 @@
+use std::hash::Hash;
+
+use crate::{containers::show_tooltip, paint::*, widgets::Widget, *};
+
+/// Zoom limits, in points per texel.
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 128.0;
+
+/// How much one point of mouse wheel scrolling zooms.
+const ZOOM_PER_SCROLL: f32 = 0.005;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct State {
+    /// Points per texel.
+    zoom: f32,
+    /// The texel coordinate at the top left corner of the view.
+    pan: Pos2,
+}
+
+impl State {
+    /// Show all of the texture, centered.
+    fn fit(view_size: Vec2, texture_size: Vec2) -> Self {
+        let zoom = (view_size.x / texture_size.x).min(view_size.y / texture_size.y);
+        let zoom = clamp(zoom, MIN_ZOOM..=MAX_ZOOM);
+        Self {
+            zoom,
+            pan: Pos2::default() + 0.5 * texture_size - 0.5 * view_size / zoom,
+        }
+    }
+
+    fn texel_at(&self, rect: Rect, pos: Pos2) -> Pos2 {
+        self.pan + (pos - rect.min) / self.zoom
+    }
+
+    fn screen_pos(&self, rect: Rect, texel: Pos2) -> Pos2 {
+        rect.min + (texel - self.pan) * self.zoom
+    }
+}
+
+/// A view of a texture you can zoom into (with the mouse wheel or a pinch, about the mouse)
+/// and pan around (by dragging it). Double-click to show all of it again.
+///
+/// Past a zoom level (see `grid_zoom`) the pixel grid is shown.
+/// Hover a pixel to see its coordinate, and its color if you provide it with `pixel_color`.
+/// Useful for texture debugging tools.
+///
+/// ```ignore
+/// ui.add(
+///     ImageInspector::new("my_texture", TextureId::User(my_id), vec2(256.0, 256.0))
+///         .pixel_color(|x, y| my_pixels[y as usize * 256 + x as usize]),
+/// );
+/// ```
+pub struct ImageInspector<'a> {
+    id_source: Id,
+    texture_id: TextureId,
+    /// In texels.
+    texture_size: Vec2,
+    desired_size: Vec2,
+    grid_zoom: f32,
+    pixel_color: Option<Box<dyn Fn(u32, u32) -> Color + 'a>>,
+}
+
+impl<'a> ImageInspector<'a> {
+    /// `texture_size` is the size of the texture in texels.
+    pub fn new(id_source: impl Hash, texture_id: TextureId, texture_size: impl Into<Vec2>) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            texture_id,
+            texture_size: texture_size.into(),
+            desired_size: vec2(256.0, 256.0),
+            grid_zoom: 8.0,
+            pixel_color: None,
+        }
+    }
+
+    /// Size of the view, in points.
+    pub fn desired_size(mut self, desired_size: impl Into<Vec2>) -> Self {
+        self.desired_size = desired_size.into();
+        self
+    }
+
+    /// Show the pixel grid when zoomed in to at least this many points per texel.
+    pub fn grid_zoom(mut self, grid_zoom: f32) -> Self {
+        self.grid_zoom = grid_zoom;
+        self
+    }
+
+    /// The color of the texel at `(x, y)` (from the top left), shown when it is hovered.
+    pub fn pixel_color(mut self, pixel_color: impl Fn(u32, u32) -> Color + 'a) -> Self {
+        self.pixel_color = Some(Box::new(pixel_color));
+        self
+    }
+}
+
+impl<'a> Widget for ImageInspector<'a> {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let ImageInspector {
+            id_source,
+            texture_id,
+            texture_size,
+            desired_size,
+            grid_zoom,
+            pixel_color,
+        } = self;
+
+        let id = ui.make_child_id(id_source);
+        let rect = ui.allocate_space(desired_size);
+        let sense = Sense::drag().union(Sense::scroll()).union(Sense::zoom());
+        let interact = ui.interact(rect, id, sense);
+
+        let state = ui.memory().image_inspectors.get(&id).copied();
+        let mut state = match state {
+            Some(state) if !interact.double_clicked => state,
+            _ => State::fit(rect.size(), texture_size),
+        };
+
+        if interact.active {
+            let delta = ui.input().mouse.delta;
+            state.pan -= delta / state.zoom;
+            ui.output().cursor_icon = CursorIcon::Grabbing;
+        } else if interact.hovered {
+            ui.output().cursor_icon = CursorIcon::Grab;
+        }
+
+        let zoom_factor = interact.zoom_delta * (ZOOM_PER_SCROLL * interact.scroll_delta.y).exp();
+        if zoom_factor != 1.0 {
+            // Keep the texel under the mouse where it is:
+            let anchor = ui.input().mouse.pos.unwrap_or_else(|| rect.center());
+            let texel = state.texel_at(rect, anchor);
+            state.zoom = clamp(state.zoom * zoom_factor, MIN_ZOOM..=MAX_ZOOM);
+            state.pan = texel - (anchor - rect.min) / state.zoom;
+        }
+
+        ui.memory().image_inspectors.insert(id, state);
+
+        ui.add_paint_cmd(PaintCmd::Rect {
+            rect,
+            corner_radius: 0.0,
+            fill: Some(ui.style().dark_bg_color),
+            outline: None,
+        });
+
+        let visible_texels = Rect::from_min_max(
+            state.texel_at(rect, rect.min),
+            state.texel_at(rect, rect.max),
+        )
+        .intersect(Rect::from_min_size(Pos2::default(), texture_size));
+        if !visible_texels.is_empty() {
+            let image_rect = Rect::from_min_max(
+                state.screen_pos(rect, visible_texels.min),
+                state.screen_pos(rect, visible_texels.max),
+            );
+            let uv = Rect::from_min_max(
+                pos2(
+                    visible_texels.min.x / texture_size.x,
+                    visible_texels.min.y / texture_size.y,
+                ),
+                pos2(
+                    visible_texels.max.x / texture_size.x,
+                    visible_texels.max.y / texture_size.y,
+                ),
+            );
+            let mut image_ui = ui.child_ui(image_rect);
+            image_ui.add(Image::new(texture_id, image_rect.size()).uv(uv));
+
+            if state.zoom >= grid_zoom {
+                paint_pixel_grid(ui, rect, &state, visible_texels);
+            }
+        }
+
+        let hovered_texel = match ui.input().mouse.pos {
+            Some(mouse_pos) if interact.hovered => Some(state.texel_at(rect, mouse_pos).floor()),
+            _ => None,
+        };
+        if let Some(texel) = hovered_texel {
+            let inside = texel.x >= 0.0
+                && texel.y >= 0.0
+                && texel.x < texture_size.x
+                && texel.y < texture_size.y;
+            if inside && !interact.active {
+                let (x, y) = (texel.x as u32, texel.y as u32);
+                if state.zoom >= grid_zoom {
+                    ui.add_paint_cmd(PaintCmd::Rect {
+                        rect: Rect::from_min_max(
+                            state.screen_pos(rect, texel),
+                            state.screen_pos(rect, texel + Vec2::splat(1.0)),
+                        ),
+                        corner_radius: 0.0,
+                        fill: None,
+                        outline: Some(LineStyle::new(ui.style().line_width, color::YELLOW)),
+                    });
+                }
+                show_tooltip(ui.ctx(), |ui| {
+                    ui.add(label!("x: {}, y: {}", x, y).text_style(TextStyle::Monospace));
+                    if let Some(pixel_color) = &pixel_color {
+                        let color = pixel_color(x, y);
+                        ui.horizontal(|ui| {
+                            let swatch =
+                                ui.allocate_space(Vec2::splat(ui.style().clickable_diameter));
+                            ui.add_paint_cmd(PaintCmd::Rect {
+                                rect: swatch,
+                                corner_radius: 0.0,
+                                fill: Some(color),
+                                outline: Some(ui.style().thin_outline),
+                            });
+                            ui.add(label!("{}", color.to_hex()).text_style(TextStyle::Monospace));
+                        });
+                    }
+                });
+            }
+        }
+
+        interact
+    }
+}
+
+fn paint_pixel_grid(ui: &mut Ui, rect: Rect, state: &State, visible_texels: Rect) {
+    let grid_color = color::gray(128, 96);
+    let (min, max) = (visible_texels.min.ceil(), visible_texels.max.floor());
+    let (top_left, bottom_right) = (
+        state.screen_pos(rect, visible_texels.min),
+        state.screen_pos(rect, visible_texels.max),
+    );
+
+    let mut x = min.x;
+    while x <= max.x {
+        let screen_x = state.screen_pos(rect, pos2(x, 0.0)).x;
+        ui.add_paint_cmd(PaintCmd::line_segment(
+            [pos2(screen_x, top_left.y), pos2(screen_x, bottom_right.y)],
+            grid_color,
+            1.0,
+        ));
+        x += 1.0;
+    }
+    let mut y = min.y;
+    while y <= max.y {
+        let screen_y = state.screen_pos(rect, pos2(0.0, y)).y;
+        ui.add_paint_cmd(PaintCmd::line_segment(
+            [pos2(top_left.x, screen_y), pos2(bottom_right.x, screen_y)],
+            grid_color,
+            1.0,
+        ));
+        y += 1.0;
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{paint::*, widgets::Label, *};
+
+/// A rotary knob for editing an angle. Drag around the knob to turn it.
+///
+/// An angle of zero points to the right, and positive angles turn clockwise,
+/// just like `Vec2::angled`. You can turn it around more than once.
+///
+/// Usage: `ui.add(Knob::degrees(&mut self.heading).text("heading"))`
+pub struct Knob<'a> {
+    /// Always in radians
+    angle: f32,
+    value: &'a mut f32,
+    degrees: bool,
+    text: Option<String>,
+    diameter: Option<f32>,
+}
+
+impl<'a> Knob<'a> {
+    pub fn radians(radians: &'a mut f32) -> Self {
+        Self {
+            angle: *radians,
+            value: radians,
+            degrees: false,
+            text: None,
+            diameter: None,
+        }
+    }
+
+    pub fn degrees(degrees: &'a mut f32) -> Self {
+        Self {
+            angle: degrees.to_radians(),
+            value: degrees,
+            degrees: true,
+            text: None,
+            diameter: None,
+        }
+    }
+
+    /// Show this text and the current value to the right of the knob.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Defaults to twice `style.clickable_diameter`.
+    pub fn diameter(mut self, diameter: f32) -> Self {
+        self.diameter = Some(diameter);
+        self
+    }
+}
+
+impl<'a> Knob<'a> {
+    fn knob_ui(&mut self, ui: &mut Ui) -> InteractInfo {
+        let diameter = self.diameter.unwrap_or(2.0 * ui.style().clickable_diameter);
+        let id = ui.make_position_id();
+        let rect = ui.allocate_space(Vec2::splat(diameter));
+        let interact = ui.interact(rect, id, Sense::drag());
+        let center = rect.center();
+        let radius = 0.5 * diameter;
+
+        let value_before = *self.value;
+        if interact.active {
+            if let Some(mouse_pos) = ui.input().mouse.pos {
+                let prev_mouse_pos = mouse_pos - ui.input().mouse.delta;
+                // Too close to the center to tell which way we are turning:
+                let min_distance = 2.0;
+                if (mouse_pos - center).length() > min_distance
+                    && (prev_mouse_pos - center).length() > min_distance
+                {
+                    let mut delta =
+                        (mouse_pos - center).angle() - (prev_mouse_pos - center).angle();
+                    // Take the shortest way around:
+                    if delta > 0.5 * TAU {
+                        delta -= TAU;
+                    } else if delta < -0.5 * TAU {
+                        delta += TAU;
+                    }
+                    self.angle += delta;
+                    *self.value = if self.degrees {
+                        self.angle.to_degrees()
+                    } else {
+                        self.angle
+                    };
+                }
+            }
+        }
+
+        let style = *ui.style().interact(&interact);
+        ui.add_paint_cmd(PaintCmd::Circle {
+            center,
+            radius,
+            fill: Some(ui.style().dark_bg_color),
+            outline: Some(LineStyle::new(style.stroke_width, style.stroke_color)),
+        });
+        let tip = center + (radius - 2.0) * Vec2::angled(self.angle);
+        ui.add_paint_cmd(PaintCmd::line_segment(
+            [center, tip],
+            style.stroke_color,
+            (2.0 * style.stroke_width).max(2.0),
+        ));
+        ui.add_paint_cmd(PaintCmd::Circle {
+            center,
+            radius: 0.15 * radius,
+            fill: Some(style.fill),
+            outline: None,
+        });
+
+        InteractInfo {
+            changed: *self.value != value_before,
+            committed: interact.active && ui.input().mouse.released,
+            ..interact
+        }
+    }
+}
+
+impl<'a> Widget for Knob<'a> {
+    fn ui(mut self, ui: &mut Ui) -> InteractInfo {
+        if let Some(text) = self.text.take() {
+            ui.horizontal(|ui| {
+                let interact = self.knob_ui(ui);
+                let text = if self.degrees {
+                    format!("{}: {:.1} deg", text, *self.value)
+                } else {
+                    format!("{}: {:.3} rad", text, *self.value)
+                };
+                let text_rect = Rect::from_min_size(
+                    ui.available().min,
+                    vec2(ui.available().width(), interact.rect.height()),
+                );
+                let mut text_ui = ui.child_ui(text_rect);
+                text_ui.inner_layout(Layout::horizontal(Align::Center), |ui| {
+                    ui.add(Label::new(text).multiline(false));
+                });
+                ui.expand_to_include_child(text_ui.child_bounds());
+                interact
+            })
+            .0
+        } else {
+            self.knob_ui(ui)
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{
+    containers::hover_card::CardContents,
+    paint::{mesher, *},
+    widgets::Widget,
+    *,
+};
+
+/// How much italic text leans to the right (horizontal offset per vertical point).
+const ITALICS_SLANT: f32 = 0.2;
+
+/// How text in a `MarkdownLabel` is emphasized.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Format {
+    strong: bool,
+    italics: bool,
+    code: bool,
+}
+
+/// A word (or inline code span) laid out in a `MarkdownLabel`.
+struct Fragment {
+    /// Relative to the top left of the label
+    pos: Pos2,
+    galley: font::Galley,
+    text_style: TextStyle,
+    format: Format,
+    /// Headings are painted with the strong color, but are not faux-bold.
+    heading: bool,
+}
+
+/// A label that understands a small subset of markdown:
+///
+/// * `# Heading` and `## Smaller heading`
+/// * bullet list items starting with `- ` or `* `
+/// * `**strong**`, `*italics*` and `` `inline code` ``
+///
+/// Unlike real markdown, every newline starts a new line.
+/// Empty lines separate paragraphs.
+///
+/// Usage: `ui.add(MarkdownLabel::new("Some **important** `code`"))`
+pub struct MarkdownLabel<'a> {
+    text: String,
+    hover_cards: Vec<(String, CardContents<'a>)>,
+}
+
+impl<'a> std::fmt::Debug for MarkdownLabel<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<&str> = self
+            .hover_cards
+            .iter()
+            .map(|(term, _)| term.as_str())
+            .collect();
+        f.debug_struct("MarkdownLabel")
+            .field("text", &self.text)
+            .field("hover_cards", &terms)
+            .finish()
+    }
+}
+
+impl<'a> MarkdownLabel<'a> {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            hover_cards: vec![],
+        }
+    }
+
+    /// Underline every occurrence of the word `term`,
+    /// and show a `HoverCard` with `add_contents` when it is hovered.
+    pub fn hover_card(
+        mut self,
+        term: impl Into<String>,
+        add_contents: impl Fn(&mut Ui) + 'a,
+    ) -> Self {
+        self.hover_cards.push((term.into(), Box::new(add_contents)));
+        self
+    }
+}
+
+impl<'a> Widget for MarkdownLabel<'a> {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let max_width = ui.available().width();
+        let (fragments, bullets, size) = layout(ui, &self.text, max_width);
+        let id = ui.make_position_id();
+        let rect = ui.allocate_space(size);
+
+        let text_color = ui.style().text_color;
+        let bullet_radius = 0.15 * ui.fonts()[TextStyle::Body].height();
+        for center in bullets {
+            // The fonts have no bullet glyph:
+            ui.add_paint_cmd(PaintCmd::Circle {
+                center: rect.min + center.to_vec2(),
+                radius: bullet_radius,
+                fill: Some(text_color),
+                outline: None,
+            });
+        }
+
+        let strong_color = ui.style().strong_text_color;
+        let faux_bold_offset = vec2(1.0 / ui.input().pixels_per_point, 0.0);
+
+        for (term, add_contents) in &self.hover_cards {
+            let mut hovered_rect = None;
+            for fragment in &fragments {
+                let word = fragment
+                    .galley
+                    .text
+                    .trim_matches(|c: char| !c.is_alphanumeric() && c != '_');
+                if word == term {
+                    let word_rect = Rect::from_min_size(
+                        rect.min + fragment.pos.to_vec2(),
+                        fragment.galley.size,
+                    );
+                    ui.add_paint_cmd(PaintCmd::line_segment(
+                        [word_rect.left_bottom(), word_rect.right_bottom()],
+                        color::gray(128, 255),
+                        1.0,
+                    ));
+                    if ui.hovered(word_rect) {
+                        hovered_rect = Some(word_rect);
+                    }
+                }
+            }
+            HoverCard::new(id.with(term)).show(ui.ctx(), hovered_rect, |ui| add_contents(ui));
+        }
+
+        for fragment in fragments {
+            let Fragment {
+                pos,
+                galley,
+                text_style,
+                format,
+                heading,
+            } = fragment;
+            let pos = rect.min + pos.to_vec2();
+
+            if format.code {
+                ui.add_paint_cmd(PaintCmd::Rect {
+                    rect: Rect::from_min_size(pos, galley.size).expand(1.0),
+                    corner_radius: 2.0,
+                    fill: Some(ui.style().dark_bg_color),
+                    outline: None,
+                });
+            }
+
+            let color = if format.strong || heading {
+                strong_color
+            } else {
+                text_color
+            };
+
+            if format.strong {
+                paint_text(
+                    ui,
+                    pos + faux_bold_offset,
+                    galley.clone(),
+                    text_style,
+                    color,
+                    format.italics,
+                );
+            }
+            paint_text(ui, pos, galley, text_style, color, format.italics);
+        }
+
+        ui.interact_hover(rect)
+    }
+}
+
+fn paint_text(
+    ui: &mut Ui,
+    pos: Pos2,
+    galley: font::Galley,
+    text_style: TextStyle,
+    color: Color,
+    italics: bool,
+) {
+    if !italics {
+        ui.add_galley(pos, galley, text_style, Some(color));
+        return;
+    }
+
+    // There is no italic font, so we slant the glyphs ourselves:
+    let bottom = pos.y + galley.size.y;
+    let mut triangles = Triangles::default();
+    mesher::paint_command_into_triangles(
+        &mut Path::default(),
+        PaintOptions::default(),
+        ui.fonts(),
+        PaintCmd::Text {
+            pos,
+            galley,
+            text_style,
+            color,
+        },
+        &mut triangles,
+    );
+    for vertex in &mut triangles.vertices {
+        vertex.pos.x += ITALICS_SLANT * (bottom - vertex.pos.y);
+    }
+    ui.add_paint_cmd(PaintCmd::Triangles(triangles));
+}
+
+/// Returns the fragments, the centers of the bullet points and the size of it all
+fn layout(ui: &Ui, text: &str, max_width: f32) -> (Vec<Fragment>, Vec<Pos2>, Vec2) {
+    let fonts = ui.fonts();
+    let bullet_indent = ui.style().indent;
+
+    let mut fragments = vec![];
+    let mut bullets = vec![];
+    let mut y = 0.0;
+    let mut width: f32 = 0.0;
+
+    for line in text.split('\n') {
+        let trimmed = line.trim_start();
+        let (text_style, heading, indent, line) = if let Some(rest) = trimmed.strip_prefix("# ") {
+            (TextStyle::Heading, true, 0.0, rest)
+        } else if trimmed.starts_with("##") {
+            let rest = trimmed.trim_start_matches('#').trim_start();
+            (TextStyle::Button, true, 0.0, rest)
+        } else if let Some(rest) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            (TextStyle::Body, false, bullet_indent, rest)
+        } else {
+            (TextStyle::Body, false, 0.0, line)
+        };
+
+        if line.trim().is_empty() {
+            // Paragraph break:
+            y += 0.5 * fonts[TextStyle::Body].line_spacing();
+            continue;
+        }
+
+        if indent > 0.0 {
+            let height = fonts[text_style].height();
+            bullets.push(pos2(0.5 * indent, y + 0.5 * height));
+        }
+
+        // Flow the words left to right, starting a new row when we run out of width:
+        let mut row: Vec<Fragment> = vec![];
+        let mut x = indent;
+        let mut flush_row =
+            |row: &mut Vec<Fragment>, fragments: &mut Vec<Fragment>, y: &mut f32| {
+                let row_height = row
+                    .iter()
+                    .map(|fragment| fragment.galley.size.y)
+                    .fold(0.0, f32::max);
+                for mut fragment in row.drain(..) {
+                    // Line up the bottoms of the different fonts:
+                    fragment.pos.y = *y + row_height - fragment.galley.size.y;
+                    width = width.max(fragment.pos.x + fragment.galley.size.x);
+                    fragments.push(fragment);
+                }
+                *y += row_height;
+            };
+
+        for (span, format) in parse_inline(line) {
+            let text_style = if format.code {
+                TextStyle::Monospace
+            } else {
+                text_style
+            };
+            let font = &fonts[text_style];
+            let space_width = font.layout_single_line(" ".to_owned()).size.x;
+
+            let words: Vec<&str> = if format.code {
+                vec![span.as_str()]
+            } else {
+                span.split(' ').collect()
+            };
+            for (i, word) in words.iter().enumerate() {
+                if i > 0 {
+                    x += space_width;
+                }
+                if word.is_empty() {
+                    continue;
+                }
+                let galley = font.layout_single_line((*word).to_owned());
+                if x + galley.size.x > max_width && !row.is_empty() {
+                    flush_row(&mut row, &mut fragments, &mut y);
+                    x = indent;
+                }
+                let word_width = galley.size.x;
+                row.push(Fragment {
+                    pos: pos2(x, 0.0),
+                    galley,
+                    text_style,
+                    format,
+                    heading,
+                });
+                x += word_width;
+            }
+        }
+        flush_row(&mut row, &mut fragments, &mut y);
+    }
+
+    (fragments, bullets, vec2(width, y))
+}
+
+/// Split a line into spans of the same format.
+/// `**` toggles strong, `*` toggles italics and `` ` `` toggles code.
+fn parse_inline(line: &str) -> Vec<(String, Format)> {
+    let mut spans = vec![];
+    let mut format = Format::default();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let mut new_format = format;
+        if c == '`' {
+            new_format.code = !format.code;
+        } else if format.code {
+            current.push(c);
+            continue;
+        } else if c == '*' && chars.peek() == Some(&'*') {
+            chars.next();
+            new_format.strong = !format.strong;
+        } else if c == '*' {
+            new_format.italics = !format.italics;
+        } else {
+            current.push(c);
+            continue;
+        }
+
+        if !current.is_empty() {
+            spans.push((std::mem::take(&mut current), format));
+        }
+        format = new_format;
+    }
+    if !current.is_empty() {
+        spans.push((current, format));
+    }
+    spans
+}
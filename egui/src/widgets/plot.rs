@@ -0,0 +1,445 @@
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use crate::{containers::show_tooltip, paint::*, widgets::Widget, *};
+
+/// Colors for series that don't have one of their own, in order.
+const SERIES_COLORS: [Color; 5] = [
+    color::LIGHT_BLUE,
+    color::YELLOW,
+    color::GREEN,
+    color::RED,
+    color::LIGHT_GRAY,
+];
+
+/// How close (in points) the mouse must be to a point to show its value.
+const HOVER_DISTANCE: f32 = 12.0;
+
+/// Which series of a `Plot` have been hidden by clicking them in the legend.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with_serde", serde(default))]
+pub(crate) struct State {
+    /// Names of the hidden series.
+    hidden: HashSet<String>,
+}
+
+/// A line in a `Plot`: a name and some `(x, y)` points.
+#[derive(Clone, Debug)]
+pub struct Series {
+    name: String,
+    points: Vec<(f32, f32)>,
+    color: Option<Color>,
+}
+
+impl Series {
+    pub fn new(name: impl Into<String>, points: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        Self {
+            name: name.into(),
+            points: points.into_iter().collect(),
+            color: None,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// A line chart of one or more `Series`.
+///
+/// The axes are scaled to fit all the visible points.
+/// Hover a point to see its value.
+/// Below the plot is a legend: hover a series there to highlight it, click it to hide or show it.
+///
+/// ```ignore
+/// let sine = (0..=100).map(|i| {
+///     let x = i as f32 / 10.0;
+///     (x, x.sin())
+/// });
+/// ui.add(Plot::new().series(Series::new("sin", sine)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Plot {
+    id: Option<Id>,
+    series: Vec<Series>,
+    height: f32,
+    desired_width: Option<f32>,
+    include_y_zero: bool,
+}
+
+impl Plot {
+    pub fn new() -> Self {
+        Self {
+            id: None,
+            series: vec![],
+            height: 160.0,
+            desired_width: None,
+            include_y_zero: false,
+        }
+    }
+
+    /// Needed if there are several plots in the same `Ui`,
+    /// so that they remember which series are hidden separately.
+    pub fn id(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id = Some(Id::new(id_source));
+        self
+    }
+
+    pub fn series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Height of the whole plot, including the axis labels and the legend.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Defaults to the available width.
+    pub fn desired_width(mut self, desired_width: f32) -> Self {
+        self.desired_width = Some(desired_width);
+        self
+    }
+
+    /// Always show `y = 0`, even if all the values are far from it.
+    pub fn include_y_zero(mut self, include_y_zero: bool) -> Self {
+        self.include_y_zero = include_y_zero;
+        self
+    }
+}
+
+impl Widget for Plot {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let Plot {
+            id,
+            series,
+            height,
+            desired_width,
+            include_y_zero,
+        } = self;
+
+        let id = ui.make_child_id(id);
+        let width = desired_width.unwrap_or_else(|| ui.available_finite().width());
+        let rect = ui.allocate_space(vec2(width, height));
+        let interact = ui.interact_hover(rect);
+
+        let text_style = TextStyle::Monospace;
+        let line_spacing = ui.fonts()[text_style].line_spacing();
+        let text_color = ui.style().text_color;
+        let grid_color = color::gray(128, 32);
+
+        let series_color = |i: usize| {
+            series[i]
+                .color
+                .unwrap_or(SERIES_COLORS[i % SERIES_COLORS.len()])
+        };
+
+        // The legend comes first, since it decides which series are shown:
+        let mut state = ui.memory().plots.get(&id).cloned().unwrap_or_default();
+        let legend_top = rect.bottom() - line_spacing;
+        let mut legend_left = rect.left();
+        let mut highlighted = None;
+        for (i, series) in series.iter().enumerate() {
+            let galley = ui.fonts()[text_style].layout_single_line(series.name.clone());
+            let swatch_size = 0.6 * line_spacing;
+            let entry_rect = Rect::from_min_size(
+                pos2(legend_left, legend_top),
+                vec2(swatch_size + 4.0 + galley.size.x, line_spacing),
+            );
+            legend_left = entry_rect.right() + 2.0 * ui.style().item_spacing.x;
+
+            let entry = ui.interact(entry_rect, id.with(("legend", i)), Sense::click());
+            if entry.clicked && !state.hidden.remove(&series.name) {
+                state.hidden.insert(series.name.clone());
+            }
+            let hidden = state.hidden.contains(&series.name);
+            if entry.hovered && !hidden {
+                highlighted = Some(i);
+            }
+
+            let swatch_rect = Rect::from_center_size(
+                pos2(entry_rect.left() + 0.5 * swatch_size, entry_rect.center().y),
+                Vec2::splat(swatch_size),
+            );
+            ui.add_paint_cmd(PaintCmd::Rect {
+                rect: swatch_rect,
+                corner_radius: 2.0,
+                fill: if hidden { None } else { Some(series_color(i)) },
+                outline: Some(LineStyle::new(1.0, series_color(i))),
+            });
+            let name_color = if hidden {
+                ui.style().interact.disabled.stroke_color
+            } else if entry.hovered {
+                ui.style().interact.hovered.stroke_color
+            } else {
+                text_color
+            };
+            ui.floating_text(
+                pos2(swatch_rect.right() + 4.0, entry_rect.center().y),
+                series.name.clone(),
+                text_style,
+                (Align::Min, Align::Center),
+                Some(name_color),
+            );
+        }
+        ui.memory().plots.insert(id, state.clone());
+
+        let visible: Vec<&Series> = series
+            .iter()
+            .filter(|series| !state.hidden.contains(&series.name))
+            .collect();
+        let (x_range, y_range) = bounds(&visible, include_y_zero);
+
+        // The y labels go to the left of the plot, so we need to know how wide they are:
+        let y_step = grid_step(&y_range, (rect.height() / (2.0 * line_spacing)).floor());
+        let y_labels: Vec<(f32, String)> = grid_values(&y_range, y_step)
+            .map(|y| (y, format!("{:.*}", decimals(y_step), y)))
+            .collect();
+        let y_labels_width = y_labels
+            .iter()
+            .map(|(_, text)| {
+                ui.fonts()[text_style]
+                    .layout_single_line(text.clone())
+                    .size
+                    .x
+            })
+            .fold(0.0, f32::max);
+
+        let plot_rect = Rect::from_min_max(
+            pos2(rect.left() + y_labels_width + 4.0, rect.top()),
+            pos2(rect.right(), legend_top - line_spacing - 4.0),
+        );
+        let to_screen = |(x, y): (f32, f32)| {
+            pos2(
+                remap(x, x_range.clone(), plot_rect.range_x()),
+                remap(y, y_range.clone(), plot_rect.bottom()..=plot_rect.top()),
+            )
+        };
+
+        ui.add_paint_cmd(PaintCmd::Rect {
+            rect: plot_rect,
+            corner_radius: 0.0,
+            fill: Some(ui.style().dark_bg_color),
+            outline: Some(ui.style().thin_outline),
+        });
+
+        for (y, text) in y_labels {
+            let screen_y = to_screen((*x_range.start(), y)).y;
+            ui.add_paint_cmd(PaintCmd::line_segment(
+                [
+                    pos2(plot_rect.left(), screen_y),
+                    pos2(plot_rect.right(), screen_y),
+                ],
+                grid_color,
+                1.0,
+            ));
+            ui.floating_text(
+                pos2(plot_rect.left() - 4.0, screen_y),
+                text,
+                text_style,
+                (Align::Max, Align::Center),
+                Some(text_color),
+            );
+        }
+
+        let x_step = grid_step(&x_range, (plot_rect.width() / 80.0).floor());
+        for x in grid_values(&x_range, x_step) {
+            let screen_x = to_screen((x, *y_range.start())).x;
+            ui.add_paint_cmd(PaintCmd::line_segment(
+                [
+                    pos2(screen_x, plot_rect.top()),
+                    pos2(screen_x, plot_rect.bottom()),
+                ],
+                grid_color,
+                1.0,
+            ));
+            ui.floating_text(
+                pos2(screen_x, plot_rect.bottom() + 2.0),
+                format!("{:.*}", decimals(x_step), x),
+                text_style,
+                (Align::Center, Align::Min),
+                Some(text_color),
+            );
+        }
+
+        // Closest point to the mouse, as (screen position, series index, value):
+        let mut hovered: Option<(Pos2, usize, (f32, f32))> = None;
+        let mouse_pos = ui.input().mouse.pos.filter(|_| interact.hovered);
+        let mut closest_distance = HOVER_DISTANCE;
+
+        for (i, series) in series.iter().enumerate() {
+            if state.hidden.contains(&series.name) {
+                continue;
+            }
+            let color = series_color(i);
+            let line_width = if highlighted == Some(i) { 3.0 } else { 1.5 };
+            let points: Vec<(Pos2, (f32, f32))> = series
+                .points
+                .iter()
+                .filter(|(x, y)| x.is_finite() && y.is_finite())
+                .map(|&point| (to_screen(point), point))
+                .collect();
+
+            if let Some(mouse_pos) = mouse_pos {
+                for &(pos, value) in &points {
+                    let distance = pos.distance(mouse_pos);
+                    if distance < closest_distance {
+                        closest_distance = distance;
+                        hovered = Some((pos, i, value));
+                    }
+                }
+            }
+
+            let screen_points: Vec<Pos2> = points.iter().map(|(pos, _)| *pos).collect();
+            if screen_points.len() >= 2 {
+                ui.add_paint_cmd(PaintCmd::Path {
+                    path: Path::from_open_points(&screen_points),
+                    closed: false,
+                    fill: None,
+                    outline: Some(LineStyle::new(line_width, color)),
+                });
+            } else if let Some(&center) = screen_points.first() {
+                ui.add_paint_cmd(PaintCmd::Circle {
+                    center,
+                    radius: line_width + 0.5,
+                    fill: Some(color),
+                    outline: None,
+                });
+            }
+        }
+
+        if let Some((pos, i, (x, y))) = hovered {
+            ui.add_paint_cmd(PaintCmd::Circle {
+                center: pos,
+                radius: 4.0,
+                fill: None,
+                outline: Some(LineStyle::new(1.5, series_color(i))),
+            });
+            // One more decimal than the grid labels:
+            let text = format!(
+                "{}\nx: {:.*}\ny: {:.*}",
+                series[i].name,
+                decimals(x_step) + 1,
+                x,
+                decimals(y_step) + 1,
+                y
+            );
+            show_tooltip(ui.ctx(), |ui| {
+                ui.add(Label::new(text).text_style(TextStyle::Monospace));
+            });
+        }
+
+        interact
+    }
+}
+
+/// The ranges of x and y that fit all the finite points.
+fn bounds(series: &[&Series], include_y_zero: bool) -> (RangeInclusive<f32>, RangeInclusive<f32>) {
+    let mut min = pos2(f32::INFINITY, f32::INFINITY);
+    let mut max = pos2(f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for &(x, y) in series.iter().flat_map(|series| &series.points) {
+        if x.is_finite() && y.is_finite() {
+            min = min.min(pos2(x, y));
+            max = max.max(pos2(x, y));
+        }
+    }
+    if include_y_zero {
+        min.y = min.y.min(0.0);
+        max.y = max.y.max(0.0);
+    }
+
+    let x_range = widen(min.x, max.x);
+    let y_range = widen(min.y, max.y);
+    // Some room above and below the lines:
+    let y_margin = 0.05 * (y_range.end() - y_range.start());
+    (
+        x_range,
+        y_range.start() - y_margin..=y_range.end() + y_margin,
+    )
+}
+
+/// Make sure we don't divide by zero when mapping to the screen.
+fn widen(min: f32, max: f32) -> RangeInclusive<f32> {
+    if !min.is_finite() || !max.is_finite() {
+        0.0..=1.0
+    } else if min == max {
+        let half = 0.5 * min.abs().max(1.0);
+        min - half..=max + half
+    } else {
+        min..=max
+    }
+}
+
+/// Grid lines closer than this are not drawn.
+const MIN_GRID_STEP: f32 = 1e-30;
+
+/// Most grid lines in one direction, in case the plot is huge.
+const MAX_GRID_LINES: i64 = 1000;
+
+/// A nice distance between grid lines (1, 2 or 5 times a power of ten),
+/// so that there are at most `max_lines` of them.
+/// `None` if there is no such step, e.g. for an empty or infinite range.
+fn grid_step(range: &RangeInclusive<f32>, max_lines: f32) -> Option<f32> {
+    let min_step = (range.end() - range.start()) / max_lines.max(1.0);
+    if !min_step.is_finite() || min_step < MIN_GRID_STEP {
+        return None;
+    }
+    let magnitude = 10.0_f32.powf(min_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|&step| step >= min_step)
+        .unwrap_or(10.0 * magnitude);
+    Some(step).filter(|step| step.is_finite() && *step >= MIN_GRID_STEP)
+}
+
+/// No values at all without a `step`.
+fn grid_values(range: &RangeInclusive<f32>, step: Option<f32>) -> impl Iterator<Item = f32> {
+    let (step, first, last) = match step {
+        Some(step) if range.start().is_finite() && range.end().is_finite() => {
+            let first = (range.start() / step).ceil() as i64;
+            let last = (range.end() / step).floor() as i64;
+            (step, first, last.min(first + MAX_GRID_LINES - 1))
+        }
+        _ => (1.0, 0, -1),
+    };
+    (first..=last).map(move |i| i as f32 * step)
+}
+
+/// Enough decimals to tell grid lines `step` apart.
+fn decimals(step: Option<f32>) -> usize {
+    match step {
+        Some(step) if step.is_finite() && step > 0.0 => (-step.log10()).ceil().max(0.0) as usize,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_grid_without_a_finite_step() {
+        assert_eq!(grid_step(&(0.0..=0.0), 10.0), None);
+        assert_eq!(grid_step(&(0.0..=f32::INFINITY), 10.0), None);
+        assert_eq!(grid_values(&(0.0..=1.0), None).count(), 0);
+        assert_eq!(decimals(None), 0);
+        assert_eq!(grid_step(&(0.0..=10.0), 5.0), Some(2.0));
+        assert_eq!(grid_values(&(0.0..=10.0), Some(2.0)).count(), 6);
+    }
+
+    #[test]
+    fn plot_in_area() {
+        let mut ctx = Context::new();
+        let guard = ctx.begin_frame(RawInput {
+            screen_size: vec2(400.0, 300.0),
+            ..Default::default()
+        });
+        Area::new("area").show(guard.ctx(), |ui| {
+            ui.add(Plot::new().series(Series::new("line", vec![(0.0, 0.0), (1.0, 1.0)])));
+        });
+        guard.end();
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{paint::*, widgets::Widget, *};
+
+/// A horizontal bar showing how far along a task is.
+///
+/// Usage: `ui.add(ProgressBar::new(0.5).show_percentage(true))`
+///
+/// Use `ProgressBar::indeterminate()` when you don't know how long the task will take:
+/// it shows a stripe sliding back and forth instead.
+pub struct ProgressBar {
+    /// In 0..=1, or `None` for indeterminate.
+    progress: Option<f32>,
+    show_percentage: bool,
+    text: Option<String>,
+    desired_width: Option<f32>,
+}
+
+impl ProgressBar {
+    /// `progress` in the range `0.0..=1.0`
+    pub fn new(progress: f32) -> Self {
+        Self {
+            progress: Some(progress),
+            show_percentage: false,
+            text: None,
+            desired_width: None,
+        }
+    }
+
+    /// For tasks of unknown duration
+    pub fn indeterminate() -> Self {
+        Self {
+            progress: None,
+            ..Self::new(0.0)
+        }
+    }
+
+    /// Show e.g. "42%" on top of the bar. Ignored for indeterminate bars.
+    pub fn show_percentage(mut self, show_percentage: bool) -> Self {
+        self.show_percentage = show_percentage;
+        self
+    }
+
+    /// Show this text on top of the bar (instead of the percentage).
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Defaults to the available width.
+    pub fn desired_width(mut self, desired_width: f32) -> Self {
+        self.desired_width = Some(desired_width);
+        self
+    }
+}
+
+impl Widget for ProgressBar {
+    fn ui(self, ui: &mut Ui) -> InteractInfo {
+        let ProgressBar {
+            progress,
+            show_percentage,
+            text,
+            desired_width,
+        } = self;
+
+        let text = text.or_else(|| {
+            progress
+                .filter(|_| show_percentage)
+                .map(|progress| format!("{:.0}%", 100.0 * clamp(progress, 0.0..=1.0)))
+        });
+
+        let text_style = TextStyle::Button;
+        let galley = text.map(|text| ui.fonts()[text_style].layout_single_line(text));
+
+        let height = ui.style().clickable_diameter;
+        let width = desired_width.unwrap_or_else(|| ui.available().width());
+        let rect = ui.allocate_space(vec2(width, height));
+        let interact = ui.interact_hover(rect);
+
+        let corner_radius = ui.style().interact.inactive.corner_radius;
+        ui.add_paint_cmd(PaintCmd::Rect {
+            rect,
+            corner_radius,
+            fill: Some(ui.style().dark_bg_color),
+            outline: Some(ui.style().thin_outline),
+        });
+
+        let (left, right) = match progress {
+            Some(progress) => (0.0, clamp(progress, 0.0..=1.0)),
+            None => {
+                // A stripe a third of the width, bouncing back and forth once per second:
+                let t = ui.input().time.fract() as f32;
+                let t = if t < 0.5 { 2.0 * t } else { 2.0 - 2.0 * t };
+                let left = t * 2.0 / 3.0;
+                (left, left + 1.0 / 3.0)
+            }
+        };
+
+        if left < right {
+            let fill_rect = Rect::from_min_max(
+                pos2(lerp(rect.range_x(), left), rect.top()),
+                pos2(lerp(rect.range_x(), right), rect.bottom()),
+            );
+            ui.add_paint_cmd(PaintCmd::Rect {
+                rect: fill_rect.expand(-1.0),
+                corner_radius,
+                fill: Some(ui.style().interact.active.fill),
+                outline: None,
+            });
+        }
+
+        if let Some(galley) = galley {
+            let text_pos = rect.center() - 0.5 * galley.size;
+            let text_color = ui.style().text_color;
+            ui.add_galley(text_pos, galley, text_style, Some(text_color));
+        }
+
+        interact
+    }
+}
@@ -0,0 +1,173 @@
+use std::ops::RangeInclusive;
+
+use crate::{paint::*, widgets::Label, *};
+
+/// Edit a `(min, max)` pair with two handles on the same rail.
+///
+/// Usage: `ui.add(RangeSlider::new(&mut self.filter, 0.0..=100.0).text("filter"))`
+///
+/// The handles can not be dragged past each other.
+pub struct RangeSlider<'a> {
+    value: &'a mut (f32, f32),
+    range: RangeInclusive<f32>,
+    text: Option<String>,
+    precision: usize,
+    id: Option<Id>,
+}
+
+impl<'a> RangeSlider<'a> {
+    pub fn new(value: &'a mut (f32, f32), range: RangeInclusive<f32>) -> Self {
+        Self {
+            value,
+            range,
+            text: None,
+            precision: 3,
+            id: None,
+        }
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Needed if there are several range sliders with the same text in the same `Ui`
+    pub fn id(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id = Some(Id::new(id_source));
+        self
+    }
+}
+
+impl<'a> Widget for RangeSlider<'a> {
+    fn ui(mut self, ui: &mut Ui) -> InteractInfo {
+        if let Some(text) = self.text.take() {
+            if self.id.is_none() {
+                self.id = Some(ui.make_unique_child_id(&text));
+            }
+
+            let full_text = format!(
+                "{}: {:.*} - {:.*}",
+                text, self.precision, self.value.0, self.precision, self.value.1
+            );
+
+            ui.columns(2, |columns| {
+                let slider_response = columns[0].add(self);
+
+                // Place the text in line with the slider on the left:
+                columns[1].set_desired_height(slider_response.rect.height());
+                columns[1].inner_layout(Layout::horizontal(Align::Center), |ui| {
+                    ui.add(Label::new(full_text).multiline(false));
+                });
+
+                slider_response.into()
+            })
+        } else {
+            let RangeSlider {
+                value, range, id, ..
+            } = self;
+            debug_assert!(range.start() <= range.end());
+
+            let height = ui.fonts()[TextStyle::Button]
+                .line_spacing()
+                .max(ui.style().clickable_diameter);
+            let handle_radius = height / 2.5;
+
+            let id = match id {
+                Some(id_source) => ui.make_child_id(id_source),
+                None => ui.make_position_id(),
+            };
+            let rect = ui.allocate_space(vec2(ui.available().width(), height));
+            let interact = ui.interact(rect, id, Sense::click_and_drag());
+
+            let left = interact.rect.left() + handle_radius;
+            let right = interact.rect.right() - handle_radius;
+
+            let value_before = *value;
+            let mouse = &ui.input().mouse;
+            if let Some(mouse_pos) = mouse.pos {
+                // Jump to the press, but ignore jitters until it becomes a drag:
+                if interact.active && (mouse.pressed || mouse.is_dragging) {
+                    let mouse_value = remap_clamp(mouse_pos.x, left..=right, range.clone());
+
+                    let handle = match ui.memory().interaction.drag_handle {
+                        Some(handle) => handle,
+                        None => {
+                            // Grab whichever handle is closest:
+                            let handle = if value.0 == value.1 {
+                                // On top of each other: pick the one that can move that way.
+                                if mouse_value < value.0 {
+                                    0
+                                } else {
+                                    1
+                                }
+                            } else if (mouse_value - value.0).abs() <= (mouse_value - value.1).abs()
+                            {
+                                0
+                            } else {
+                                1
+                            };
+                            ui.memory().interaction.drag_handle = Some(handle);
+                            handle
+                        }
+                    };
+
+                    if handle == 0 {
+                        value.0 = mouse_value.min(value.1);
+                    } else {
+                        value.1 = mouse_value.max(value.0);
+                    }
+                }
+            }
+
+            // Paint it:
+            {
+                let rail_radius = ui.round_to_pixel((height / 8.0).max(2.0));
+                let rail_rect = Rect::from_min_max(
+                    pos2(interact.rect.left(), rect.center().y - rail_radius),
+                    pos2(interact.rect.right(), rect.center().y + rail_radius),
+                );
+                let min_x = remap_clamp(value.0, range.clone(), left..=right);
+                let max_x = remap_clamp(value.1, range, left..=right);
+
+                ui.add_paint_cmd(PaintCmd::Rect {
+                    rect: rail_rect,
+                    corner_radius: rail_radius,
+                    fill: Some(ui.style().background_fill),
+                    outline: Some(LineStyle::new(1.0, color::gray(200, 255))), // TODO
+                });
+
+                // The selected range:
+                ui.add_paint_cmd(PaintCmd::Rect {
+                    rect: Rect::from_min_max(
+                        pos2(min_x, rail_rect.top()),
+                        pos2(max_x, rail_rect.bottom()),
+                    ),
+                    corner_radius: rail_radius,
+                    fill: Some(ui.style().interact.active.fill),
+                    outline: None,
+                });
+
+                let style = *ui.style().interact(&interact);
+                for &x in &[min_x, max_x] {
+                    ui.add_paint_cmd(PaintCmd::Circle {
+                        center: pos2(x, rail_rect.center().y),
+                        radius: handle_radius,
+                        fill: Some(style.fill),
+                        outline: Some(LineStyle::new(style.stroke_width, style.stroke_color)),
+                    });
+                }
+            }
+
+            InteractInfo {
+                changed: *value != value_before,
+                committed: interact.active && ui.input().mouse.released,
+                ..interact
+            }
+        }
+    }
+}
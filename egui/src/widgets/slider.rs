@@ -6,6 +6,15 @@ use crate::{paint::*, widgets::Label, *};
 /// for the borrow checker.
 type SliderGetSet<'a> = Box<dyn 'a + FnMut(Option<f32>) -> f32>;
 
+/// While the value of a `Slider` is being typed in. See `Slider::editable_value`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct State {
+    edit_text: String,
+}
+
+/// Steps are marked on the rail if there are no more than this many of them.
+const MAX_STEP_TICKS: f32 = 20.0;
+
 pub struct Slider<'a> {
     get_set_value: SliderGetSet<'a>,
     range: RangeInclusive<f32>,
@@ -15,6 +24,54 @@ pub struct Slider<'a> {
     text_color: Option<Color>,
     text_on_top: Option<bool>,
     id: Option<Id>,
+    ticks: Vec<Tick>,
+    snap_distance: f32,
+    step: Option<f32>,
+    editable_value: bool,
+    vertical: bool,
+    scale: Scale,
+    small: bool,
+}
+
+/// How the values are spread out along the rail
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Scale {
+    Linear,
+    /// Each order of magnitude gets the same length.
+    /// Only for ranges where both ends are positive: other ranges are linear.
+    Logarithmic,
+}
+
+impl Scale {
+    /// Where along the rail the value is, in `0..=1`
+    fn normalized_from_value(self, value: f32, range: &RangeInclusive<f32>) -> f32 {
+        let (min, max) = (*range.start(), *range.end());
+        if min == max {
+            return 0.0;
+        }
+        if self == Scale::Logarithmic && min > 0.0 {
+            remap_clamp(value.max(min).ln(), min.ln()..=max.ln(), 0.0..=1.0)
+        } else {
+            remap_clamp(value, min..=max, 0.0..=1.0)
+        }
+    }
+
+    /// The inverse of `normalized_from_value`
+    fn value_from_normalized(self, t: f32, range: &RangeInclusive<f32>) -> f32 {
+        let (min, max) = (*range.start(), *range.end());
+        if self == Scale::Logarithmic && min > 0.0 {
+            clamp(lerp(min.ln()..=max.ln(), t).exp(), min..=max)
+        } else {
+            lerp(min..=max, t)
+        }
+    }
+}
+
+/// A mark on the rail of a `Slider`
+struct Tick {
+    value: f32,
+    /// Labeled ticks are detents: the slider snaps to them.
+    label: Option<String>,
 }
 
 impl<'a> Slider<'a> {
@@ -30,6 +87,13 @@ impl<'a> Slider<'a> {
             text_on_top: None,
             text_color: None,
             id: None,
+            ticks: vec![],
+            snap_distance: 6.0,
+            step: None,
+            editable_value: false,
+            vertical: false,
+            scale: Scale::Linear,
+            small: false,
         }
     }
 
@@ -49,6 +113,7 @@ impl<'a> Slider<'a> {
         let range = (*range.start() as f32)..=(*range.end() as f32);
         Slider {
             precision: 0,
+            step: Some(1.0),
             ..Self::from_get_set(range, move |v: Option<f32>| {
                 if let Some(v) = v {
                     *value = v.round() as i32
@@ -62,6 +127,7 @@ impl<'a> Slider<'a> {
         let range = (*range.start() as f32)..=(*range.end() as f32);
         Slider {
             precision: 0,
+            step: Some(1.0),
             ..Self::from_get_set(range, move |v: Option<f32>| {
                 if let Some(v) = v {
                     *value = v.round() as usize
@@ -86,6 +152,89 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Paint a tick mark next to the rail at this value.
+    pub fn tick(mut self, value: f32) -> Self {
+        self.ticks.push(Tick { value, label: None });
+        self
+    }
+
+    /// A labeled tick mark that the slider snaps to
+    /// when dragged to within `snap_distance` of it.
+    pub fn detent(mut self, value: f32, label: impl Into<String>) -> Self {
+        self.ticks.push(Tick {
+            value,
+            label: Some(label.into()),
+        });
+        self
+    }
+
+    /// How close (in points) to a detent the slider must be dragged to snap to it.
+    pub fn snap_distance(mut self, snap_distance: f32) -> Self {
+        self.snap_distance = snap_distance;
+        self
+    }
+
+    /// Only allow values that are a whole number of `step`:s from the start of the range.
+    /// If there are few enough steps, they are marked on the rail.
+    /// Integer sliders have a step of 1 by default.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = Some(step).filter(|&step| step > 0.0);
+        self
+    }
+
+    /// Click the value next to the slider to type in an exact number instead.
+    /// Press return to use it, or escape to keep the old value.
+    /// The typed value is clamped to the range and snapped to the `step`.
+    /// It is read leniently (see `lenient_number`), and if it is not a number the old value is kept.
+    /// Pasting something that is not a number does nothing, but makes the field flash.
+    /// Only for sliders with a `text`.
+    pub fn editable_value(mut self, editable_value: bool) -> Self {
+        self.editable_value = editable_value;
+        self
+    }
+
+    /// Lay out the rail vertically, with the largest value at the top.
+    /// Takes up the available height (or 200 points if that is unbounded).
+    /// Any text will be put on top of the slider.
+    pub fn vertical(mut self) -> Self {
+        self.vertical = true;
+        self
+    }
+
+    /// Spread the values logarithmically along the rail, so that e.g. `0.001..=1000.0`
+    /// gets as much room for `0.001..=0.01` as for `100.0..=1000.0`.
+    /// Only works for ranges where both ends are positive.
+    pub fn logarithmic(mut self, logarithmic: bool) -> Self {
+        self.scale = if logarithmic {
+            Scale::Logarithmic
+        } else {
+            Scale::Linear
+        };
+        self
+    }
+
+    /// Smaller text and a thinner rail (see `Style::compact_spacing`), for dense panels.
+    pub fn small(mut self) -> Self {
+        self.small = true;
+        self
+    }
+
+    /// The values of all steps, if there are few enough to mark on the rail.
+    fn step_values(&self) -> Vec<f32> {
+        let (min, max) = (*self.range.start(), *self.range.end());
+        match self.step {
+            Some(step) if (max - min) / step <= MAX_STEP_TICKS => {
+                let count = ((max - min) / step).floor() as usize;
+                (0..=count).map(|i| min + i as f32 * step).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    fn snap_to_step(&self, value: f32) -> f32 {
+        snap_to_step(value, &self.range, self.step)
+    }
+
     fn get_value_f32(&mut self) -> f32 {
         (self.get_set_value)(None)
     }
@@ -100,26 +249,48 @@ impl<'a> Slider<'a> {
 
 impl<'a> Widget for Slider<'a> {
     fn ui(mut self, ui: &mut Ui) -> InteractInfo {
-        let text_style = TextStyle::Button;
+        let (text_style, label_style) = if self.small {
+            (TextStyle::Small, TextStyle::Small)
+        } else {
+            (TextStyle::Button, TextStyle::Body)
+        };
         let font = &ui.fonts()[text_style];
 
-        if let Some(text) = &self.text {
-            if self.id.is_none() {
-                self.id = Some(ui.make_unique_child_id(text));
-            }
+        if let Some(text) = self.text.clone() {
+            let id = *self
+                .id
+                .get_or_insert_with(|| ui.make_unique_child_id(&text));
+            ui.register_widget_label(id, &text);
 
-            let text_on_top = self.text_on_top.unwrap_or_default();
+            let text_on_top = self.text_on_top.unwrap_or(self.vertical);
             let text_color = self.text_color;
+            let style_label = move |label: Label| {
+                let label = label.text_style(label_style);
+                match text_color {
+                    Some(text_color) => label.text_color(text_color),
+                    None => label,
+                }
+            };
+            let editable_value = self.editable_value;
+            let precision = self.precision;
+            let (range, step) = (self.range.clone(), self.step);
             let value = (self.get_set_value)(None);
-            let full_text = format!("{}: {:.*}", text, self.precision, value);
 
-            let slider_sans_text = Slider { text: None, ..self };
+            // Borrowed by the slider, so we can set a typed value after it is done:
+            let mut get_set_value = std::mem::replace(&mut self.get_set_value, Box::new(|_| 0.0));
+            let slider_sans_text = Slider {
+                text: None,
+                get_set_value: Box::new(&mut get_set_value),
+                ..self
+            };
 
-            if text_on_top {
-                let galley = font.layout_single_line(full_text);
-                let pos = ui.allocate_space(galley.size).min;
-                ui.add_galley(pos, galley, text_style, text_color);
-                slider_sans_text.ui(ui)
+            let (mut slider_response, typed_value) = if text_on_top {
+                let typed_value = ui
+                    .horizontal(|ui| {
+                        value_ui(ui, id, &text, value, precision, editable_value, style_label)
+                    })
+                    .0;
+                (slider_sans_text.ui(ui), typed_value)
             } else {
                 ui.columns(2, |columns| {
                     // Slider on the left:
@@ -127,35 +298,136 @@ impl<'a> Widget for Slider<'a> {
 
                     // Place the text in line with the slider on the left:
                     columns[1].set_desired_height(slider_response.rect.height());
-                    columns[1].inner_layout(Layout::horizontal(Align::Center), |ui| {
-                        ui.add(Label::new(full_text).multiline(false));
-                    });
+                    let typed_value = columns[1]
+                        .inner_layout(Layout::horizontal(Align::Center), |ui| {
+                            value_ui(ui, id, &text, value, precision, editable_value, style_label)
+                        })
+                        .0;
 
-                    slider_response.into()
+                    (slider_response.into(), typed_value)
                 })
+            };
+
+            if let Some(typed_value) = typed_value {
+                let typed_value = snap_to_step(clamp(typed_value, range.clone()), &range, step);
+                let typed_value = if precision == 0 {
+                    typed_value.round()
+                } else {
+                    typed_value
+                };
+                get_set_value(Some(typed_value));
+                slider_response.changed = true;
+                slider_response.committed = true;
             }
+            slider_response
         } else {
-            let height = font.line_spacing().max(ui.style().clickable_diameter);
-            let handle_radius = height / 2.5;
+            let vertical = self.vertical;
+            let clickable_diameter = if self.small {
+                ui.style().compact_spacing.clickable_diameter
+            } else {
+                ui.style().clickable_diameter
+            };
+            let thickness = font.line_spacing().max(clickable_diameter);
+            let handle_radius = thickness / 2.5;
 
             let id = self.id.unwrap_or_else(|| ui.make_position_id());
 
-            let size = Vec2 {
-                x: ui.available().width(),
-                y: height,
+            // Room for the labels below (or to the right of a vertical) slider:
+            let label_galleys: Vec<_> = self
+                .ticks
+                .iter()
+                .map(|tick| {
+                    tick.label
+                        .as_ref()
+                        .map(|label| ui.fonts()[label_style].layout_single_line(label.clone()))
+                })
+                .collect();
+            let labels_thickness = label_galleys
+                .iter()
+                .flatten()
+                .map(|galley| {
+                    if vertical {
+                        galley.size.x
+                    } else {
+                        galley.size.y
+                    }
+                })
+                .fold(0.0, f32::max);
+
+            let size = if vertical {
+                let height = ui.available().height();
+                let height = if height.is_finite() { height } else { 200.0 };
+                vec2(thickness + labels_thickness, height)
+            } else {
+                vec2(ui.available().width(), thickness + labels_thickness)
             };
             let rect = ui.allocate_space(size);
             let interact = ui.interact(rect, id, Sense::click_and_drag());
 
-            let left = interact.rect.left() + handle_radius;
-            let right = interact.rect.right() - handle_radius;
+            // The part of `rect` with the rail, i.e. excluding the labels:
+            let rail_area = if vertical {
+                Rect::from_min_size(rect.min, vec2(thickness, size.y))
+            } else {
+                Rect::from_min_size(rect.min, vec2(size.x, thickness))
+            };
+
+            // Screen coordinates along the rail, and a point from (along, across):
+            let along = |pos: Pos2| if vertical { pos.y } else { pos.x };
+            let point = |along: f32, across: f32| {
+                if vertical {
+                    pos2(across, along)
+                } else {
+                    pos2(along, across)
+                }
+            };
 
             let range = self.range.clone();
             debug_assert!(range.start() <= range.end());
 
-            if let Some(mouse_pos) = ui.input().mouse.pos {
-                if interact.active {
-                    self.set_value_f32(remap_clamp(mouse_pos.x, left..=right, range.clone()));
+            // Vertical sliders have the largest value at the top:
+            let scale = self.scale;
+            let position_from_value = |value: f32| {
+                let t = scale.normalized_from_value(value, &range);
+                if vertical {
+                    let (top, bottom) = (rect.top() + handle_radius, rect.bottom() - handle_radius);
+                    lerp(bottom..=top, t)
+                } else {
+                    let (left, right) = (rect.left() + handle_radius, rect.right() - handle_radius);
+                    lerp(left..=right, t)
+                }
+            };
+            let value_from_position = |position: f32| {
+                let t = if vertical {
+                    let (top, bottom) = (rect.top() + handle_radius, rect.bottom() - handle_radius);
+                    remap_clamp(position, top..=bottom, 1.0..=0.0)
+                } else {
+                    let (left, right) = (rect.left() + handle_radius, rect.right() - handle_radius);
+                    remap_clamp(position, left..=right, 0.0..=1.0)
+                };
+                scale.value_from_normalized(t, &range)
+            };
+
+            let value_before = self.get_value_f32();
+            let mouse = &ui.input().mouse;
+            if let Some(mouse_pos) = mouse.pos {
+                // Jump to the press, but ignore jitters until it becomes a drag:
+                if interact.active && (mouse.pressed || mouse.is_dragging) {
+                    let mouse_along = along(mouse_pos);
+                    let mut value = self.snap_to_step(value_from_position(mouse_along));
+                    let detent = self
+                        .ticks
+                        .iter()
+                        .filter(|tick| tick.label.is_some() && range.contains(&tick.value))
+                        .map(|tick| {
+                            let position = position_from_value(tick.value);
+                            (tick.value, (position - mouse_along).abs())
+                        })
+                        .filter(|(_, distance)| *distance <= self.snap_distance)
+                        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+                    if let Some((detent_value, _)) = detent {
+                        value = detent_value;
+                    }
+                    self.set_value_f32(value);
                 }
             }
 
@@ -163,13 +435,64 @@ impl<'a> Widget for Slider<'a> {
             {
                 let value = self.get_value_f32();
 
-                let rect = interact.rect;
-                let rail_radius = ui.round_to_pixel((height / 8.0).max(2.0));
+                let rail_radius = ui.round_to_pixel((thickness / 8.0).max(2.0));
+                let (rail_start, rail_end, rail_center, rail_area_end) = if vertical {
+                    (
+                        rect.top(),
+                        rect.bottom(),
+                        rail_area.center().x,
+                        rail_area.right(),
+                    )
+                } else {
+                    (
+                        rect.left(),
+                        rect.right(),
+                        rail_area.center().y,
+                        rail_area.bottom(),
+                    )
+                };
                 let rail_rect = Rect::from_min_max(
-                    pos2(interact.rect.left(), rect.center().y - rail_radius),
-                    pos2(interact.rect.right(), rect.center().y + rail_radius),
+                    point(rail_start, rail_center - rail_radius),
+                    point(rail_end, rail_center + rail_radius),
                 );
-                let marker_center_x = remap_clamp(value, range, left..=right);
+                let marker_center = point(position_from_value(value), rail_center);
+
+                let tick_color = ui.style().text_color;
+                let step_ticks = self
+                    .step_values()
+                    .into_iter()
+                    .map(|value| (Tick { value, label: None }, None));
+                let ticks = self.ticks.drain(..).zip(label_galleys).chain(step_ticks);
+                for (tick, galley) in ticks {
+                    if !range.contains(&tick.value) {
+                        continue;
+                    }
+                    let position = ui.round_to_pixel(position_from_value(tick.value));
+                    ui.add_paint_cmd(PaintCmd::line_segment(
+                        [
+                            point(position, rail_center + rail_radius),
+                            point(position, rail_area_end),
+                        ],
+                        tick_color,
+                        ui.style().thin_outline.width,
+                    ));
+
+                    if let Some(galley) = galley {
+                        // Centered on the tick, but not outside the slider:
+                        let label_pos = if vertical {
+                            let y = (position - 0.5 * galley.size.y)
+                                .max(rect.top())
+                                .min(rect.bottom() - galley.size.y);
+                            pos2(rail_area.right(), y)
+                        } else {
+                            let x = (position - 0.5 * galley.size.x)
+                                .max(rect.left())
+                                .min(rect.right() - galley.size.x);
+                            pos2(x, rail_area.bottom())
+                        };
+                        ui.add_galley(label_pos, galley, label_style, Some(tick_color));
+                    }
+                }
 
                 ui.add_paint_cmd(PaintCmd::Rect {
                     rect: rail_rect,
@@ -179,7 +502,7 @@ impl<'a> Widget for Slider<'a> {
                 });
 
                 ui.add_paint_cmd(PaintCmd::Circle {
-                    center: pos2(marker_center_x, rail_rect.center().y),
+                    center: marker_center,
                     radius: handle_radius,
                     fill: Some(ui.style().interact(&interact).fill),
                     outline: Some(LineStyle::new(
@@ -189,7 +512,86 @@ impl<'a> Widget for Slider<'a> {
                 });
             }
 
-            interact
+            InteractInfo {
+                changed: self.get_value_f32() != value_before,
+                committed: interact.active && ui.input().mouse.released,
+                ..interact
+            }
+        }
+    }
+}
+
+fn snap_to_step(value: f32, range: &RangeInclusive<f32>, step: Option<f32>) -> f32 {
+    match step {
+        Some(step) => {
+            let min = *range.start();
+            let value = min + ((value - min) / step).round() * step;
+            clamp(value, range.clone())
+        }
+        None => value,
+    }
+}
+
+/// The "text: value" next to a slider.
+/// Returns the value typed in by the user, if they just pressed return.
+fn value_ui(
+    ui: &mut Ui,
+    slider_id: Id,
+    text: &str,
+    value: f32,
+    precision: usize,
+    editable: bool,
+    style_label: impl Fn(Label) -> Label,
+) -> Option<f32> {
+    let edit_id = slider_id.with("value");
+    let state = ui.memory().sliders.get(&slider_id).cloned();
+
+    let mut state = match state {
+        Some(state) if editable => state,
+        _ => {
+            let value_text = format!("{}: {:.*}", text, precision, value);
+            let response = ui.add(style_label(Label::new(value_text).multiline(false)));
+            if editable {
+                let interact = ui.interact(response.rect, edit_id, Sense::click());
+                if interact.hovered {
+                    ui.output().cursor_icon = CursorIcon::Text;
+                }
+                if interact.clicked {
+                    let edit_text = format!("{:.*}", precision, value);
+                    ui.memory().sliders.insert(slider_id, State { edit_text });
+                    ui.request_kb_focus(TextEdit::id_for(ui, edit_id));
+                }
+            }
+            return None;
         }
+    };
+
+    ui.add(style_label(
+        Label::new(format!("{}:", text)).multiline(false),
+    ));
+    let text_edit_id = TextEdit::id_for(ui, edit_id);
+    ui.add(
+        TextEdit::new(&mut state.edit_text)
+            .id(edit_id)
+            .multiline(false)
+            .single_line_paste(SingleLinePaste::Value(lenient_number)),
+    );
+
+    let mut typed_value = None;
+    let mut done = !ui.has_kb_focus(text_edit_id);
+    if ui.input().key_pressed(Key::Return) {
+        typed_value = lenient_number(&state.edit_text)
+            .and_then(|number| number.parse().ok())
+            .filter(|v: &f32| v.is_finite());
+        done = true;
+    } else if ui.ctx().consume_key(Key::Escape) {
+        done = true;
+    }
+
+    if done {
+        ui.memory().sliders.remove(&slider_id);
+    } else {
+        ui.memory().sliders.insert(slider_id, state);
     }
+    typed_value
 }
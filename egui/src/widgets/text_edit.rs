@@ -1,20 +1,118 @@
 use crate::{paint::*, *};
 
-#[derive(Clone, Copy, Debug, Default)]
+/// How long (in seconds) a field flashes when something pasted into it is rejected.
+const REJECTED_PASTE_FLASH_TIME: f64 = 0.5;
+
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "with_serde", derive(serde::Deserialize, serde::Serialize))]
 pub(crate) struct State {
     /// Charctaer based, NOT bytes.
     /// TODO: store as line + row
     pub cursor: Option<usize>,
+    /// So we know when the keyboard focus is lost, which commits the edit.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub had_kb_focus: bool,
+    /// Text being composed with an IME, shown underlined at the cursor until it is done.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub preedit: String,
+    /// Where the double- or triple-clicked selection starts. It ends at the cursor.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub selection_anchor: Option<usize>,
+    /// `InputState::time` when a paste was last rejected, for flashing the field.
+    #[cfg_attr(feature = "with_serde", serde(skip))]
+    pub rejected_paste_time: Option<f64>,
+}
+
+/// What a single-line `TextEdit` does with pasted text that contains line breaks.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum SingleLinePaste {
+    /// Remove the line breaks, keeping all the text.
+    #[default]
+    StripNewlines,
+    /// Only keep the text before the first line break.
+    FirstLine,
+    /// For fields holding a single value, e.g. a number: the pasted text replaces all of the field,
+    /// cleaned up by the function (see `lenient_number`).
+    /// If the function returns `None` the paste is rejected, and the field flashes.
+    Value(fn(&str) -> Option<String>),
+}
+
+impl SingleLinePaste {
+    fn sanitize(self, text: &str) -> String {
+        match self {
+            SingleLinePaste::StripNewlines | SingleLinePaste::Value(_) => {
+                text.chars().filter(|c| *c != '\n' && *c != '\r').collect()
+            }
+            SingleLinePaste::FirstLine => text.lines().next().unwrap_or_default().to_owned(),
+        }
+    }
+}
+
+/// Clean up pasted text that should be a number, for `SingleLinePaste::Value`.
+///
+/// Only the first line is used. Whitespace, `_` and `'` are ignored, and so is a leading `+`.
+/// Commas are thousands separators, except for a single one without a `.`,
+/// which is a decimal comma (so `"1,5"` is `1.5`, but `"1,000.5"` is `1000.5`).
+/// Returns `None` if the rest is not a finite number.
+pub fn lenient_number(text: &str) -> Option<String> {
+    let mut number: String = text
+        .lines()
+        .next()?
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_' && *c != '\'')
+        .map(|c| if c == '\u{2212}' { '-' } else { c }) // Unicode minus sign
+        .collect();
+    if number.contains('.') || number.matches(',').count() > 1 {
+        number.retain(|c| c != ',');
+    } else {
+        number = number.replace(',', ".");
+    }
+    let number = number.strip_prefix('+').unwrap_or(&number).to_owned();
+    let value: f64 = number.parse().ok()?;
+    if value.is_finite() {
+        Some(number)
+    } else {
+        None
+    }
+}
+
+/// Outline `rect` in red that fades out after a paste was rejected at `rejected_time`.
+pub(crate) fn paint_rejected_paste(ui: &mut Ui, rect: Rect, rejected_time: f64) {
+    let age = ui.input().time - rejected_time;
+    if (0.0..REJECTED_PASTE_FLASH_TIME).contains(&age) {
+        let fade = 1.0 - (age / REJECTED_PASTE_FLASH_TIME) as f32;
+        ui.add_paint_cmd(PaintCmd::Rect {
+            rect,
+            corner_radius: 2.0,
+            fill: None,
+            outline: Some(LineStyle::new(2.0, color::RED.multiply(fade))),
+        });
+    }
 }
 
-#[derive(Debug)]
 pub struct TextEdit<'t> {
     text: &'t mut String,
     id: Option<Id>,
     text_style: TextStyle, // TODO: Option<TextStyle>, where None means "use the default for the current Ui"
     text_color: Option<Color>,
     multiline: bool,
+    single_line_paste: SingleLinePaste,
+    password: bool,
+}
+
+impl<'t> std::fmt::Debug for TextEdit<'t> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text: &str = if self.password { "<hidden>" } else { self.text };
+        f.debug_struct("TextEdit")
+            .field("text", &text)
+            .field("id", &self.id)
+            .field("text_style", &self.text_style)
+            .field("text_color", &self.text_color)
+            .field("multiline", &self.multiline)
+            .field("single_line_paste", &self.single_line_paste)
+            .field("password", &self.password)
+            .finish()
+    }
 }
 
 impl<'t> TextEdit<'t> {
@@ -25,6 +123,8 @@ impl<'t> TextEdit<'t> {
             text_style: TextStyle::Body,
             text_color: Default::default(),
             multiline: true,
+            single_line_paste: Default::default(),
+            password: false,
         }
     }
 
@@ -33,6 +133,12 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// The id a `TextEdit` with `.id(id_source)` gets when added to `ui`,
+    /// e.g. for `Ui::request_kb_focus` or `Ui::has_kb_focus`.
+    pub fn id_for(ui: &Ui, id_source: impl std::hash::Hash) -> Id {
+        ui.make_child_id(Some(Id::new(id_source)))
+    }
+
     pub fn text_style(mut self, text_style: TextStyle) -> Self {
         self.text_style = text_style;
         self
@@ -47,6 +153,20 @@ impl<'t> TextEdit<'t> {
         self.multiline = multiline;
         self
     }
+
+    /// How to get rid of the line breaks in text pasted into a single-line `TextEdit`.
+    /// Ignored for multiline ones.
+    pub fn single_line_paste(mut self, single_line_paste: SingleLinePaste) -> Self {
+        self.single_line_paste = single_line_paste;
+        self
+    }
+
+    /// Show a `*` for each character instead of the text itself.
+    /// The text can then not be copied, and is left out of the `Debug` output.
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
 }
 
 impl<'t> Widget for TextEdit<'t> {
@@ -57,8 +177,20 @@ impl<'t> Widget for TextEdit<'t> {
             text_style,
             text_color,
             multiline,
+            single_line_paste,
+            password,
         } = self;
 
+        // One char per char, so that the cursor indices are the same for both.
+        // The fonts only have ASCII glyphs for now, so no fancy bullets.
+        let shown_text = |text: &str| {
+            if password {
+                "*".repeat(text.chars().count())
+            } else {
+                text.to_owned()
+            }
+        };
+
         let id = ui.make_child_id(id);
 
         let mut state = ui.memory().text_edit.get(&id).cloned().unwrap_or_default();
@@ -67,9 +199,9 @@ impl<'t> Widget for TextEdit<'t> {
         let line_spacing = font.line_spacing();
         let available_width = ui.available().width();
         let mut galley = if multiline {
-            font.layout_multiline(text.clone(), available_width)
+            font.layout_multiline(shown_text(text), available_width)
         } else {
-            font.layout_single_line(text.clone())
+            font.layout_single_line(shown_text(text))
         };
         let desired_size = galley.size.max(vec2(available_width, line_spacing));
         let rect = ui.allocate_space(desired_size);
@@ -78,41 +210,136 @@ impl<'t> Widget for TextEdit<'t> {
         if interact.clicked {
             ui.request_kb_focus(id);
             if let Some(mouse_pos) = ui.input().mouse.pos {
-                state.cursor = Some(galley.char_at(mouse_pos - interact.rect.min).char_idx);
+                let char_idx = galley.char_at(mouse_pos - interact.rect.min).char_idx;
+                let selection = if interact.triple_clicked {
+                    Some(paragraph_range(text, char_idx))
+                } else if interact.double_clicked {
+                    Some(word_range(text, char_idx))
+                } else {
+                    None
+                };
+                if let Some((start, end)) = selection {
+                    state.selection_anchor = Some(start);
+                    state.cursor = Some(end);
+                } else {
+                    state.selection_anchor = None;
+                    state.cursor = Some(char_idx);
+                }
             }
         }
         if interact.hovered {
             ui.output().cursor_icon = CursorIcon::Text;
         }
+        ui.interested_in_kb_focus(id);
         let has_kb_focus = ui.has_kb_focus(id);
 
+        let mut changed = false;
+        let mut pressed_return = false;
         if has_kb_focus {
+            let text_before = text.clone();
             let mut cursor = state.cursor.unwrap_or_else(|| text.chars().count());
             cursor = clamp(cursor, 0..=text.chars().count());
+            let mut selection = state
+                .selection_anchor
+                .map(|anchor| (anchor.min(cursor), anchor.max(cursor)))
+                .filter(|(start, end)| start < end && *end <= text.chars().count());
 
             for event in &ui.input().events {
+                // Typing or deleting replaces the selection:
+                let replaces_selection = match event {
+                    Event::Text(text_to_insert) => text_to_insert != "\t",
+                    Event::Paste(_) | Event::CompositionEnd(_) => true,
+                    Event::Key {
+                        key: Key::Backspace,
+                        pressed: true,
+                    }
+                    | Event::Key {
+                        key: Key::Delete,
+                        pressed: true,
+                    } => true,
+                    _ => false,
+                };
+                if replaces_selection {
+                    if let Some((start, end)) = selection.take() {
+                        delete_range(text, start, end);
+                        cursor = start;
+                        if let Event::Key { .. } = event {
+                            continue;
+                        }
+                    }
+                }
+
                 match event {
-                    Event::Copy | Event::Cut => {
-                        // TODO: cut
-                        ui.ctx().output().copied_text = text.clone();
+                    Event::Copy if !password => {
+                        ui.ctx().output().copied_text = match selection {
+                            Some((start, end)) => char_range(text, start, end).to_owned(),
+                            None => text.clone(),
+                        };
                     }
-                    Event::Text(text_to_insert) => {
+                    Event::Cut if !password => {
+                        // Nothing selected means nothing to cut.
+                        if let Some((start, end)) = selection.take() {
+                            ui.ctx().output().copied_text = char_range(text, start, end).to_owned();
+                            delete_range(text, start, end);
+                            cursor = start;
+                        }
+                    }
+                    // Tab moves the keyboard focus instead:
+                    Event::Text(text_to_insert) if text_to_insert == "\t" => {}
+                    Event::Text(text_to_insert) if multiline => {
                         insert_text(&mut cursor, text, text_to_insert);
                     }
+                    Event::Text(text_to_insert) => {
+                        // Also stops the enter key from adding a newline.
+                        let text_to_insert = single_line_paste.sanitize(text_to_insert);
+                        insert_text(&mut cursor, text, &text_to_insert);
+                    }
+                    Event::Paste(pasted) if multiline => insert_text(&mut cursor, text, pasted),
+                    Event::Paste(pasted) => match single_line_paste {
+                        SingleLinePaste::Value(clean_up) => match clean_up(pasted) {
+                            Some(value) => {
+                                *text = value;
+                                cursor = text.chars().count();
+                            }
+                            None => state.rejected_paste_time = Some(ui.input().time),
+                        },
+                        _ => insert_text(&mut cursor, text, &single_line_paste.sanitize(pasted)),
+                    },
+                    Event::Key {
+                        key: Key::Return,
+                        pressed: true,
+                    } if !multiline => pressed_return = true,
                     Event::Key { key, pressed: true } => {
+                        selection = None;
                         on_key_press(&mut cursor, text, *key);
                     }
+                    Event::CompositionStart => state.preedit.clear(),
+                    Event::CompositionUpdate(preedit) => state.preedit = preedit.clone(),
+                    Event::CompositionEnd(text_to_insert) => {
+                        state.preedit.clear();
+                        let text_to_insert = if multiline {
+                            text_to_insert.clone()
+                        } else {
+                            single_line_paste.sanitize(text_to_insert)
+                        };
+                        insert_text(&mut cursor, text, &text_to_insert);
+                    }
                     _ => {}
                 }
             }
             state.cursor = Some(cursor);
+            state.selection_anchor =
+                selection.map(|(start, end)| if cursor == start { end } else { start });
+            changed = *text != text_before;
 
-            // layout again to avoid frame delay:
+            // layout again to avoid frame delay, with the preedit text at the cursor:
+            let mut text_with_preedit = text.clone();
+            insert_text(&mut cursor.clone(), &mut text_with_preedit, &state.preedit);
             let font = &ui.fonts()[text_style];
             galley = if multiline {
-                font.layout_multiline(text.clone(), available_width)
+                font.layout_multiline(shown_text(&text_with_preedit), available_width)
             } else {
-                font.layout_single_line(text.clone())
+                font.layout_single_line(shown_text(&text_with_preedit))
             };
 
             // dbg!(&galley);
@@ -126,31 +353,75 @@ impl<'t> Widget for TextEdit<'t> {
                 fill: Some(ui.style().dark_bg_color),
                 outline: ui.style().interact.style(&interact).rect_outline,
             });
+            if let Some(rejected_time) = state.rejected_paste_time {
+                paint_rejected_paste(ui, bg_rect, rejected_time);
+            }
+        }
+
+        if !has_kb_focus {
+            state.selection_anchor = None;
         }
 
         if has_kb_focus {
+            let cursor = state.cursor.unwrap_or_default();
+            if let Some(anchor) = state.selection_anchor {
+                for i in anchor.min(cursor)..anchor.max(cursor) {
+                    let start = interact.rect.min + galley.char_start_pos(i);
+                    let end = interact.rect.min + galley.char_start_pos(i + 1);
+                    if start.y == end.y {
+                        ui.add_paint_cmd(PaintCmd::Rect {
+                            rect: Rect::from_min_max(start, pos2(end.x, start.y + line_spacing)),
+                            corner_radius: 0.0,
+                            fill: Some(ui.style().selection_color),
+                            outline: None,
+                        });
+                    }
+                }
+            }
+
+            let preedit_len = state.preedit.chars().count();
+            for i in cursor..cursor + preedit_len {
+                let start = interact.rect.min + galley.char_start_pos(i);
+                let end = interact.rect.min + galley.char_start_pos(i + 1);
+                if start.y == end.y {
+                    let y = start.y + line_spacing;
+                    ui.add_paint_cmd(PaintCmd::line_segment(
+                        [pos2(start.x, y), pos2(end.x, y)],
+                        text_color.unwrap_or(color::WHITE),
+                        1.0,
+                    ));
+                }
+            }
+
+            let cursor_pos = interact.rect.min + galley.char_start_pos(cursor + preedit_len);
+            ui.output().text_cursor = Some(cursor_pos + vec2(0.0, line_spacing));
+
             let cursor_blink_hz = ui.style().cursor_blink_hz;
             let show_cursor =
                 (ui.input().time * cursor_blink_hz as f64 * 3.0).floor() as i64 % 3 != 0;
             if show_cursor {
-                if let Some(cursor) = state.cursor {
-                    let cursor_pos = interact.rect.min + galley.char_start_pos(cursor);
-                    ui.add_paint_cmd(PaintCmd::line_segment(
-                        [cursor_pos, cursor_pos + vec2(0.0, line_spacing)],
-                        color::WHITE,
-                        ui.style().text_cursor_width,
-                    ));
-                }
+                ui.add_paint_cmd(PaintCmd::line_segment(
+                    [cursor_pos, cursor_pos + vec2(0.0, line_spacing)],
+                    color::WHITE,
+                    ui.style().text_cursor_width,
+                ));
             }
         }
 
         ui.add_galley(interact.rect.min, galley, text_style, text_color);
+
+        let committed = pressed_return || (state.had_kb_focus && !has_kb_focus);
+        state.had_kb_focus = has_kb_focus;
         ui.memory().text_edit.insert(id, state);
-        interact
+        InteractInfo {
+            changed,
+            committed,
+            ..interact
+        }
     }
 }
 
-fn insert_text(cursor: &mut usize, text: &mut String, text_to_insert: &str) {
+pub(crate) fn insert_text(cursor: &mut usize, text: &mut String, text_to_insert: &str) {
     // eprintln!("insert_text {:?}", text_to_insert);
 
     let mut char_it = text.chars();
@@ -165,7 +436,55 @@ fn insert_text(cursor: &mut usize, text: &mut String, text_to_insert: &str) {
     *text = new_text;
 }
 
-fn on_key_press(cursor: &mut usize, text: &mut String, key: Key) {
+/// The slice of `text` between the char indices `start` and `end`.
+fn char_range(text: &str, start: usize, end: usize) -> &str {
+    let byte_idx = |char_idx| {
+        text.char_indices()
+            .nth(char_idx)
+            .map(|(byte_idx, _)| byte_idx)
+            .unwrap_or_else(|| text.len())
+    };
+    &text[byte_idx(start)..byte_idx(end)]
+}
+
+fn delete_range(text: &mut String, start: usize, end: usize) {
+    *text = text
+        .chars()
+        .enumerate()
+        .filter(|(i, _)| *i < start || end <= *i)
+        .map(|(_, c)| c)
+        .collect();
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The word around the char index `char_idx`. A non-word char is selected on its own.
+fn word_range(text: &str, char_idx: usize) -> (usize, usize) {
+    let chars: Vec<char> = text.chars().collect();
+    if char_idx >= chars.len() || !is_word_char(chars[char_idx]) {
+        return (char_idx, (char_idx + 1).min(chars.len()));
+    }
+    let mut start = char_idx;
+    while start > 0 && is_word_char(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = char_idx;
+    while end < chars.len() && is_word_char(chars[end]) {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// The paragraph around the char index `char_idx`, not including its line break.
+fn paragraph_range(text: &str, char_idx: usize) -> (usize, usize) {
+    let (line, _) = line_col_from_char_idx(text, char_idx);
+    let start = char_idx_from_line_col(text, (line, 0));
+    (start, start + line_from_number(text, line).chars().count())
+}
+
+pub(crate) fn on_key_press(cursor: &mut usize, text: &mut String, key: Key) {
     // eprintln!("on_key_press before: '{}', cursor at {}", text, cursor);
 
     match key {
@@ -260,3 +579,35 @@ fn line_from_number(s: &str, desired_line_number: usize) -> &str {
     }
     s
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_paste_sanitize() {
+        let pasted = "first\r\nsecond\nthird";
+        assert_eq!(
+            SingleLinePaste::StripNewlines.sanitize(pasted),
+            "firstsecondthird"
+        );
+        assert_eq!(SingleLinePaste::FirstLine.sanitize(pasted), "first");
+        assert_eq!(SingleLinePaste::FirstLine.sanitize(""), "");
+        assert_eq!(SingleLinePaste::FirstLine.sanitize("\nsecond"), "");
+    }
+
+    #[test]
+    fn lenient_numbers() {
+        assert_eq!(lenient_number(" 42 \n"), Some("42".to_owned()));
+        assert_eq!(lenient_number("+1 000"), Some("1000".to_owned()));
+        assert_eq!(lenient_number("1,000,000"), Some("1000000".to_owned()));
+        assert_eq!(lenient_number("1,000.5"), Some("1000.5".to_owned()));
+        assert_eq!(lenient_number("1,5"), Some("1.5".to_owned()));
+        assert_eq!(lenient_number("\u{2212}3"), Some("-3".to_owned()));
+        assert_eq!(lenient_number("1e3"), Some("1e3".to_owned()));
+        assert_eq!(lenient_number("abc"), None);
+        assert_eq!(lenient_number(""), None);
+        assert_eq!(lenient_number("inf"), None);
+        assert_eq!(lenient_number("NaN"), None);
+    }
+}
@@ -2,8 +2,13 @@
 #![warn(clippy::all)]
 #![allow(clippy::single_match)]
 mod painter;
+mod storage;
 
-pub use painter::Painter;
+pub use {
+    egui::storage::{get_value, load_memory_and_style, save_memory_and_style, set_value},
+    painter::Painter,
+    storage::FileStorage,
+};
 
 use {
     clipboard::{ClipboardContext, ClipboardProvider},
@@ -32,17 +37,32 @@ pub fn input_event(
         glutin::Event::WindowEvent { event, .. } => match event {
             CloseRequested | Destroyed => *running = false,
 
+            Focused(focused) => raw_input.focused = focused,
             Resized(glutin::dpi::LogicalSize { width, height }) => {
                 raw_input.screen_size = vec2(width as f32, height as f32);
             }
-            MouseInput { state, .. } => {
-                raw_input.mouse_down = state == glutin::ElementState::Pressed;
+            MouseInput {
+                state,
+                button,
+                modifiers,
+                ..
+            } => {
+                raw_input.modifiers = Some(translate_modifiers(modifiers));
+                let down = state == glutin::ElementState::Pressed;
+                match button {
+                    glutin::MouseButton::Right => raw_input.secondary_mouse_down = down,
+                    glutin::MouseButton::Middle => raw_input.middle_mouse_down = down,
+                    _ => raw_input.mouse_down = down,
+                }
             }
             CursorMoved { position, .. } => {
                 raw_input.mouse_pos = Some(pos2(position.x as f32, position.y as f32));
             }
             CursorLeft { .. } => {
-                raw_input.mouse_pos = None;
+                // While dragging we keep getting `CursorMoved` outside the window on most platforms:
+                if !raw_input.mouse_down {
+                    raw_input.mouse_pos = None;
+                }
             }
             ReceivedCharacter(ch) => {
                 if !should_ignore_char(ch) {
@@ -54,18 +74,26 @@ pub fn input_event(
                 }
             }
             KeyboardInput { input, .. } => {
+                raw_input.modifiers = Some(translate_modifiers(input.modifiers));
                 if let Some(virtual_keycode) = input.virtual_keycode {
                     // TODO: If mac
                     if input.modifiers.logo && virtual_keycode == VirtualKeyCode::Q {
                         *running = false;
                     }
 
-                    match virtual_keycode {
-                        VirtualKeyCode::Paste => {
+                    let pressed = input.state == glutin::ElementState::Pressed;
+                    let is_command = input.modifiers.ctrl || input.modifiers.logo;
+                    // The dedicated keys, or Ctrl+V etc (Cmd+V on Mac):
+                    let is_shortcut = |dedicated, letter| {
+                        virtual_keycode == dedicated || (is_command && virtual_keycode == letter)
+                    };
+
+                    if is_shortcut(VirtualKeyCode::Paste, VirtualKeyCode::V) {
+                        if pressed {
                             if let Some(clipboard) = clipboard {
                                 match clipboard.get_contents() {
                                     Ok(contents) => {
-                                        raw_input.events.push(Event::Text(contents));
+                                        raw_input.events.push(Event::Paste(contents));
                                     }
                                     Err(err) => {
                                         eprintln!("Paste error: {}", err);
@@ -73,27 +101,41 @@ pub fn input_event(
                                 }
                             }
                         }
-                        VirtualKeyCode::Copy => raw_input.events.push(Event::Copy),
-                        VirtualKeyCode::Cut => raw_input.events.push(Event::Cut),
-                        _ => {
-                            if let Some(key) = translate_virtual_key_code(virtual_keycode) {
-                                raw_input.events.push(Event::Key {
-                                    key,
-                                    pressed: input.state == glutin::ElementState::Pressed,
-                                });
-                            }
+                    } else if is_shortcut(VirtualKeyCode::Copy, VirtualKeyCode::C) {
+                        if pressed {
+                            raw_input.events.push(Event::Copy);
+                        }
+                    } else if is_shortcut(VirtualKeyCode::Cut, VirtualKeyCode::X) {
+                        if pressed {
+                            raw_input.events.push(Event::Cut);
                         }
+                    } else if let Some(key) = translate_virtual_key_code(virtual_keycode) {
+                        raw_input.events.push(Event::Key { key, pressed });
                     }
                 }
             }
+            Touch(touch) => {
+                let phase = match touch.phase {
+                    glutin::TouchPhase::Started => TouchPhase::Start,
+                    glutin::TouchPhase::Moved => TouchPhase::Move,
+                    glutin::TouchPhase::Ended => TouchPhase::End,
+                    glutin::TouchPhase::Cancelled => TouchPhase::Cancel,
+                };
+                raw_input.events.push(Event::Touch {
+                    id: touch.id,
+                    phase,
+                    pos: pos2(touch.location.x as f32, touch.location.y as f32),
+                });
+            }
             MouseWheel { delta, .. } => {
+                // Accumulate: a trackpad can send many small events per frame.
                 match delta {
                     glutin::MouseScrollDelta::LineDelta(x, y) => {
-                        raw_input.scroll_delta = vec2(x, y) * 24.0;
+                        raw_input.scroll_notches += vec2(x, y);
                     }
                     glutin::MouseScrollDelta::PixelDelta(delta) => {
                         // Actually point delta
-                        raw_input.scroll_delta = vec2(delta.x as f32, delta.y as f32);
+                        raw_input.scroll_delta += vec2(delta.x as f32, delta.y as f32);
                     }
                 }
             }
@@ -120,17 +162,28 @@ fn should_ignore_char(chr: char) -> bool {
         '\u{f72c}' |  // page up
         '\u{f72d}' |  // page down
         '\u{f710}' |  // print screen
-        '\u{f704}' | '\u{f705}'  // F1, F2, ...
+        '\u{f704}' | '\u{f705}' | // F1, F2, ...
+        '\u{1}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1a}' // Ctrl+letter, e.g. Ctrl+C
         => true,
         _ => false,
     }
 }
 
+pub fn translate_modifiers(modifiers: glutin::ModifiersState) -> Modifiers {
+    Modifiers {
+        alt: modifiers.alt,
+        ctrl: modifiers.ctrl,
+        shift: modifiers.shift,
+        logo: modifiers.logo,
+    }
+}
+
 pub fn translate_virtual_key_code(key: glutin::VirtualKeyCode) -> Option<egui::Key> {
     use VirtualKeyCode::*;
 
     Some(match key {
         Escape => Key::Escape,
+        F2 => Key::F2,
         Insert => Key::Insert,
         Home => Key::Home,
         Delete => Key::Delete,
@@ -146,6 +199,33 @@ pub fn translate_virtual_key_code(key: glutin::VirtualKeyCode) -> Option<egui::K
         // Space => Key::Space,
         Tab => Key::Tab,
 
+        A => Key::A,
+        B => Key::B,
+        C => Key::C,
+        D => Key::D,
+        E => Key::E,
+        F => Key::F,
+        G => Key::G,
+        H => Key::H,
+        I => Key::I,
+        J => Key::J,
+        K => Key::K,
+        L => Key::L,
+        M => Key::M,
+        N => Key::N,
+        O => Key::O,
+        P => Key::P,
+        Q => Key::Q,
+        R => Key::R,
+        S => Key::S,
+        T => Key::T,
+        U => Key::U,
+        V => Key::V,
+        W => Key::W,
+        X => Key::X,
+        Y => Key::Y,
+        Z => Key::Z,
+
         LAlt | RAlt => Key::Alt,
         LShift | RShift => Key::Shift,
         LControl | RControl => Key::Control,
@@ -166,6 +246,8 @@ pub fn translate_cursor(cursor_icon: egui::CursorIcon) -> glutin::MouseCursor {
         CursorIcon::ResizeNwSe => glutin::MouseCursor::NwseResize,
         CursorIcon::ResizeVertical => glutin::MouseCursor::NsResize,
         CursorIcon::Text => glutin::MouseCursor::Text,
+        CursorIcon::Grab => glutin::MouseCursor::Grab,
+        CursorIcon::Grabbing => glutin::MouseCursor::Grabbing,
     }
 }
 
@@ -195,30 +277,23 @@ pub fn handle_output(
 
 // ----------------------------------------------------------------------------
 
+#[deprecated = "Use `load_memory_and_style` with a `FileStorage` instead"]
 pub fn read_memory(ctx: &Context, memory_json_path: impl AsRef<std::path::Path>) {
-    match std::fs::File::open(memory_json_path) {
-        Ok(file) => {
-            let reader = std::io::BufReader::new(file);
-            match serde_json::from_reader(reader) {
-                Ok(memory) => {
-                    *ctx.memory() = memory;
-                }
-                Err(err) => {
-                    eprintln!("ERROR: Failed to parse memory json: {}", err);
-                }
-            }
-        }
-        Err(_err) => {
-            // File probably doesn't exist. That's fine.
-        }
+    let storage = FileStorage::from_path(memory_json_path.as_ref());
+    if let Some(memory) = get_value(&storage, egui::storage::MEMORY_KEY) {
+        *ctx.memory() = memory;
     }
 }
 
+/// Note that the file is now in the format of `FileStorage`.
+#[deprecated = "Use `save_memory_and_style` with a `FileStorage` instead"]
 pub fn write_memory(
     ctx: &Context,
     memory_json_path: impl AsRef<std::path::Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    serde_json::to_writer_pretty(std::fs::File::create(memory_json_path)?, &*ctx.memory())?;
+    let mut storage = FileStorage::from_path(memory_json_path.as_ref());
+    set_value(&mut storage, egui::storage::MEMORY_KEY, &*ctx.memory());
+    storage.flush();
     Ok(())
 }
 
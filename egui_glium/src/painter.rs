@@ -2,16 +2,19 @@
 
 use {
     egui::{
-        paint::{PaintBatches, Triangles},
+        paint::{Color, PaintBatches, TextureId, Triangles},
         Rect,
     },
     glium::{implement_vertex, index::PrimitiveType, program, texture, uniform, Frame, Surface},
+    std::collections::HashMap,
 };
 
 pub struct Painter {
     program: glium::Program,
     texture: texture::texture2d::Texture2d,
     current_texture_id: Option<u64>,
+    user_textures: HashMap<u64, texture::SrgbTexture2d>,
+    next_user_texture_id: u64,
 }
 
 impl Painter {
@@ -46,6 +49,7 @@ impl Painter {
                     fragment: "
                         #version 140
                         uniform sampler2D u_sampler;
+                        uniform bool u_user_texture;
                         in vec2 v_pos;
                         in vec4 v_color;
                         in vec2 v_tc;
@@ -67,7 +71,11 @@ impl Painter {
                             if (v_pos.y > v_clip_rect.w) { discard; }
                             f_color = v_color;
                             f_color.rgb = linear_from_srgb(f_color.rgb);
-                            f_color *= texture(u_sampler, v_tc).r;
+                            if (u_user_texture) {
+                                f_color *= texture(u_sampler, v_tc);
+                            } else {
+                                f_color *= texture(u_sampler, v_tc).r;
+                            }
                         }
                     "
             },
@@ -101,6 +109,7 @@ impl Painter {
                     fragment: "
                         #version 110
                         uniform sampler2D u_sampler;
+                        uniform bool u_user_texture;
                         varying vec2 v_pos;
                         varying vec4 v_color;
                         varying vec2 v_tc;
@@ -121,7 +130,11 @@ impl Painter {
                             if (v_pos.y > v_clip_rect.w) { discard; }
                             gl_FragColor = v_color;
                             gl_FragColor.rgb = linear_from_srgb(gl_FragColor.rgb);
-                            gl_FragColor *= texture2D(u_sampler, v_tc).r;
+                            if (u_user_texture) {
+                                gl_FragColor *= texture2D(u_sampler, v_tc);
+                            } else {
+                                gl_FragColor *= texture2D(u_sampler, v_tc).r;
+                            }
                         }
                     ",
             },
@@ -155,6 +168,7 @@ impl Painter {
                     fragment: "
                         #version 100
                         uniform sampler2D u_sampler;
+                        uniform bool u_user_texture;
                         varying mediump vec2 v_pos;
                         varying mediump vec4 v_color;
                         varying mediump vec2 v_tc;
@@ -175,7 +189,11 @@ impl Painter {
                             if (v_pos.y > v_clip_rect.w) { discard; }
                             gl_FragColor = v_color;
                             gl_FragColor.rgb = linear_from_srgb(gl_FragColor.rgb);
-                            gl_FragColor *= texture2D(u_sampler, v_tc).r;
+                            if (u_user_texture) {
+                                gl_FragColor *= texture2D(u_sampler, v_tc);
+                            } else {
+                                gl_FragColor *= texture2D(u_sampler, v_tc).r;
+                            }
                         }
                     ",
             },
@@ -192,6 +210,36 @@ impl Painter {
             program,
             texture,
             current_texture_id: None,
+            user_textures: Default::default(),
+            next_user_texture_id: 0,
+        }
+    }
+
+    /// Upload an image for use with `egui::Image` (or your own `Triangles`).
+    /// `pixels` are sRGBA with premultiplied alpha, row by row, top to bottom.
+    pub fn new_user_texture(
+        &mut self,
+        facade: &dyn glium::backend::Facade,
+        size: (usize, usize),
+        pixels: &[Color],
+    ) -> TextureId {
+        assert_eq!(size.0 * size.1, pixels.len());
+
+        let pixels: Vec<Vec<(u8, u8, u8, u8)>> = pixels
+            .chunks(size.0)
+            .map(|row| row.iter().map(|c| (c.r, c.g, c.b, c.a)).collect())
+            .collect();
+        let texture = texture::SrgbTexture2d::new(facade, pixels).unwrap();
+
+        let id = self.next_user_texture_id;
+        self.next_user_texture_id += 1;
+        self.user_textures.insert(id, texture);
+        TextureId::User(id)
+    }
+
+    pub fn free_user_texture(&mut self, id: TextureId) {
+        if let TextureId::User(id) = id {
+            self.user_textures.remove(&id);
         }
     }
 
@@ -270,12 +318,13 @@ impl Painter {
         let width_points = width_pixels as f32 / pixels_per_point;
         let height_points = height_pixels as f32 / pixels_per_point;
 
-        let uniforms = uniform! {
-            u_clip_rect: [clip_rect.min.x, clip_rect.min.y, clip_rect.max.x, clip_rect.max.y],
-            u_screen_size: [width_points, height_points],
-            u_tex_size: [texture.width as f32, texture.height as f32],
-            u_sampler: &self.texture,
-        };
+        let u_clip_rect = [
+            clip_rect.min.x,
+            clip_rect.min.y,
+            clip_rect.max.x,
+            clip_rect.max.y,
+        ];
+        let u_screen_size = [width_points, height_points];
 
         // Emilib outputs colors with premultiplied alpha:
         let blend_func = glium::BlendingFunction::Addition {
@@ -293,14 +342,48 @@ impl Painter {
             ..Default::default()
         };
 
-        target
-            .draw(
-                &vertex_buffer,
-                &index_buffer,
-                &self.program,
-                &uniforms,
-                &params,
-            )
-            .unwrap();
+        match triangles.texture_id {
+            TextureId::Egui => {
+                let uniforms = uniform! {
+                    u_clip_rect: u_clip_rect,
+                    u_screen_size: u_screen_size,
+                    u_tex_size: [texture.width as f32, texture.height as f32],
+                    u_sampler: &self.texture,
+                    u_user_texture: false,
+                };
+                target
+                    .draw(
+                        &vertex_buffer,
+                        &index_buffer,
+                        &self.program,
+                        &uniforms,
+                        &params,
+                    )
+                    .unwrap();
+            }
+            TextureId::User(id) => {
+                let user_texture = match self.user_textures.get(&id) {
+                    Some(user_texture) => user_texture,
+                    None => return, // Freed, or never allocated by us
+                };
+                let uniforms = uniform! {
+                    u_clip_rect: u_clip_rect,
+                    u_screen_size: u_screen_size,
+                    // User textures have uv normalized to u16::MAX:
+                    u_tex_size: [u16::MAX as f32, u16::MAX as f32],
+                    u_sampler: user_texture,
+                    u_user_texture: true,
+                };
+                target
+                    .draw(
+                        &vertex_buffer,
+                        &index_buffer,
+                        &self.program,
+                        &uniforms,
+                        &params,
+                    )
+                    .unwrap();
+            }
+        }
     }
 }
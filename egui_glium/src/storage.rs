@@ -0,0 +1,71 @@
+use std::{collections::HashMap, path::PathBuf};
+
+/// A key-value store backed by a json file, written on `flush`.
+pub struct FileStorage {
+    path: PathBuf,
+    kv: HashMap<String, String>,
+    dirty: bool,
+}
+
+impl FileStorage {
+    /// Reads the file if it exists. It is only created on the first `flush`.
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            kv: read_json(&path).unwrap_or_default(),
+            path,
+            dirty: false,
+        }
+    }
+}
+
+impl egui::Storage for FileStorage {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.kv.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        if self.kv.get(key) != Some(&value) {
+            self.kv.insert(key.to_owned(), value);
+            self.dirty = true;
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.dirty {
+            match std::fs::File::create(&self.path) {
+                Ok(file) => {
+                    if let Err(err) = serde_json::to_writer_pretty(file, &self.kv) {
+                        eprintln!("ERROR: Failed to write {}: {}", self.path.display(), err);
+                    }
+                }
+                Err(err) => {
+                    eprintln!("ERROR: Failed to create {}: {}", self.path.display(), err);
+                }
+            }
+            self.dirty = false;
+        }
+    }
+}
+
+fn read_json<T>(path: impl AsRef<std::path::Path>) -> Option<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    match std::fs::File::open(path) {
+        Ok(file) => {
+            let reader = std::io::BufReader::new(file);
+            match serde_json::from_reader(reader) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    eprintln!("ERROR: Failed to parse json: {}", err);
+                    None
+                }
+            }
+        }
+        Err(_err) => {
+            // File probably doesn't exist. That's fine.
+            None
+        }
+    }
+}
@@ -3,6 +3,8 @@
 
 pub mod webgl;
 
+pub use egui::storage::{get_value, load_memory_and_style, save_memory_and_style, set_value};
+
 // ----------------------------------------------------------------------------
 // Helpers to hide some of the verbosity of web_sys
 
@@ -35,29 +37,35 @@ pub fn local_storage_remove(key: &str) {
     local_storage().map(|storage| storage.remove_item(key));
 }
 
+/// `egui::Storage` using the `localStorage` of the browser.
+/// Every `set_string` is written at once, so there is nothing to flush.
+#[derive(Default)]
+pub struct LocalStorage {}
+
+impl egui::Storage for LocalStorage {
+    fn get_string(&self, key: &str) -> Option<String> {
+        local_storage_get(key)
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        local_storage_set(key, &value);
+    }
+
+    fn flush(&mut self) {}
+}
+
+#[deprecated = "Use `load_memory_and_style` with a `LocalStorage` instead"]
 pub fn load_memory(ctx: &egui::Context) {
-    if let Some(memory_string) = local_storage_get("egui_memory_json") {
-        match serde_json::from_str(&memory_string) {
-            Ok(memory) => {
-                *ctx.memory() = memory;
-            }
-            Err(err) => {
-                console_log(format!("ERROR: Failed to parse memory json: {}", err));
-            }
-        }
+    if let Some(memory) = get_value(&LocalStorage::default(), egui::storage::MEMORY_KEY) {
+        *ctx.memory() = memory;
     }
 }
 
+#[deprecated = "Use `save_memory_and_style` with a `LocalStorage` instead"]
 pub fn save_memory(ctx: &egui::Context) {
-    match serde_json::to_string(&*ctx.memory()) {
-        Ok(json) => {
-            local_storage_set("egui_memory_json", &json);
-        }
-        Err(err) => {
-            console_log(format!(
-                "ERROR: Failed to seriealize memory as json: {}",
-                err
-            ));
-        }
-    }
+    set_value(
+        &mut LocalStorage::default(),
+        egui::storage::MEMORY_KEY,
+        &*ctx.memory(),
+    );
 }
@@ -5,7 +5,7 @@ use {
 };
 
 use egui::{
-    paint::{Color, PaintBatches, Texture, Triangles},
+    paint::{Color, PaintBatches, Texture, TextureId, Triangles},
     vec2, Pos2,
 };
 
@@ -22,6 +22,8 @@ pub struct Painter {
     color_buffer: WebGlBuffer,
     tex_size: (u16, u16),
     current_texture_id: Option<u64>,
+    /// User textures are not supported yet, which we only want to log once.
+    logged_user_texture: bool,
 }
 
 impl Painter {
@@ -121,6 +123,7 @@ impl Painter {
             color_buffer,
             tex_size: (0, 0),
             current_texture_id: None,
+            logged_user_texture: false,
         })
     }
 
@@ -213,6 +216,18 @@ impl Painter {
         gl.clear(Gl::COLOR_BUFFER_BIT);
 
         for (clip_rect, triangles) in batches {
+            if let TextureId::User(id) = triangles.texture_id {
+                // TODO: support user textures
+                if !self.logged_user_texture {
+                    crate::console_log(format!(
+                        "egui_wasm: user textures are not supported yet, so TextureId::User({}) is not painted (logged only once)",
+                        id
+                    ));
+                    self.logged_user_texture = true;
+                }
+                continue;
+            }
+
             // Avoid infinities in shader:
             let clip_min = clip_rect.min.max(Pos2::default());
             let clip_max = clip_rect.max.min(Pos2::default() + screen_size_points);
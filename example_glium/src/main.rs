@@ -14,36 +14,25 @@ struct Window {
     size: Option<Vec2>,
 }
 
-fn read_json<T>(memory_json_path: impl AsRef<std::path::Path>) -> Option<T>
-where
-    T: serde::de::DeserializeOwned,
-{
-    match std::fs::File::open(memory_json_path) {
-        Ok(file) => {
-            let reader = std::io::BufReader::new(file);
-            match serde_json::from_reader(reader) {
-                Ok(value) => Some(value),
-                Err(err) => {
-                    eprintln!("ERROR: Failed to parse json: {}", err);
-                    None
-                }
-            }
-        }
-        Err(_err) => {
-            // File probably doesn't exist. That's fine.
-            None
-        }
-    }
-}
-
 fn main() {
-    // TODO: combine
-    let memory_path = "egui.json";
-    let settings_json_path: &str = "window.json";
-    let app_json_path: &str = "example_app.json";
+    let mut storage = egui_glium::FileStorage::from_path("egui.json");
+
+    // Run with `EGUI_RECORD=input.json` to record the input of the session,
+    // and with `EGUI_REPLAY=input.json` to play it back from a clean state.
+    let record_path = std::env::var("EGUI_RECORD").ok();
+    let replay: Option<InputRecording> = std::env::var("EGUI_REPLAY").ok().map(|path| {
+        let file = std::fs::File::open(&path).expect("Failed to open EGUI_REPLAY file");
+        serde_json::from_reader(std::io::BufReader::new(file)).expect("Bad EGUI_REPLAY file")
+    });
+    let mut player = replay.as_ref().map(InputRecording::player);
+    let mut recording = InputRecording::default();
 
-    let mut example_app: ExampleApp = read_json(app_json_path).unwrap_or_default();
-    let mut window_settings: Window = read_json(settings_json_path).unwrap_or_default();
+    let mut example_app: ExampleApp = if replay.is_some() {
+        Default::default()
+    } else {
+        egui_glium::get_value(&storage, "example_app").unwrap_or_default()
+    };
+    let mut window_settings: Window = egui_glium::get_value(&storage, "window").unwrap_or_default();
 
     let mut events_loop = glutin::EventsLoop::new();
     let window = glutin::WindowBuilder::new().with_title("Egui example");
@@ -72,6 +61,18 @@ fn main() {
         egui_glium::Painter::new(&display)
     });
 
+    // A user texture, to show off `Image`:
+    let gradient_texture_id = {
+        let size = (64, 64);
+        let pixels: Vec<Color> = (0..size.0 * size.1)
+            .map(|i| {
+                let (x, y) = (i % size.0, i / size.0);
+                color::srgba((4 * x) as u8, (4 * y) as u8, 128, 255)
+            })
+            .collect();
+        painter.new_user_texture(&display, size, &pixels)
+    };
+
     let mut raw_input = egui::RawInput {
         screen_size: {
             let (width, height) = display.get_framebuffer_dimensions();
@@ -88,7 +89,9 @@ fn main() {
     let mut frame_times = egui::MovementTracker::new(1000, 1.0);
     let mut clipboard = egui_glium::init_clipboard();
 
-    egui_glium::read_memory(&ctx, memory_path);
+    if replay.is_none() {
+        egui_glium::load_memory_and_style(&ctx, &storage);
+    }
 
     while running {
         {
@@ -104,21 +107,32 @@ fn main() {
             raw_input.time = start_time.elapsed().as_nanos() as f64 * 1e-9;
             raw_input.seconds_since_midnight = Some(egui_glium::local_time_of_day());
             raw_input.scroll_delta = vec2(0.0, 0.0);
+            raw_input.scroll_notches = vec2(0.0, 0.0);
             raw_input.events.clear();
             events_loop.poll_events(|event| {
                 egui_glium::input_event(event, clipboard.as_mut(), &mut raw_input, &mut running)
             });
         }
 
+        let frame_input = player
+            .as_mut()
+            .and_then(InputPlayer::next_input)
+            .unwrap_or_else(|| raw_input.clone()); // TODO: avoid clone
+        if record_path.is_some() {
+            recording.record(&frame_input);
+        }
+
         let egui_start = Instant::now();
-        let mut ui = ctx.begin_frame(raw_input.clone()); // TODO: avoid clone
-        example_app.ui(&mut ui, "");
-        let mut ui = ui.centered_column(ui.available().width().min(480.0));
+        let mut frame = ctx.begin_frame(frame_input);
+        example_app.ui(&mut frame, "");
+        let width = frame.available().width().min(480.0);
+        let mut ui = frame.centered_column(width);
         ui.set_layout(Layout::vertical(Align::Min));
         ui.add(label!("Egui running inside of Glium").text_style(TextStyle::Heading));
         if ui.add(Button::new("Quit")).clicked {
             running = false;
         }
+        ui.add(Image::new(gradient_texture_id, vec2(64.0, 64.0)));
 
         ui.add(
             label!(
@@ -135,7 +149,7 @@ fn main() {
             .text_style(TextStyle::Monospace),
         );
 
-        let (output, paint_batches) = ctx.end_frame();
+        let (output, paint_batches) = frame.end();
 
         frame_times.add(
             raw_input.time,
@@ -146,6 +160,14 @@ fn main() {
         egui_glium::handle_output(output, &display, clipboard.as_mut());
     }
 
+    if let Some(record_path) = record_path {
+        let file = std::fs::File::create(&record_path).expect("Failed to create EGUI_RECORD file");
+        serde_json::to_writer(file, &recording).expect("Failed to write EGUI_RECORD file");
+    }
+    if replay.is_some() {
+        return; // Don't overwrite the real state with that of the replay
+    }
+
     // Save state to disk:
     window_settings.pos = display
         .gl_window()
@@ -156,18 +178,10 @@ fn main() {
         .get_inner_size()
         .map(|size| vec2(size.width as f32, size.height as f32));
 
-    if let Err(err) = egui_glium::write_memory(&ctx, memory_path) {
-        eprintln!("ERROR: Failed to save egui state: {}", err);
-    }
-
-    serde_json::to_writer_pretty(std::fs::File::create(app_json_path).unwrap(), &example_app)
-        .unwrap();
-
-    serde_json::to_writer_pretty(
-        std::fs::File::create(settings_json_path).unwrap(),
-        &window_settings,
-    )
-    .unwrap();
+    egui_glium::save_memory_and_style(&ctx, &mut storage);
+    egui_glium::set_value(&mut storage, "example_app", &example_app);
+    egui_glium::set_value(&mut storage, "window", &window_settings);
+    storage.flush();
 }
 
 fn profile<R>(name: &str, action: impl FnOnce() -> R) -> R {
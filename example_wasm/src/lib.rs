@@ -33,6 +33,7 @@ pub struct State {
     example_app: ExampleApp,
     ctx: Arc<Context>,
     webgl_painter: egui_wasm::webgl::Painter,
+    storage: egui_wasm::LocalStorage,
 
     frame_times: egui::MovementTracker<f32>,
 }
@@ -40,11 +41,13 @@ pub struct State {
 impl State {
     fn new(canvas_id: &str) -> Result<State, JsValue> {
         let ctx = Context::new();
-        egui_wasm::load_memory(&ctx);
+        let storage = egui_wasm::LocalStorage::default();
+        egui_wasm::load_memory_and_style(&ctx, &storage);
         Ok(State {
             example_app: Default::default(),
             ctx,
             webgl_painter: egui_wasm::webgl::Painter::new(canvas_id)?,
+            storage,
             frame_times: egui::MovementTracker::new(1000, 1.0),
         })
     }
@@ -52,9 +55,11 @@ impl State {
     fn run(&mut self, web_input: WebInput) -> Result<Output, JsValue> {
         let everything_start = now_sec();
 
-        let mut ui = self.ctx.begin_frame(web_input.egui);
-        self.example_app.ui(&mut ui, &web_input.web.location_hash);
-        let mut ui = ui.centered_column(ui.available().width().min(480.0));
+        let mut frame = self.ctx.begin_frame(web_input.egui);
+        self.example_app
+            .ui(&mut frame, &web_input.web.location_hash);
+        let width = frame.available().width().min(480.0);
+        let mut ui = frame.centered_column(width);
         ui.set_layout(Layout::vertical(Align::Min));
         ui.add(label!("Egui!").text_style(TextStyle::Heading));
         ui.label("Egui is an immediate mode GUI written in Rust, compiled to WebAssembly, rendered with WebGL.");
@@ -90,7 +95,7 @@ impl State {
         );
 
         let bg_color = srgba(0, 0, 0, 0); // Use background css color.
-        let (output, batches) = self.ctx.end_frame();
+        let (output, batches) = frame.end();
 
         let now = now_sec();
         self.frame_times.add(now, (now - everything_start) as f32);
@@ -102,7 +107,8 @@ impl State {
             self.ctx.pixels_per_point(),
         )?;
 
-        egui_wasm::save_memory(&self.ctx); // TODO: don't save every frame
+        egui_wasm::save_memory_and_style(&self.ctx, &mut self.storage); // TODO: don't save every frame
+        self.storage.flush();
 
         Ok(output)
     }